@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use std::process;
+
+use async_benchmark::fixture::{self, Schema, Size};
+
+// Writes the input file of the benchmark (see src/fixture.rs):
+//
+//      cargo run --bin generate_fixture                                  10 000 rows in ../data.csv
+//      cargo run --bin generate_fixture -- --bytes 50000000              about 50 MB
+//      cargo run --bin generate_fixture -- --rows 10 --schema id:int,name:text,price:float --seed 7 --output small.csv
+
+fn parse_args() -> Result<(PathBuf, Schema, Size, u64), String> {
+    let mut output = fixture::default_input();
+    let mut schema: Schema = fixture::DEFAULT_SCHEMA.parse()?;
+    let mut size = Size::Rows(10_000);
+    let mut seed = 42;
+    let mut args = std::env::args().skip(1);
+    while let Some(option) = args.next() {
+        let value = args.next().ok_or_else(|| format!("missing value for {}", option))?;
+        let invalid = || format!("invalid value '{}' for {}", value, option);
+        match option.as_str() {
+            "--rows" => size = Size::Rows(value.parse().map_err(|_| invalid())?),
+            "--bytes" => size = Size::Bytes(value.parse().map_err(|_| invalid())?),
+            "--schema" => schema = value.parse()?,
+            "--seed" => seed = value.parse().map_err(|_| invalid())?,
+            "--output" => output = PathBuf::from(value),
+            _ => return Err(format!("unknown option {}", option)),
+        }
+    }
+    Ok((output, schema, size, seed))
+}
+
+fn main() {
+    let (output, schema, size, seed) = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!("usage: generate_fixture [--rows N | --bytes N] [--schema name:type,...] [--seed N] [--output FILE]");
+            process::exit(2);
+        }
+    };
+    match fixture::write_file(&output, &schema, size, seed) {
+        Ok(rows) => println!("{} rows ({}) written to {}", rows, schema, output.display()),
+        Err(error) => {
+            eprintln!("cannot write {}: {}", output.display(), error);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// The command line of the benchmark: which modes to run, and the workload they all run.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::fixture;
+
+/// How the tasks of the workload are run, from the first example (#1) to the last one (#4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// #1: one task after the other, with blocking reads and a blocking sleep.
+    Sync,
+    /// #2: every task is a future of the same tokio task (like `tokio::join!`): the reads and the sleep
+    /// overlap, but a fib blocks all of them.
+    Async,
+    /// #3: every task is spawned on the runtime (`tokio::spawn`), the fib still runs on a worker thread
+    /// of the runtime and holds it.
+    AsyncCpu,
+    /// #4: like AsyncCpu, but the fib runs on a thread of `spawn_blocking`.
+    AsyncSpawnBlocking,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 4] = [Mode::Sync, Mode::Async, Mode::AsyncCpu, Mode::AsyncSpawnBlocking];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Sync => "sync",
+            Mode::Async => "async",
+            Mode::AsyncCpu => "async-cpu",
+            Mode::AsyncSpawnBlocking => "async-spawn-blocking",
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Mode, String> {
+        Mode::ALL.into_iter().find(|mode| mode.name() == name).ok_or_else(|| format!("unknown mode '{}'", name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    Table,
+    Json,
+}
+
+/// The workload: one sleeper, and `tasks` tasks that each read the input file then compute fib(fib_depth).
+/// The input is `../data.csv` from the crate directory by default, see [`fixture::default_input`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub modes: Vec<Mode>,
+    pub tasks: usize,
+    pub fib_depth: u32,
+    pub input: PathBuf,
+    pub sleep: Duration,
+    /// The most tasks running at the same time; None: no limit.
+    pub max_in_flight: Option<usize>,
+    /// The tasks not done after this time are cancelled; None: no timeout.
+    pub timeout: Option<Duration>,
+    pub output: Output,
+    /// Logs what every task does.
+    pub verbose: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            modes: Mode::ALL.to_vec(),
+            tasks: 10,
+            fib_depth: 35,
+            input: fixture::default_input(),
+            sleep: Duration::from_secs(1),
+            max_in_flight: None,
+            timeout: None,
+            output: Output::Table,
+            verbose: false,
+        }
+    }
+}
+
+impl Config {
+    /// Reads `--mode all|sync|async|async-cpu|async-spawn-blocking --tasks N --fib N --input FILE
+    /// --sleep-ms N --max-in-flight N --timeout-ms N --json --verbose`, every option is optional.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut args = args.into_iter();
+        while let Some(option) = args.next() {
+            // the options without a value
+            match option.as_str() {
+                "--json" => {
+                    config.output = Output::Json;
+                    continue;
+                }
+                "--verbose" => {
+                    config.verbose = true;
+                    continue;
+                }
+                _ => {}
+            }
+            let value = args.next().ok_or_else(|| format!("missing value for {}", option))?;
+            let invalid = || format!("invalid value '{}' for {}", value, option);
+            match option.as_str() {
+                "--mode" if value == "all" => config.modes = Mode::ALL.to_vec(),
+                "--mode" => config.modes = vec![value.parse()?],
+                "--tasks" => config.tasks = value.parse().map_err(|_| invalid())?,
+                // fib(48) does not fit in a u32 anymore (and takes minutes)
+                "--fib" => config.fib_depth = value.parse().ok().filter(|depth| *depth <= 47).ok_or_else(invalid)?,
+                "--input" => config.input = PathBuf::from(value),
+                "--sleep-ms" => config.sleep = Duration::from_millis(value.parse().map_err(|_| invalid())?),
+                "--max-in-flight" => config.max_in_flight = Some(value.parse().ok().filter(|max| *max > 0).ok_or_else(invalid)?),
+                "--timeout-ms" => config.timeout = Some(Duration::from_millis(value.parse().map_err(|_| invalid())?)),
+                _ => return Err(format!("unknown option {}", option)),
+            }
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,335 @@
+// Reading the input file line by line, and computing aggregates of its columns on the way:
+//
+//      id,city,temperature        header: the names of the columns
+//      0,Lisbon,21.37             the first row gives the type of every column (int, float or text)
+//      1,"Porto, PT",-3.05        a quoted field can contain commas, and quotes written twice ("")
+//      2,Oslo                     malformed: 2 fields instead of 3, the row is skipped
+//
+// Only one line is in memory at a time, and the aggregates of a column are a few numbers (count, sum,
+// min, max), whatever the size of the file. The rows that cannot be read are counted and skipped;
+// only the first MAX_ERRORS of them are kept, with their line number.
+//
+// The same Aggregator is fed by the async reader (tokio::io::BufReader) and by the sync one
+// (std::io::BufReader), so both give the same results, see tests/csv.rs. Both read raw bytes up to
+// each '\n' (read_until): a line that is not valid UTF-8 is a malformed row, not an error of the whole file.
+
+use std::fmt;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use tokio::io::AsyncBufReadExt;
+
+use crate::fixture::ColumnType;
+
+/// The number of errors kept in [`Aggregates::errors`].
+pub const MAX_ERRORS: usize = 10;
+
+/// A row that could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// 1 is the header line.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Splits a line into its fields. A field in quotes can contain commas, and `""` for a quote.
+/// A quoted field cannot contain a line break: the file is read line by line.
+pub fn split_fields(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err(String::from("unterminated quoted field")),
+                }
+            }
+            // the closing quote ends the field
+            match chars.peek() {
+                None | Some(',') => {}
+                Some(c) => return Err(format!("unexpected '{}' after a quoted field", c)),
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                match c {
+                    ',' => break,
+                    '"' => return Err(String::from("unexpected quote in an unquoted field")),
+                    c => {
+                        field.push(c);
+                        chars.next();
+                    }
+                }
+            }
+        }
+        fields.push(field);
+        // a comma: another field follows, even if it is empty
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+/// A field of a row, typed after its column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl Value {
+    // The type of a column is the type of its first value: "12" is an int, "1.5" a float.
+    fn guess(field: String) -> Value {
+        if let Ok(int) = field.parse() {
+            Value::Int(int)
+        } else if let Some(float) = field.parse().ok().filter(|_| field.contains(|c: char| c.is_ascii_digit())) {
+            // with a digit: "inf" or "NaN" are texts here
+            Value::Float(float)
+        } else {
+            Value::Text(field)
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(int) => Some(*int as f64),
+            Value::Float(float) => Some(*float),
+            Value::Text(_) => None,
+        }
+    }
+}
+
+/// The aggregates of one column. A text column only has a count.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub name: String,
+    pub kind: ColumnType,
+    pub count: usize,
+    pub sum: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl ColumnStats {
+    fn new(name: String) -> Self {
+        // the type is set by the first row
+        ColumnStats { name, kind: ColumnType::Text, count: 0, sum: 0.0, min: None, max: None }
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        match self.kind {
+            ColumnType::Text => None,
+            _ if self.count == 0 => None,
+            _ => Some(self.sum / self.count as f64),
+        }
+    }
+
+    fn add(&mut self, value: &Value) {
+        self.count += 1;
+        // "123" in a text column is guessed as an int, but it is a name, not a number: only counted
+        if self.kind == ColumnType::Text {
+            return;
+        }
+        if let Some(number) = value.as_f64() {
+            self.sum += number;
+            self.min = Some(self.min.map_or(number, |min| min.min(number)));
+            self.max = Some(self.max.map_or(number, |max| max.max(number)));
+        }
+    }
+}
+
+/// The result of reading a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregates {
+    pub columns: Vec<ColumnStats>,
+    /// The rows counted in the aggregates.
+    pub rows: usize,
+    /// The rows skipped because they could not be read.
+    pub malformed: usize,
+    /// The first [`MAX_ERRORS`] malformed rows.
+    pub errors: Vec<RowError>,
+}
+
+impl fmt::Display for Aggregates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} rows, {} malformed", self.rows, self.malformed)?;
+        writeln!(f, "{:<16} {:<6} {:>10} {:>16} {:>12} {:>12} {:>12}", "column", "type", "count", "sum", "min", "max", "mean")?;
+        let number = |value: Option<f64>| value.map_or(String::from("-"), |value| format!("{:.2}", value));
+        for column in &self.columns {
+            let kind = match column.kind {
+                ColumnType::Int => "int",
+                ColumnType::Float => "float",
+                ColumnType::Text => "text",
+            };
+            let sum = if column.kind == ColumnType::Text { None } else { Some(column.sum) };
+            writeln!(
+                f,
+                "{:<16} {:<6} {:>10} {:>16} {:>12} {:>12} {:>12}",
+                column.name,
+                kind,
+                column.count,
+                number(sum),
+                number(column.min),
+                number(column.max),
+                number(column.mean())
+            )?;
+        }
+        for error in &self.errors {
+            writeln!(f, "{}", error)?;
+        }
+        if self.malformed > self.errors.len() {
+            writeln!(f, "... and {} more malformed rows", self.malformed - self.errors.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the aggregates one line at a time: feed it every line of the file, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregator {
+    aggregates: Aggregates,
+    lines: usize,
+    typed: bool,
+}
+
+impl Default for Aggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Aggregator {
+    pub fn new() -> Self {
+        Aggregator { aggregates: Aggregates { columns: Vec::new(), rows: 0, malformed: 0, errors: Vec::new() }, lines: 0, typed: false }
+    }
+
+    /// The next line of the file, without its line break.
+    pub fn push_line(&mut self, line: &str) {
+        self.lines += 1;
+        if line.is_empty() {
+            return;
+        }
+        let fields = match split_fields(line) {
+            Ok(fields) => fields,
+            Err(message) => return self.malformed(message),
+        };
+        if self.aggregates.columns.is_empty() {
+            // the header
+            self.aggregates.columns = fields.into_iter().map(ColumnStats::new).collect();
+            return;
+        }
+        match self.parse_row(fields) {
+            Ok(values) => {
+                for (column, value) in self.aggregates.columns.iter_mut().zip(&values) {
+                    column.add(value);
+                }
+                self.aggregates.rows += 1;
+            }
+            Err(message) => self.malformed(message),
+        }
+    }
+
+    /// The next line of the file as read, with or without its line break (`\n` or `\r\n`).
+    /// A line that is not valid UTF-8 is a malformed row.
+    pub fn push_bytes(&mut self, line: &[u8]) {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        match std::str::from_utf8(line) {
+            Ok(line) => self.push_line(line),
+            Err(error) => {
+                self.lines += 1;
+                self.malformed(format!("invalid UTF-8 at byte {}", error.valid_up_to()));
+            }
+        }
+    }
+
+    // The whole row is checked before any aggregate is updated: a malformed row counts for nothing.
+    fn parse_row(&mut self, fields: Vec<String>) -> Result<Vec<Value>, String> {
+        let columns = &mut self.aggregates.columns;
+        if fields.len() != columns.len() {
+            return Err(format!("expected {} fields, found {}", columns.len(), fields.len()));
+        }
+        let mut values: Vec<Value> = fields.into_iter().map(Value::guess).collect();
+        if !self.typed {
+            for (column, value) in columns.iter_mut().zip(&values) {
+                column.kind = match value {
+                    Value::Int(_) => ColumnType::Int,
+                    Value::Float(_) => ColumnType::Float,
+                    Value::Text(_) => ColumnType::Text,
+                };
+            }
+            self.typed = true;
+        }
+        let mut promoted = Vec::new();
+        for (i, (column, value)) in columns.iter().zip(values.iter_mut()).enumerate() {
+            match (column.kind, &*value) {
+                (ColumnType::Int, Value::Int(_)) | (ColumnType::Float, Value::Float(_)) => {}
+                // an int in a float column is a float
+                (ColumnType::Float, Value::Int(int)) => *value = Value::Float(*int as f64),
+                // a float in an int column: the column becomes a float column (its sum is already a float)
+                (ColumnType::Int, Value::Float(_)) => promoted.push(i),
+                // a text column takes anything
+                (ColumnType::Text, _) => {}
+                (ColumnType::Int | ColumnType::Float, Value::Text(text)) => {
+                    return Err(format!("column '{}': '{}' is not a number", column.name, text));
+                }
+            }
+        }
+        for i in promoted {
+            columns[i].kind = ColumnType::Float;
+        }
+        Ok(values)
+    }
+
+    fn malformed(&mut self, message: String) {
+        self.aggregates.malformed += 1;
+        if self.aggregates.errors.len() < MAX_ERRORS {
+            self.aggregates.errors.push(RowError { line: self.lines, message });
+        }
+    }
+
+    pub fn finish(self) -> Aggregates {
+        self.aggregates
+    }
+}
+
+/// Reads the file with tokio, one line at a time.
+pub async fn aggregate_async(path: &Path) -> io::Result<Aggregates> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut reader = tokio::io::BufReader::new(file);
+    let mut aggregator = Aggregator::new();
+    // one buffer for every line
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line).await? > 0 {
+        aggregator.push_bytes(&line);
+        line.clear();
+    }
+    Ok(aggregator.finish())
+}
+
+/// The synchronous reference: the same aggregates, with std.
+pub fn aggregate_sync(path: &Path) -> io::Result<Aggregates> {
+    let file = std::fs::File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut aggregator = Aggregator::new();
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        aggregator.push_bytes(&line);
+        line.clear();
+    }
+    Ok(aggregator.finish())
+}
@@ -0,0 +1,172 @@
+// The input file of the benchmark: a CSV file generated from a seed, so every run (and every machine)
+// reads exactly the same data.
+//
+//      id,city,temperature,humidity          <- the schema: id:int,city:text,temperature:float,humidity:int
+//      0,Lisbon,21.37,64
+//      1,"Porto, PT",-3.05,88                 <- a text with a comma is quoted
+//
+// The file is not in the repository (it is in .gitignore): generate it with
+//
+//      cargo run --bin generate_fixture -- --rows 100000
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub const DEFAULT_SCHEMA: &str = "id:int,city:text,temperature:float,humidity:int";
+
+/// The default input file: `data.csv` next to this crate, wherever the program is started from.
+pub fn default_input() -> PathBuf {
+    let crate_directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+    crate_directory.parent().unwrap_or(crate_directory).join("data.csv")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Int,
+    Float,
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub kind: ColumnType,
+}
+
+/// The columns of the file, written `name:type,name:type` with the types int, float and text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    pub columns: Vec<Column>,
+}
+
+impl FromStr for Schema {
+    type Err = String;
+
+    fn from_str(schema: &str) -> Result<Schema, String> {
+        let columns = schema
+            .split(',')
+            .map(|column| {
+                let (name, kind) = column.split_once(':').ok_or_else(|| format!("column '{}' has no type (name:type)", column))?;
+                let kind = match kind {
+                    "int" => ColumnType::Int,
+                    "float" => ColumnType::Float,
+                    "text" => ColumnType::Text,
+                    _ => return Err(format!("unknown type '{}' for column '{}' (int, float or text)", kind, name)),
+                };
+                // the names go in the header line as they are
+                if name.is_empty() || name.contains(['"', '\n', '\r']) {
+                    return Err(format!("invalid column name '{}'", name));
+                }
+                Ok(Column { name: name.to_string(), kind })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Schema { columns })
+    }
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let kind = match column.kind {
+                    ColumnType::Int => "int",
+                    ColumnType::Float => "float",
+                    ColumnType::Text => "text",
+                };
+                format!("{}:{}", column.name, kind)
+            })
+            .collect();
+        f.write_str(&columns.join(","))
+    }
+}
+
+/// How big the file is: a number of rows, or at least a number of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Size {
+    Rows(usize),
+    Bytes(u64),
+}
+
+// Some of them need quotes in a CSV file
+const TEXTS: [&str; 8] = ["Lisbon", "Porto, PT", "Berlin", "São Paulo", "New York", "Quote \"Q\" City", "Oslo", "Tokyo"];
+
+fn write_field(out: &mut impl Write, column: &Column, row: usize, rng: &mut StdRng) -> io::Result<()> {
+    match column.kind {
+        // an int column named id is the row number, so it is unique
+        ColumnType::Int if column.name == "id" => write!(out, "{}", row),
+        ColumnType::Int => write!(out, "{}", rng.gen_range(0..=100)),
+        ColumnType::Float => write!(out, "{:.2}", rng.gen_range(-50.0..50.0)),
+        ColumnType::Text => {
+            let text = TEXTS[rng.gen_range(0..TEXTS.len())];
+            if text.contains([',', '"']) {
+                // quoted, with the quotes inside doubled
+                write!(out, "\"{}\"", text.replace('"', "\"\""))
+            } else {
+                out.write_all(text.as_bytes())
+            }
+        }
+    }
+}
+
+// Counts the bytes written, for Size::Bytes.
+struct Counting<W> {
+    inner: W,
+    written: u64,
+}
+
+impl<W: Write> Write for Counting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Writes the header line then the rows; the same seed always gives the same bytes.
+/// Returns the number of rows written.
+pub fn generate(out: impl Write, schema: &Schema, size: Size, seed: u64) -> io::Result<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut out = Counting { inner: out, written: 0 };
+    let header: Vec<&str> = schema.columns.iter().map(|column| column.name.as_str()).collect();
+    writeln!(out, "{}", header.join(","))?;
+    let mut rows = 0;
+    loop {
+        let done = match size {
+            Size::Rows(count) => rows >= count,
+            Size::Bytes(bytes) => out.written >= bytes,
+        };
+        if done {
+            break;
+        }
+        for (i, column) in schema.columns.iter().enumerate() {
+            if i > 0 {
+                out.write_all(b",")?;
+            }
+            write_field(&mut out, column, rows, &mut rng)?;
+        }
+        out.write_all(b"\n")?;
+        rows += 1;
+    }
+    out.flush()?;
+    Ok(rows)
+}
+
+/// [`generate`] into a file, creating its directory if needed.
+pub fn write_file(path: &Path, schema: &Schema, size: Size, seed: u64) -> io::Result<usize> {
+    if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+        fs::create_dir_all(directory)?;
+    }
+    generate(BufWriter::new(File::create(path)?), schema, size, seed)
+}
@@ -0,0 +1,6 @@
+// The benchmark used by main.rs, in a library so the tests in tests/ can use it too.
+pub mod config;
+pub mod csv;
+pub mod fixture;
+pub mod report;
+pub mod workload;
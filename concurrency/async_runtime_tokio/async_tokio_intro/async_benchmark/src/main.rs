@@ -0,0 +1,67 @@
+use async_benchmark::config::{Config, Output};
+use async_benchmark::report::{self, Summary};
+use async_benchmark::workload;
+
+// One binary for the four examples of async_tokio_step_by_step.adoc: every mode runs the same workload
+// (a sleeper, and tasks that read a file then compute a fibonacci number) and the modes are compared.
+//
+//      cargo run --bin generate_fixture                      the input file first (../data.csv), see src/fixture.rs
+//      cargo run --release                                  every mode, 10 tasks, fib(35)
+//      cargo run --release -- --mode async-cpu --fib 35     a single mode
+//      cargo run --release -- --tasks 50 --json             JSON instead of the table
+//      cargo run --release -- --tasks 50 --max-in-flight 4 --timeout-ms 2000
+//                                                           4 tasks at most at the same time, cancelled after 2s
+//
+// Build with --release: in debug mode fib is much slower and hides the differences between the modes.
+
+fn main() {
+    let config = match Config::from_args(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!(
+                "usage: async_benchmark [--mode all|sync|async|async-cpu|async-spawn-blocking] [--tasks N] [--fib N] \
+                 [--input FILE] [--sleep-ms N] [--max-in-flight N] [--timeout-ms N] [--json] [--verbose]"
+            );
+            std::process::exit(2);
+        }
+    };
+    if config.verbose {
+        // create the simple logger: shows the time of every step of every task
+        simple_logger::init_with_level(log::Level::Info).expect("Error creating logger.");
+    }
+
+    let mut summaries: Vec<Summary> = Vec::new();
+    let mut aggregates = None;
+    for mode in &config.modes {
+        let run = workload::run(*mode, &config);
+        if let Some(error) = run.failed.first() {
+            eprintln!("[{}] {} task(s) failed, the first one: {}", mode, run.failed.len(), error);
+        }
+        summaries.push(Summary::of(&run));
+        aggregates = aggregates.or(run.aggregates);
+    }
+
+    match config.output {
+        Output::Table => {
+            print!("{} tasks, fib({}), sleeper {:?}, input {}", config.tasks, config.fib_depth, config.sleep, config.input.display());
+            if let Some(max) = config.max_in_flight {
+                print!(", at most {} in flight", max);
+            }
+            if let Some(timeout) = config.timeout {
+                print!(", timeout {:?}", timeout);
+            }
+            println!("\n");
+            print!("{}", report::table(&summaries));
+            // the same in every mode
+            if let Some(aggregates) = aggregates {
+                print!("\n{}", aggregates);
+            }
+        }
+        Output::Json => println!("{}", report::json(&summaries)),
+    }
+    // nothing worked: probably the input file (see the errors above)
+    if summaries.iter().all(|summary| summary.succeeded == 0 && summary.failed > 0) {
+        std::process::exit(1);
+    }
+}
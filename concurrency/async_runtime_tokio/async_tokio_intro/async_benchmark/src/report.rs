@@ -0,0 +1,91 @@
+// The comparison of the modes: a table for people, or JSON for scripts.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::workload::Run;
+
+/// The numbers of one mode, the times in milliseconds. The latencies are the ones of the tasks that succeeded.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub mode: &'static str,
+    pub tasks: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    /// The most tasks that ran at the same time.
+    pub max_in_flight: usize,
+    pub wall_ms: f64,
+    pub task_p50_ms: f64,
+    pub task_p99_ms: f64,
+    pub task_max_ms: f64,
+    pub slept_ms: f64,
+    /// Rows read by all the tasks.
+    pub rows: usize,
+    /// Rows skipped by every task.
+    pub malformed: usize,
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// nearest rank: the smallest latency such that p% of the latencies are lower or equal
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    sorted[(sorted.len() * p).div_ceil(100).max(1) - 1]
+}
+
+impl Summary {
+    pub fn of(run: &Run) -> Summary {
+        let mut latencies = run.tasks.clone();
+        latencies.sort();
+        Summary {
+            mode: run.mode.name(),
+            tasks: run.succeeded() + run.failed.len() + run.cancelled,
+            succeeded: run.succeeded(),
+            failed: run.failed.len(),
+            cancelled: run.cancelled,
+            max_in_flight: run.max_in_flight,
+            wall_ms: ms(run.wall),
+            task_p50_ms: ms(percentile(&latencies, 50)),
+            task_p99_ms: ms(percentile(&latencies, 99)),
+            task_max_ms: ms(latencies.last().copied().unwrap_or_default()),
+            slept_ms: ms(run.slept),
+            rows: run.rows,
+            malformed: run.aggregates.as_ref().map_or(0, |aggregates| aggregates.malformed),
+        }
+    }
+}
+
+/// One line per mode.
+pub fn table(summaries: &[Summary]) -> String {
+    let mut out = format!(
+        "{:<22} {:>6} {:>6} {:>6} {:>9} {:>10} {:>12} {:>12} {:>12} {:>10}\n",
+        "mode", "tasks", "ok", "failed", "cancelled", "wall (ms)", "task p50", "task p99", "task max", "slept (ms)"
+    );
+    for summary in summaries {
+        out.push_str(&format!(
+            "{:<22} {:>6} {:>6} {:>6} {:>9} {:>10.1} {:>12.1} {:>12.1} {:>12.1} {:>10.1}\n",
+            summary.mode,
+            summary.tasks,
+            summary.succeeded,
+            summary.failed,
+            summary.cancelled,
+            summary.wall_ms,
+            summary.task_p50_ms,
+            summary.task_p99_ms,
+            summary.task_max_ms,
+            summary.slept_ms
+        ));
+    }
+    out
+}
+
+/// A JSON array, one object per mode.
+pub fn json(summaries: &[Summary]) -> String {
+    serde_json::to_string_pretty(summaries).expect("a summary is always valid JSON")
+}
@@ -0,0 +1,393 @@
+// The workload of the four examples, and the four ways to run it (see Mode in config.rs).
+//
+// Every mode runs the same work: one sleeper, and `tasks` tasks that each read the input file (parsing
+// its rows and computing the aggregates of its columns, see csv.rs) and compute a fibonacci number.
+// What changes is where that work runs:
+//
+//      sync                  main thread: read fib read fib read fib ... sleep
+//      async                 one tokio task: the reads and the sleep overlap, but every fib stops them all
+//      async-cpu             one tokio task per task (a JoinSet), on the worker threads: a fib holds its worker
+//      async-spawn-blocking  one tokio task per task (a JoinSet), the fib on the threads of spawn_blocking
+//
+// For every task we record its latency: the time from the start of the run to the end of the task.
+// For the sleeper we record how long it really slept: a sleeper that is not polled when its timer
+// fires sleeps longer than asked.
+//
+// NOTE: Limits. At most `max_in_flight` tasks run at the same time: a task waits for a permit of a
+// Semaphore before it starts (its latency includes that wait). With a `timeout`, the tasks not done
+// when it expires are cancelled: the tasks that get their permit after the deadline do not start, and
+// the JoinSet aborts the ones still running (a task stops at its next .await). A tokio timer only fires
+// when the runtime gets to it: a fib holding the thread delays it, so a task also checks the clock
+// itself before starting.
+//
+// A task that fails (it cannot read the input file, or it panics) does not stop the others: every
+// task ends up succeeded, failed (with its error) or cancelled, and the Run counts them.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+use tokio::task::{JoinError, JoinSet};
+
+use crate::config::{Config, Mode};
+use crate::csv::{self, Aggregates};
+use crate::fixture;
+
+pub fn fib(n: u32) -> u32 {
+    match n {
+        0 => 0,
+        1 => 1,
+        n => fib(n - 1) + fib(n - 2),
+    }
+}
+
+/// The result of one mode.
+#[derive(Debug)]
+pub struct Run {
+    pub mode: Mode,
+    /// Time to run the whole workload.
+    pub wall: Duration,
+    /// The latency of every task that succeeded, sorted (the order the tasks finished in).
+    pub tasks: Vec<Duration>,
+    /// The errors of the tasks that failed.
+    pub failed: Vec<TaskError>,
+    pub cancelled: usize,
+    /// The most tasks that ran at the same time.
+    pub max_in_flight: usize,
+    /// How long the sleeper really slept.
+    pub slept: Duration,
+    /// Rows read by all the tasks.
+    pub rows: usize,
+    /// What the tasks found in the input (they all read the same file); None without tasks.
+    pub aggregates: Option<Aggregates>,
+}
+
+/// The input file could not be read.
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot read {}: {}", self.path.display(), self.source)?;
+        if self.source.kind() == io::ErrorKind::NotFound {
+            write!(f, "\ngenerate it with: cargo run --bin generate_fixture")?;
+            if self.path != fixture::default_input() {
+                write!(f, " -- --output {}", self.path.display())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Why a task failed.
+#[derive(Debug)]
+pub enum TaskError {
+    Input(InputError),
+    /// The task panicked, with this message.
+    Panicked(String),
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskError::Input(error) => error.fmt(f),
+            TaskError::Panicked(message) => write!(f, "task panicked: {}", message),
+        }
+    }
+}
+
+impl Error for TaskError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TaskError::Input(error) => Some(error),
+            TaskError::Panicked(_) => None,
+        }
+    }
+}
+
+impl Run {
+    pub fn succeeded(&self) -> usize {
+        self.tasks.len()
+    }
+}
+
+// How a task ended.
+enum Outcome {
+    Succeeded(Aggregates, Duration),
+    Failed(TaskError),
+    Cancelled,
+}
+
+// panic!("...") gives a &str, panic!("{}", x) a String, anything else is unusual
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("(not a string)")
+    }
+}
+
+// A task that did not return: aborted by the timeout, or panicked.
+fn not_returned(error: JoinError) -> Outcome {
+    if error.is_cancelled() {
+        Outcome::Cancelled
+    } else {
+        Outcome::Failed(TaskError::Panicked(panic_message(&*error.into_panic())))
+    }
+}
+
+// Builds the Run from the outcomes of the tasks.
+fn collect(mode: Mode, start: Instant, slept: Duration, outcomes: Vec<Outcome>, max_in_flight: usize) -> Run {
+    let mut run = Run {
+        mode,
+        wall: start.elapsed(),
+        tasks: Vec::new(),
+        failed: Vec::new(),
+        cancelled: 0,
+        max_in_flight,
+        slept,
+        rows: 0,
+        aggregates: None,
+    };
+    for outcome in outcomes {
+        match outcome {
+            Outcome::Succeeded(aggregates, latency) => {
+                run.tasks.push(latency);
+                run.rows += aggregates.rows;
+                run.aggregates.get_or_insert(aggregates);
+            }
+            Outcome::Failed(error) => run.failed.push(error),
+            Outcome::Cancelled => run.cancelled += 1,
+        }
+    }
+    run.tasks.sort();
+    run
+}
+
+/// Runs the workload of the config in this mode.
+pub fn run(mode: Mode, config: &Config) -> Run {
+    match mode {
+        Mode::Sync => run_sync(config),
+        Mode::Async | Mode::AsyncCpu | Mode::AsyncSpawnBlocking => {
+            // create an instance of the tokio runtime (one worker thread per CPU)
+            let runtime = tokio::runtime::Runtime::new().expect("cannot create the tokio runtime");
+            runtime.block_on(run_async(mode, config))
+        }
+    }
+}
+
+// #1: one task after the other, the sleeper only sleeps after all the reading happens.
+fn run_sync(config: &Config) -> Run {
+    let start = Instant::now();
+    let mut outcomes = Vec::with_capacity(config.tasks);
+    for task in 0..config.tasks {
+        if config.timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            outcomes.push(Outcome::Cancelled);
+            continue;
+        }
+        log::info!("[sync] task {} reading {}", task, config.input.display());
+        let aggregates = match csv::aggregate_sync(&config.input) {
+            Ok(aggregates) => aggregates,
+            Err(source) => {
+                let error = InputError { path: config.input.clone(), source };
+                outcomes.push(Outcome::Failed(TaskError::Input(error)));
+                continue;
+            }
+        };
+        log::info!("[sync] task {} computing fib({})", task, config.fib_depth);
+        fib(config.fib_depth);
+        outcomes.push(Outcome::Succeeded(aggregates, start.elapsed()));
+    }
+    log::info!("[sync] sleeping");
+    let before = Instant::now();
+    std::thread::sleep(config.sleep);
+    let slept = before.elapsed();
+    log::info!("[sync] awake");
+    collect(Mode::Sync, start, slept, outcomes, config.tasks.min(1))
+}
+
+async fn sleeper(mode: Mode, sleep: Duration) -> Duration {
+    log::info!("[{}] sleeping", mode);
+    let before = Instant::now();
+    tokio::time::sleep(sleep).await;
+    log::info!("[{}] awake", mode);
+    before.elapsed()
+}
+
+// What every reader of a run needs: spawned tasks must own it, so it is shared in an Arc.
+struct Shared {
+    mode: Mode,
+    input: PathBuf,
+    fib_depth: u32,
+    start: Instant,
+    deadline: Option<Instant>,
+    permits: Semaphore,
+    running: AtomicUsize,
+    most_running: AtomicUsize,
+}
+
+// Counts a task as running while it holds its permit: the count goes down when the task ends, and
+// also when it is aborted (the guard is dropped with the task).
+struct Running<'a>(&'a Shared);
+
+impl<'a> Running<'a> {
+    fn enter(shared: &'a Shared) -> Self {
+        let now = shared.running.fetch_add(1, Ordering::SeqCst) + 1;
+        shared.most_running.fetch_max(now, Ordering::SeqCst);
+        Running(shared)
+    }
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// One task: wait for a permit, read the file, compute fib.
+async fn reader(task: usize, shared: Arc<Shared>) -> Outcome {
+    // the permit is given back when the task ends (or is aborted)
+    let _permit = shared.permits.acquire().await.expect("the semaphore is never closed");
+    if shared.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        return Outcome::Cancelled;
+    }
+    let _running = Running::enter(&shared);
+    match read_and_compute(task, &shared).await {
+        Ok(aggregates) => Outcome::Succeeded(aggregates, shared.start.elapsed()),
+        Err(error) => Outcome::Failed(error),
+    }
+}
+
+async fn read_and_compute(task: usize, shared: &Shared) -> Result<Aggregates, TaskError> {
+    let mode = shared.mode;
+    log::info!("[{}] task {} reading {}", mode, task, shared.input.display());
+    let aggregates = match csv::aggregate_async(&shared.input).await {
+        Ok(aggregates) => aggregates,
+        Err(source) => return Err(TaskError::Input(InputError { path: shared.input.clone(), source })),
+    };
+    log::info!("[{}] task {} computing fib({})", mode, task, shared.fib_depth);
+    let fib_depth = shared.fib_depth;
+    if mode == Mode::AsyncSpawnBlocking {
+        // runs on another thread: the worker goes on with the other tasks meanwhile
+        if let Err(error) = tokio::task::spawn_blocking(move || fib(fib_depth)).await {
+            return Err(TaskError::Panicked(panic_message(&*error.into_panic())));
+        }
+    } else {
+        // runs right here: nothing else runs on this thread until fib returns
+        fib(fib_depth);
+    }
+    Ok(aggregates)
+}
+
+async fn run_async(mode: Mode, config: &Config) -> Run {
+    let start = Instant::now();
+    let shared = Arc::new(Shared {
+        mode,
+        input: config.input.clone(),
+        fib_depth: config.fib_depth,
+        start,
+        deadline: config.timeout.map(|timeout| start + timeout),
+        // no limit: as many permits as tasks
+        permits: Semaphore::new(config.max_in_flight.unwrap_or(config.tasks).min(Semaphore::MAX_PERMITS)),
+        running: AtomicUsize::new(0),
+        most_running: AtomicUsize::new(0),
+    });
+    let deadline = shared.deadline.map(tokio::time::Instant::from_std);
+    let (slept, outcomes) = if mode == Mode::Async {
+        // every future in this task, as tokio::join!(sleeper(), reader(), reader(), ...) would do;
+        // a future not done at the deadline is dropped: cancelled
+        let readers: Vec<_> = (0..config.tasks)
+            .map(|task| {
+                let reader = reader(task, Arc::clone(&shared));
+                async move {
+                    match deadline {
+                        Some(deadline) => {
+                            tokio::time::timeout_at(deadline, reader).await.unwrap_or(Outcome::Cancelled)
+                        }
+                        None => reader.await,
+                    }
+                }
+            })
+            .collect();
+        tokio::join!(sleeper(mode, config.sleep), join_all(readers))
+    } else {
+        // every future in its own task: the runtime can run them on any of its worker threads
+        let sleeper = tokio::spawn(sleeper(mode, config.sleep));
+        let mut set = JoinSet::new();
+        for task in 0..config.tasks {
+            set.spawn(reader(task, Arc::clone(&shared)));
+        }
+        // both at the same time: awaiting the sleeper first would only look at the deadline after it wakes up
+        let (slept, outcomes) = tokio::join!(sleeper, join_set(set, deadline));
+        (slept.expect("the sleeper does not panic"), outcomes)
+    };
+    collect(mode, start, slept, outcomes, shared.most_running.load(Ordering::SeqCst))
+}
+
+// The outcome of every task of the set, in the order they end. At the deadline, the tasks still
+// running are aborted, and come out of join_next() as cancelled (or done, if they ended meanwhile).
+async fn join_set(mut set: JoinSet<Outcome>, mut deadline: Option<tokio::time::Instant>) -> Vec<Outcome> {
+    let mut outcomes = Vec::with_capacity(set.len());
+    loop {
+        let joined = match deadline {
+            Some(at) => match tokio::time::timeout_at(at, set.join_next()).await {
+                Ok(joined) => joined,
+                Err(_) => {
+                    set.abort_all();
+                    deadline = None;
+                    continue;
+                }
+            },
+            None => set.join_next().await,
+        };
+        let Some(joined) = joined else {
+            return outcomes;
+        };
+        outcomes.push(joined.unwrap_or_else(not_returned));
+    }
+}
+
+// Polls all the futures in the current task until they are all done, like tokio::join! but for a Vec:
+// the futures run concurrently (one waits for the file while another one runs), never in parallel.
+async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut futures: Vec<Pin<Box<F>>> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    std::future::poll_fn(|context| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match future.as_mut().poll(context) {
+                    Poll::Ready(value) => *output = Some(value),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().map(|output| output.expect("every future is done")).collect()
+}
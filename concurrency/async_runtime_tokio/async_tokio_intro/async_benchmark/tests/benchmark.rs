@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_benchmark::config::{Config, Mode, Output};
+use async_benchmark::report::{self, Summary};
+use async_benchmark::workload::{self, fib, TaskError};
+
+mod common;
+use common::input;
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn fibonacci() {
+    let values: Vec<u32> = (0..10).map(fib).collect();
+    assert_eq!(values, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+}
+
+#[test]
+fn options() {
+    assert_eq!(Config::from_args(args("")).unwrap(), Config::default());
+    assert_eq!(Config::default().modes, Mode::ALL.to_vec());
+
+    let config = Config::from_args(args(
+        "--mode async-spawn-blocking --tasks 3 --fib 12 --input x.csv --sleep-ms 5 --max-in-flight 2 --timeout-ms 900 --json --verbose",
+    ))
+    .unwrap();
+    assert_eq!(config.modes, vec![Mode::AsyncSpawnBlocking]);
+    assert_eq!((config.tasks, config.fib_depth), (3, 12));
+    assert_eq!(config.input, PathBuf::from("x.csv"));
+    assert_eq!(config.sleep, Duration::from_millis(5));
+    assert_eq!((config.max_in_flight, config.timeout), (Some(2), Some(Duration::from_millis(900))));
+    assert_eq!(config.output, Output::Json);
+    assert!(config.verbose);
+
+    for mode in Mode::ALL {
+        assert_eq!(mode.name().parse::<Mode>(), Ok(mode));
+    }
+    assert_eq!(Config::from_args(args("--mode all")).unwrap().modes, Mode::ALL.to_vec());
+    assert_eq!(Config::from_args(args("--mode fast")), Err(String::from("unknown mode 'fast'")));
+    assert_eq!(Config::from_args(args("--fib 48")), Err(String::from("invalid value '48' for --fib")));
+    assert_eq!(Config::from_args(args("--tasks")), Err(String::from("missing value for --tasks")));
+    assert_eq!(Config::from_args(args("--max-in-flight 0")), Err(String::from("invalid value '0' for --max-in-flight")));
+    assert_eq!(Config::from_args(args("--threads 2")), Err(String::from("unknown option --threads")));
+}
+
+// Every mode runs the same work: same number of tasks, same rows read, and the sleeper sleeps at least
+// as long as asked.
+#[test]
+fn every_mode_runs_the_same_workload() {
+    let path = input("same-workload", b"a,b\n1,2\n3,x\n5,6\n");
+    let expected = async_benchmark::csv::aggregate_sync(&path).unwrap();
+    assert_eq!((expected.rows, expected.malformed), (2, 1));
+    let config = Config { tasks: 6, fib_depth: 15, input: path.clone(), sleep: Duration::from_millis(5), ..Config::default() };
+    for mode in Mode::ALL {
+        let run = workload::run(mode, &config);
+        assert_eq!(run.mode, mode);
+        assert_eq!((run.succeeded(), run.failed.len(), run.cancelled), (6, 0, 0), "{}", mode);
+        assert!((1..=6).contains(&run.max_in_flight), "{}", mode);
+        assert_eq!(run.rows, 6 * 2, "{}", mode);
+        assert_eq!(run.aggregates.as_ref(), Some(&expected), "{}", mode);
+        assert!(run.slept >= config.sleep, "{}", mode);
+        assert!(run.tasks.windows(2).all(|pair| pair[0] <= pair[1]), "{}", mode);
+        assert!(run.tasks.iter().all(|latency| *latency <= run.wall), "{}", mode);
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn a_missing_input_is_an_error_in_every_mode() {
+    let missing = std::env::temp_dir().join("async_benchmark-does-not-exist.csv");
+    let config = Config { tasks: 3, fib_depth: 1, input: missing.clone(), sleep: Duration::from_millis(1), ..Config::default() };
+    for mode in Mode::ALL {
+        // every task fails on its own, none of them panics
+        let run = workload::run(mode, &config);
+        assert_eq!((run.succeeded(), run.failed.len(), run.cancelled), (0, 3, 0), "{}", mode);
+        assert!(run.aggregates.is_none());
+        for error in &run.failed {
+            let TaskError::Input(input) = error else { panic!("{}: {}", mode, error) };
+            assert_eq!(input.path, missing);
+            assert_eq!(input.source.kind(), std::io::ErrorKind::NotFound);
+            let message = error.to_string();
+            assert!(message.starts_with(&format!("cannot read {}", missing.display())), "{}", message);
+            assert!(message.contains("generate_fixture -- --output"), "{}", message);
+        }
+    }
+}
+
+#[test]
+fn no_more_tasks_in_flight_than_the_limit() {
+    let path = input("in-flight", b"a\n1\n");
+    let config = Config { tasks: 12, fib_depth: 10, input: path.clone(), sleep: Duration::from_millis(1), max_in_flight: Some(3), ..Config::default() };
+    for mode in [Mode::Async, Mode::AsyncCpu, Mode::AsyncSpawnBlocking] {
+        let run = workload::run(mode, &config);
+        assert_eq!(run.succeeded(), 12, "{}", mode);
+        assert!((1..=3).contains(&run.max_in_flight), "{}: {}", mode, run.max_in_flight);
+    }
+    assert_eq!(workload::run(Mode::Sync, &config).max_in_flight, 1);
+    std::fs::remove_file(path).unwrap();
+}
+
+// One task at a time, each one much longer than the timeout: the tasks still waiting for their permit
+// at the deadline are cancelled.
+#[test]
+fn tasks_not_done_at_the_timeout_are_cancelled() {
+    let path = input("timeout", b"a\n1\n");
+    let config = Config {
+        tasks: 6,
+        fib_depth: 32,
+        input: path.clone(),
+        sleep: Duration::from_millis(1),
+        max_in_flight: Some(1),
+        timeout: Some(Duration::from_millis(5)),
+        ..Config::default()
+    };
+    for mode in [Mode::Async, Mode::AsyncCpu, Mode::AsyncSpawnBlocking] {
+        let run = workload::run(mode, &config);
+        assert_eq!(run.succeeded() + run.failed.len() + run.cancelled, 6, "{}", mode);
+        assert!(run.failed.is_empty(), "{}", mode);
+        assert!(run.cancelled >= 4, "{}: only {} cancelled", mode, run.cancelled);
+    }
+    // in sync mode, a task is cancelled if it has not started at the deadline
+    let run = workload::run(Mode::Sync, &Config { timeout: Some(Duration::ZERO), ..config });
+    assert_eq!((run.succeeded(), run.cancelled), (0, 6));
+    std::fs::remove_file(path).unwrap();
+}
+
+// The deadline is enforced while the sleeper sleeps, not once it wakes up.
+#[test]
+fn the_timeout_does_not_wait_for_a_longer_sleep() {
+    let path = input("long-sleep", b"a\n1\n");
+    let config = Config {
+        tasks: 4,
+        fib_depth: 32,
+        input: path.clone(),
+        sleep: Duration::from_millis(500),
+        timeout: Some(Duration::from_millis(5)),
+        ..Config::default()
+    };
+    let run = workload::run(Mode::AsyncSpawnBlocking, &config);
+    assert!(run.slept >= config.sleep);
+    assert_eq!(run.succeeded() + run.cancelled, 4);
+    assert!(run.cancelled >= 3, "only {} cancelled", run.cancelled);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn summary_table_and_json() {
+    let run = workload::Run {
+        mode: Mode::AsyncCpu,
+        wall: Duration::from_millis(120),
+        tasks: (1..=100).map(Duration::from_millis).collect(),
+        failed: vec![TaskError::Panicked(String::from("boom"))],
+        cancelled: 2,
+        max_in_flight: 4,
+        slept: Duration::from_millis(110),
+        rows: 42,
+        aggregates: None,
+    };
+    let summary = Summary::of(&run);
+    assert_eq!(summary.mode, "async-cpu");
+    assert_eq!((summary.tasks, summary.succeeded, summary.failed, summary.cancelled), (103, 100, 1, 2));
+    assert_eq!((summary.task_p50_ms, summary.task_p99_ms, summary.task_max_ms), (50.0, 99.0, 100.0));
+
+    let table = report::table(std::slice::from_ref(&summary));
+    assert_eq!(table.lines().count(), 2);
+    assert!(table.lines().nth(1).unwrap().starts_with("async-cpu"));
+
+    let json: serde_json::Value = serde_json::from_str(&report::json(&[summary])).unwrap();
+    assert_eq!(json[0]["mode"], "async-cpu");
+    assert_eq!(json[0]["wall_ms"], 120.0);
+    assert_eq!(json[0]["rows"], 42);
+    assert_eq!(json[0]["malformed"], 0);
+    assert_eq!(json[0]["cancelled"], 2);
+    assert_eq!(json[0]["max_in_flight"], 4);
+}
@@ -0,0 +1,10 @@
+// Helpers shared by the test files (tests/common/ is a module, not a test crate of its own).
+
+use std::path::PathBuf;
+
+/// Writes `contents` to a CSV file of the temporary directory, unique to this test process.
+pub fn input(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("async_benchmark-{}-{}.csv", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
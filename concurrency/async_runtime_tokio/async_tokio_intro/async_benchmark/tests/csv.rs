@@ -0,0 +1,140 @@
+use async_benchmark::csv::{self, Aggregates, Aggregator, RowError, MAX_ERRORS};
+use async_benchmark::fixture::{self, ColumnType, Size};
+
+mod common;
+use common::input;
+
+fn aggregate(lines: &[&str]) -> Aggregates {
+    let mut aggregator = Aggregator::new();
+    for line in lines {
+        aggregator.push_line(line);
+    }
+    aggregator.finish()
+}
+
+#[test]
+fn fields_and_quotes() {
+    assert_eq!(csv::split_fields("a,b,c"), Ok(vec![String::from("a"), String::from("b"), String::from("c")]));
+    assert_eq!(csv::split_fields(""), Ok(vec![String::new()]));
+    assert_eq!(csv::split_fields("a,,"), Ok(vec![String::from("a"), String::new(), String::new()]));
+    assert_eq!(
+        csv::split_fields(r#"1,"Porto, PT","say ""hi""","""#),
+        Ok(vec![String::from("1"), String::from("Porto, PT"), String::from("say \"hi\""), String::new()])
+    );
+    assert_eq!(csv::split_fields(r#"1,"open"#), Err(String::from("unterminated quoted field")));
+    assert_eq!(csv::split_fields(r#""a"b,c"#), Err(String::from("unexpected 'b' after a quoted field")));
+    assert_eq!(csv::split_fields(r#"a"b,c"#), Err(String::from("unexpected quote in an unquoted field")));
+}
+
+#[test]
+fn aggregates_of_the_columns() {
+    let aggregates = aggregate(&["id,city,temperature", "1,Lisbon,20.5", r#"2,"Porto, PT",-3"#, "", "3,Oslo,4.5"]);
+    assert_eq!((aggregates.rows, aggregates.malformed), (3, 0));
+    let [id, city, temperature] = &aggregates.columns[..] else { panic!("3 columns") };
+
+    assert_eq!((id.name.as_str(), id.kind, id.count), ("id", ColumnType::Int, 3));
+    assert_eq!((id.sum, id.min, id.max, id.mean()), (6.0, Some(1.0), Some(3.0), Some(2.0)));
+
+    assert_eq!((city.kind, city.count, city.min, city.mean()), (ColumnType::Text, 3, None, None));
+
+    // "-3" is an int in a float column: it counts as -3.0
+    assert_eq!(temperature.kind, ColumnType::Float);
+    assert_eq!((temperature.sum, temperature.min, temperature.max), (22.0, Some(-3.0), Some(20.5)));
+}
+
+#[test]
+fn a_float_turns_an_int_column_into_a_float_column() {
+    let aggregates = aggregate(&["x", "1", "2.5", "3"]);
+    assert_eq!(aggregates.columns[0].kind, ColumnType::Float);
+    assert_eq!((aggregates.columns[0].sum, aggregates.columns[0].count), (6.5, 3));
+}
+
+#[test]
+fn numbers_in_a_text_column_are_only_counted() {
+    let aggregates = aggregate(&["id,city", "1,Lisbon", "2,123", "3,4.5"]);
+    let city = &aggregates.columns[1];
+    assert_eq!((city.kind, city.count), (ColumnType::Text, 3));
+    assert_eq!((city.sum, city.min, city.max, city.mean()), (0.0, None, None, None));
+    assert!(aggregates.to_string().contains("city             text            3                -            -            -            -"));
+}
+
+// A malformed row is skipped as a whole, and reported with its line number (the header is line 1).
+#[test]
+fn malformed_rows_are_skipped_and_reported() {
+    let aggregates = aggregate(&["a,b", "1,2", "3", "4,five", r#"5,"6"#, "7,8,9", "", "10,11"]);
+    assert_eq!((aggregates.rows, aggregates.malformed), (2, 4));
+    assert_eq!(
+        aggregates.errors,
+        vec![
+            RowError { line: 3, message: String::from("expected 2 fields, found 1") },
+            RowError { line: 4, message: String::from("column 'b': 'five' is not a number") },
+            RowError { line: 5, message: String::from("unterminated quoted field") },
+            RowError { line: 6, message: String::from("expected 2 fields, found 3") },
+        ]
+    );
+    // nothing of the skipped rows in the aggregates
+    assert_eq!((aggregates.columns[0].sum, aggregates.columns[1].sum), (11.0, 13.0));
+    assert_eq!(aggregates.errors[1].to_string(), "line 4: column 'b': 'five' is not a number");
+}
+
+#[test]
+fn only_the_first_errors_are_kept() {
+    let mut lines = vec!["a"];
+    lines.extend(std::iter::repeat_n("1,2", 25));
+    let aggregates = aggregate(&lines);
+    assert_eq!(aggregates.malformed, 25);
+    assert_eq!(aggregates.errors.len(), MAX_ERRORS);
+    assert_eq!(aggregates.errors.last().unwrap().line, MAX_ERRORS + 1);
+    assert!(aggregates.to_string().ends_with("... and 15 more malformed rows\n"));
+}
+
+#[test]
+fn an_empty_file_has_no_columns() {
+    let aggregates = aggregate(&[]);
+    assert_eq!((aggregates.columns.len(), aggregates.rows, aggregates.malformed), (0, 0, 0));
+}
+
+// The async reader and the sync reference read the same file to the same results, including the
+// Windows line breaks and a malformed last line without a line break.
+#[tokio::test]
+async fn async_and_sync_readers_agree() {
+    let mut contents = Vec::new();
+    fixture::generate(&mut contents, &fixture::DEFAULT_SCHEMA.parse().unwrap(), Size::Rows(5_000), 11).unwrap();
+    let contents = String::from_utf8(contents).unwrap().replacen("\n", "\r\n", 100) + "1,\"Oslo";
+    let path = input("agree", contents.as_bytes());
+
+    let sync = csv::aggregate_sync(&path).unwrap();
+    let streamed = csv::aggregate_async(&path).await.unwrap();
+    assert_eq!(streamed, sync);
+    assert_eq!((sync.rows, sync.malformed), (5_000, 1));
+    assert_eq!(sync.errors[0].line, 5_002);
+
+    // the aggregates match a computation over the whole file in memory
+    let temperatures: Vec<f64> = contents.lines().skip(1).take(5_000).map(|line| line.rsplit(',').nth(1).unwrap().parse().unwrap()).collect();
+    let temperature = &sync.columns[2];
+    assert_eq!(temperature.count, 5_000);
+    assert_eq!(temperature.sum, temperatures.iter().sum::<f64>());
+    assert_eq!(temperature.min, temperatures.iter().copied().reduce(f64::min));
+    assert_eq!(temperature.max, temperatures.iter().copied().reduce(f64::max));
+    std::fs::remove_file(path).unwrap();
+}
+
+// Invalid UTF-8 only makes its own row malformed, the rest of the file is read.
+#[tokio::test]
+async fn a_line_with_invalid_utf8_is_a_malformed_row() {
+    let path = input("utf8", b"a,b\n1,2\n3,\xff\n5,6\n");
+    let (async_result, sync_result) = (csv::aggregate_async(&path).await.unwrap(), csv::aggregate_sync(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(async_result, sync_result);
+    assert_eq!((async_result.rows, async_result.malformed), (2, 1));
+    assert_eq!(async_result.errors, vec![RowError { line: 3, message: String::from("invalid UTF-8 at byte 2") }]);
+    assert_eq!(async_result.columns[1].sum, 8.0);
+}
+
+#[tokio::test]
+async fn a_missing_file_is_an_io_error() {
+    let missing = std::env::temp_dir().join("async_benchmark-csv-missing.csv");
+    assert_eq!(csv::aggregate_async(&missing).await.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(csv::aggregate_sync(&missing).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use async_benchmark::config::Config;
+use async_benchmark::fixture::{self, Column, ColumnType, Schema, Size};
+
+fn generate(schema: &str, size: Size, seed: u64) -> String {
+    let mut out = Vec::new();
+    fixture::generate(&mut out, &schema.parse().unwrap(), size, seed).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn the_same_seed_gives_the_same_file() {
+    let first = generate(fixture::DEFAULT_SCHEMA, Size::Rows(500), 7);
+    assert_eq!(first, generate(fixture::DEFAULT_SCHEMA, Size::Rows(500), 7));
+    assert_ne!(first, generate(fixture::DEFAULT_SCHEMA, Size::Rows(500), 8));
+}
+
+#[test]
+fn rows_follow_the_schema() {
+    let csv = generate("id:int,price:float,name:text,count:int", Size::Rows(200), 1);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("id,price,name,count"));
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), 200);
+    for (i, row) in rows.iter().enumerate() {
+        let (id, rest) = row.split_once(',').unwrap();
+        assert_eq!(id, i.to_string());
+        let (price, rest) = rest.split_once(',').unwrap();
+        assert!((-50.0..50.0).contains(&price.parse::<f64>().unwrap()), "{}", row);
+        // the name can contain a comma, but the count is always the last field
+        let (name, count) = rest.rsplit_once(',').unwrap();
+        assert!((0..=100).contains(&count.parse::<i64>().unwrap()), "{}", row);
+        if name.starts_with('"') {
+            assert!(name.ends_with('"') && name.len() >= 2, "{}", row);
+        } else {
+            assert!(!name.contains([',', '"']), "{}", row);
+        }
+    }
+    // with 200 rows, the texts that need quotes show up
+    assert!(csv.contains("\"Porto, PT\""));
+    assert!(csv.contains("\"Quote \"\"Q\"\" City\""));
+}
+
+#[test]
+fn a_size_in_bytes_is_reached_with_whole_rows() {
+    let csv = generate(fixture::DEFAULT_SCHEMA, Size::Bytes(10_000), 3);
+    assert!(csv.len() >= 10_000);
+    // one row more than needed at most
+    let last_row = csv.lines().last().unwrap().len() + 1;
+    assert!(csv.len() < 10_000 + last_row);
+    assert!(csv.ends_with('\n'));
+
+    assert_eq!(generate("a:int", Size::Rows(0), 3), "a\n");
+}
+
+#[test]
+fn schemas() {
+    let schema: Schema = "id:int,t:float,name:text".parse().unwrap();
+    assert_eq!(
+        schema.columns,
+        vec![
+            Column { name: String::from("id"), kind: ColumnType::Int },
+            Column { name: String::from("t"), kind: ColumnType::Float },
+            Column { name: String::from("name"), kind: ColumnType::Text },
+        ]
+    );
+    assert_eq!(schema.to_string(), "id:int,t:float,name:text");
+    assert_eq!("id".parse::<Schema>(), Err(String::from("column 'id' has no type (name:type)")));
+    assert_eq!("id:date".parse::<Schema>(), Err(String::from("unknown type 'date' for column 'id' (int, float or text)")));
+    assert_eq!(":int".parse::<Schema>(), Err(String::from("invalid column name ''")));
+}
+
+#[test]
+fn written_to_a_file_in_a_new_directory() {
+    let directory = std::env::temp_dir().join(format!("async_benchmark-fixture-{}", std::process::id()));
+    let path = directory.join("nested").join("data.csv");
+    let rows = fixture::write_file(&path, &fixture::DEFAULT_SCHEMA.parse().unwrap(), Size::Rows(10), 42).unwrap();
+    assert_eq!(rows, 10);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), generate(fixture::DEFAULT_SCHEMA, Size::Rows(10), 42));
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+// The default input does not depend on the directory the program is started from.
+#[test]
+fn default_input_is_next_to_the_crate() {
+    let input = fixture::default_input();
+    assert!(input.is_absolute());
+    assert_eq!(input.parent().unwrap(), Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap());
+    assert_eq!(input.file_name().unwrap(), "data.csv");
+    assert_eq!(Config::default().input, input);
+}
@@ -8,13 +8,20 @@ use std::time::Duration;
 // To create a new thread, we call the thread::spawn function, 
 // and pass a closure to it, this closure contains the code we want to run in the new thread.
 
+// The threads also record what they do with the tracer in '../thread_tracer' (thread_tracer::spawn is
+// thread::spawn with a thread name), and main shows the timeline just before it exits:
+// the [SPAWNED] column stops when main exits, and its 'run' is listed as unfinished.
+// 'cargo run -- --chrome trace.json' also saves the trace for chrome://tracing or https://ui.perfetto.dev
+
 fn main(){
 
     // Creating 20 threads that will print some text at each time interval 
     println!("-> Create and spawn 20 threads that should print some text every 10 mili seconds!");
-    thread::spawn( || {
+    thread_tracer::event("spawn");
+    thread_tracer::spawn("spawned", || {
         for i in 1..20 {
             println!("[SPAWNED] thread : Message from thread #:{} ", i);
+            thread_tracer::event(format!("message {}", i));
             thread::sleep(Duration::from_millis(10));
         }
     });
@@ -22,6 +29,7 @@ fn main(){
     println!("-> Main thread will print 10 times a message every 10 miliseconds!\n");
     for i in 1..10 {
         println!("[MAIN] thread : Message #{} from the MAIN thread!", i);
+        thread_tracer::event(format!("message {}", i));
         thread::sleep(Duration::from_millis(10));
     }
 
@@ -37,5 +45,7 @@ fn main(){
 
     // SEE example 'joining threads' in order to see how to fix this behavior 
 
+    thread_tracer::event("main exits");
+    thread_tracer::show(&thread_tracer::snapshot(), std::env::args());
 }
 
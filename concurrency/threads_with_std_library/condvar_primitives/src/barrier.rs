@@ -0,0 +1,97 @@
+// A reusable barrier: `n` threads call wait(), nobody goes on until all `n` have arrived.
+//
+//      thread 1  ──work──> wait ......... ┐
+//      thread 2  ──work────────> wait ... ├──> all go on (round 1) ──work──> wait ... (round 2)
+//      thread 3  ──work──────────────> wait ┘
+//
+// Counting the arrived threads is not enough to reuse the barrier: when the last thread arrives and
+// resets the count to 0 for the next round, a thread woken up late would see "0 arrived" and wait
+// again, forever. So the barrier also counts rounds (generation): a waiting thread goes on as soon as
+// the generation it arrived in is over, whatever the count is by then.
+
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+#[derive(Debug)]
+struct State {
+    arrived: usize,
+    generation: u64,
+}
+
+#[derive(Debug)]
+pub struct Barrier {
+    state: Mutex<State>,
+    all_arrived: Condvar,
+    n: usize,
+}
+
+/// Returned by [`Barrier::wait`]: exactly one thread of every round is the leader (the last one to arrive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+}
+
+impl BarrierWaitResult {
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
+
+impl Barrier {
+    /// A barrier for `n` threads (with 0 or 1, wait() never blocks).
+    pub fn new(n: usize) -> Self {
+        Barrier { state: Mutex::new(State { arrived: 0, generation: 0 }), all_arrived: Condvar::new(), n }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Waits until `n` threads have called wait() in this round.
+    pub fn wait(&self) -> BarrierWaitResult {
+        let mut state = self.lock();
+        let generation = state.generation;
+        state.arrived += 1;
+        if state.arrived >= self.n {
+            // the last one: start the next round and wake everybody up
+            state.arrived = 0;
+            state.generation += 1;
+            self.all_arrived.notify_all();
+            return BarrierWaitResult { is_leader: true };
+        }
+        // a spurious wake-up leaves the generation unchanged: wait again
+        while state.generation == generation {
+            state = self.all_arrived.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        BarrierWaitResult { is_leader: false }
+    }
+}
+
+// The test needs to wake the waiting thread up without a round ending: it notifies the private Condvar.
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn spurious_wake_ups_do_not_open_the_barrier() {
+        let barrier = Barrier::new(2);
+        let passed = AtomicBool::new(false);
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                assert!(!barrier.wait().is_leader());
+                passed.store(true, Ordering::SeqCst);
+            });
+            for _ in 0..20 {
+                // a wake-up with nothing changed, like a spurious one
+                barrier.all_arrived.notify_all();
+                thread::sleep(Duration::from_millis(2));
+            }
+            assert!(!passed.load(Ordering::SeqCst), "went through the barrier alone");
+            assert!(barrier.wait().is_leader());
+        });
+        assert!(passed.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,290 @@
+// A bounded multi-producer multi-consumer channel.
+//
+//      Sender ─┐                                        ┌─ Receiver
+//      Sender ─┼─ send ──> [ VecDeque, at most `capacity` ] ── recv ──┼─ Receiver
+//      Sender ─┘                                        └─ Receiver
+//
+// - send() waits while the queue is full, recv() waits while it is empty: two conditions, so two
+//   Condvars (not_full for the senders, not_empty for the receivers), both used with the same Mutex.
+// - Senders and Receivers are counted. When the last Receiver is dropped, nobody will ever read:
+//   send() fails and gives the value back. When the last Sender is dropped, nothing more will come:
+//   recv() returns the values still queued, then fails.
+// - A value wakes up one receiver (notify_one), a disconnection wakes up everybody (notify_all):
+//   all the waiting threads must see it.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receivers: usize,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> Shared<T> {
+    // No user code runs while the lock is held, so a panic cannot leave the state half updated.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn wait<'a>(&self, condvar: &Condvar, state: MutexGuard<'a, State<T>>) -> MutexGuard<'a, State<T>> {
+        condvar.wait(state).unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Creates a channel holding at most `capacity` values. Both ends can be cloned.
+///
+/// Panics if `capacity` is 0.
+pub fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "the capacity of a channel must be at least 1");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State { queue: VecDeque::with_capacity(capacity), senders: 1, receivers: 1 }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        capacity,
+    });
+    (Sender { shared: Arc::clone(&shared) }, Receiver { shared })
+}
+
+/// The value could not be sent: every Receiver is gone. The value is given back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sending on a channel without receivers")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is full, the value is given back.
+    Full(T),
+    /// Every Receiver is gone, the value is given back.
+    Disconnected(T),
+}
+
+/// Nothing more to receive: the channel is empty and every Sender is gone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on an empty channel without senders")
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// Nothing yet, but senders remain.
+    Empty,
+    /// The channel is empty and every Sender is gone.
+    Disconnected,
+}
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Queues a value, waiting while the channel is full.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        loop {
+            if state.receivers == 0 {
+                return Err(SendError(value));
+            }
+            if state.queue.len() < self.shared.capacity {
+                break;
+            }
+            // woken up by a recv(), by the last Receiver leaving... or by nothing: check again
+            state = self.shared.wait(&self.shared.not_full, state);
+        }
+        state.queue.push_back(value);
+        self.shared.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Queues a value if there is room, without waiting.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.shared.lock();
+        if state.receivers == 0 {
+            Err(TrySendError::Disconnected(value))
+        } else if state.queue.len() == self.shared.capacity {
+            Err(TrySendError::Full(value))
+        } else {
+            state.queue.push_back(value);
+            self.shared.not_empty.notify_one();
+            Ok(())
+        }
+    }
+
+    /// Number of values waiting in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Sender { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            // every waiting receiver must see that nothing more will come
+            self.shared.not_empty.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("capacity", &self.shared.capacity).finish()
+    }
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Takes the oldest value, waiting while the channel is empty.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            // values sent before the last Sender left are still delivered
+            if let Some(value) = state.queue.pop_front() {
+                self.shared.not_full.notify_one();
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = self.shared.wait(&self.shared.not_empty, state);
+        }
+    }
+
+    /// Takes the oldest value if there is one, without waiting.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.queue.pop_front() {
+            Some(value) => {
+                self.shared.not_full.notify_one();
+                Ok(value)
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// The values, as they arrive, until the channel is empty and every Sender is gone.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.recv().ok())
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().receivers += 1;
+        Receiver { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receivers -= 1;
+        if state.receivers == 0 {
+            // every waiting sender must see that nobody will read
+            self.shared.not_full.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("capacity", &self.shared.capacity).finish()
+    }
+}
+
+// The test needs to wake the waiting threads up without changing the queue: it notifies the private Condvars.
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    // a wake-up with nothing changed, like a spurious one
+    fn notify_spuriously<T>(shared: &Shared<T>) {
+        shared.not_empty.notify_all();
+        shared.not_full.notify_all();
+    }
+
+    #[test]
+    fn spurious_wake_ups_do_not_release_waiting_threads() {
+        let (sender, receiver) = bounded(1);
+        sender.send(0).unwrap();
+        let (received, sent) = (AtomicBool::new(false), AtomicBool::new(false));
+        let (empty_sender, empty_receiver) = bounded::<i32>(1);
+
+        thread::scope(|scope| {
+            // waits on a full channel
+            scope.spawn(|| {
+                sender.send(1).unwrap();
+                sent.store(true, Ordering::SeqCst);
+            });
+            // waits on an empty channel
+            scope.spawn(|| {
+                assert_eq!(empty_receiver.recv(), Ok(7));
+                received.store(true, Ordering::SeqCst);
+            });
+            for _ in 0..20 {
+                notify_spuriously(&sender.shared);
+                notify_spuriously(&empty_sender.shared);
+                thread::sleep(Duration::from_millis(2));
+            }
+            assert!(!sent.load(Ordering::SeqCst), "woken up although the channel is still full");
+            assert!(!received.load(Ordering::SeqCst), "woken up although the channel is still empty");
+            assert_eq!(receiver.recv(), Ok(0));
+            empty_sender.send(7).unwrap();
+        });
+        assert!(sent.load(Ordering::SeqCst) && received.load(Ordering::SeqCst));
+        assert_eq!(receiver.recv(), Ok(1));
+    }
+}
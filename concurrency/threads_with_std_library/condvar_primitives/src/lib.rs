@@ -0,0 +1,24 @@
+// Synchronization primitives built only from Mutex and Condvar.
+//
+// A Mutex protects data. A Condvar (condition variable) lets a thread sleep until the data is in
+// a state it can use ("the queue is not empty", "a permit is free"), without spinning:
+//
+//      let mut state = mutex.lock().unwrap();
+//      while !condition(&state) {                  // a while, not an if (see below)
+//          state = condvar.wait(state).unwrap();   // unlocks, sleeps, locks again when woken up
+//      }
+//      // the condition holds, and we hold the lock
+//
+// and the thread that changes the state wakes the sleepers up with notify_one() / notify_all().
+//
+// NOTE: Spurious wake-ups. wait() may return although nobody called notify (the OS is allowed to),
+// and even after a real notify another thread may have taken the lock first and changed the state again.
+// So a woken thread must always check the condition again: that is the while loop.
+//
+// - channel: a bounded queue, many senders and many receivers (MPMC)
+// - semaphore: a counter of permits, acquire() waits until one is free
+// - barrier: N threads wait for each other, then all go on; usable again for the next round
+
+pub mod barrier;
+pub mod channel;
+pub mod semaphore;
@@ -0,0 +1,72 @@
+use std::thread;
+use std::time::Duration;
+
+use condvar_primitives::barrier::Barrier;
+use condvar_primitives::channel;
+use condvar_primitives::semaphore::Semaphore;
+
+fn main() {
+    println!("Example 1: bounded channel");
+    // 2 producers, 2 consumers, room for 3 values: the producers are faster than the consumers,
+    // so they fill the channel and then wait (send blocks) until a consumer makes room.
+    let (sender, receiver) = channel::bounded::<String>(3);
+    thread::scope(|scope| {
+        for p in 1..=2 {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                for i in 1..=4 {
+                    sender.send(format!("P{}-{}", p, i)).unwrap();
+                    println!("[producer {}] sent P{}-{} ({} waiting in the channel)", p, p, i, sender.len());
+                }
+                // the clone is dropped here
+            });
+        }
+        for c in 1..=2 {
+            let receiver = receiver.clone();
+            scope.spawn(move || {
+                // ends when the channel is empty and every sender is gone
+                for value in receiver.iter() {
+                    println!("[consumer {}] received {}", c, value);
+                    thread::sleep(Duration::from_millis(10));
+                }
+                println!("[consumer {}] channel disconnected", c);
+            });
+        }
+        // the consumers only stop once *every* sender is dropped, this one included
+        drop(sender);
+    });
+
+    println!("Example 2: semaphore");
+    // 6 threads, but at most 2 "downloads" at the same time
+    let downloads = Semaphore::new(2);
+    thread::scope(|scope| {
+        for t in 1..=6 {
+            let downloads = &downloads;
+            scope.spawn(move || {
+                let _permit = downloads.acquire();
+                println!("[thread {}] downloading ({} free permits)", t, downloads.available_permits());
+                thread::sleep(Duration::from_millis(20));
+                println!("[thread {}] done", t);
+                // _permit is dropped here: the next thread can start
+            });
+        }
+    });
+
+    println!("Example 3: barrier");
+    // 3 threads work in rounds: nobody starts round 2 before everybody finished round 1
+    let barrier = Barrier::new(3);
+    thread::scope(|scope| {
+        for t in 1..=3 {
+            let barrier = &barrier;
+            scope.spawn(move || {
+                for round in 1..=2 {
+                    thread::sleep(Duration::from_millis(10 * t));
+                    println!("[thread {}] finished round {}", t, round);
+                    if barrier.wait().is_leader() {
+                        println!("[thread {}] was the last one, round {} is over", t, round);
+                    }
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,110 @@
+// A counting semaphore: a number of permits shared by threads.
+//
+// acquire() takes a permit, waiting while there is none; the permit goes back when the returned
+// SemaphorePermit is dropped (like a MutexGuard). With N permits, at most N threads hold one at the
+// same time: e.g. at most 3 downloads at once, whatever the number of threads.
+//
+// A Mutex<usize> holds the number of free permits, and a Condvar wakes up a waiting thread when a
+// permit is released.
+
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+#[derive(Debug)]
+pub struct Semaphore {
+    permits: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Semaphore { permits: Mutex::new(permits), released: Condvar::new() }
+    }
+
+    // Only this file changes the count, never with user code running: the lock cannot be poisoned
+    // in the middle of an update.
+    fn lock(&self) -> MutexGuard<'_, usize> {
+        self.permits.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Takes a permit, waiting until one is free.
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        let mut permits = self.lock();
+        // while, not if: a wake-up does not mean a permit is free (spurious wake-up, or another thread took it)
+        while *permits == 0 {
+            permits = self.released.wait(permits).unwrap_or_else(PoisonError::into_inner);
+        }
+        *permits -= 1;
+        SemaphorePermit { semaphore: self }
+    }
+
+    /// Takes a permit if one is free, without waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let mut permits = self.lock();
+        if *permits == 0 {
+            None
+        } else {
+            *permits -= 1;
+            Some(SemaphorePermit { semaphore: self })
+        }
+    }
+
+    /// Number of free permits (it may change as soon as it is returned).
+    pub fn available_permits(&self) -> usize {
+        *self.lock()
+    }
+
+    /// Adds permits, e.g. to let more threads in.
+    pub fn add_permits(&self, count: usize) {
+        *self.lock() += count;
+        self.released.notify_all();
+    }
+
+    fn release(&self) {
+        *self.lock() += 1;
+        // one permit: one waiting thread can use it
+        self.released.notify_one();
+    }
+}
+
+/// A permit of a [`Semaphore`], given back when dropped.
+#[derive(Debug)]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.release();
+    }
+}
+
+// The test needs to wake the waiting thread up without releasing a permit: it notifies the private Condvar.
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn spurious_wake_ups_do_not_give_a_permit() {
+        let semaphore = Semaphore::new(1);
+        let permit = semaphore.acquire();
+        let acquired = AtomicBool::new(false);
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let _permit = semaphore.acquire();
+                acquired.store(true, Ordering::SeqCst);
+            });
+            for _ in 0..20 {
+                // a wake-up with no permit released, like a spurious one
+                semaphore.released.notify_all();
+                thread::sleep(Duration::from_millis(2));
+            }
+            assert!(!acquired.load(Ordering::SeqCst), "acquired a permit nobody released");
+            drop(permit);
+        });
+        assert!(acquired.load(Ordering::SeqCst));
+    }
+}
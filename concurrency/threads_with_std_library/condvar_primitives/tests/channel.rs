@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use condvar_primitives::channel::{self, RecvError, SendError, TryRecvError, TrySendError};
+
+#[test]
+fn values_arrive_in_order() {
+    let (sender, receiver) = channel::bounded(2);
+    let producer = thread::spawn(move || {
+        for i in 0..1_000 {
+            sender.send(i).unwrap();
+        }
+    });
+    let received: Vec<i32> = receiver.iter().collect();
+    producer.join().unwrap();
+    assert_eq!(received, (0..1_000).collect::<Vec<_>>());
+}
+
+#[test]
+fn send_waits_while_the_channel_is_full() {
+    let (sender, receiver) = channel::bounded(2);
+    sender.send(1).unwrap();
+    sender.send(2).unwrap();
+    assert_eq!(sender.try_send(3), Err(TrySendError::Full(3)));
+
+    let sent = AtomicBool::new(false);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            sender.send(3).unwrap();
+            sent.store(true, Ordering::SeqCst);
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert!(!sent.load(Ordering::SeqCst), "send should wait for room");
+        assert_eq!(receiver.recv(), Ok(1));
+    });
+    assert!(sent.load(Ordering::SeqCst));
+    assert_eq!(receiver.try_recv(), Ok(2));
+    assert_eq!(receiver.try_recv(), Ok(3));
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+}
+
+// Several producers and consumers: every value is received exactly once, and the values
+// of a producer are received in the order it sent them.
+#[test]
+fn many_producers_and_consumers() {
+    const PRODUCERS: usize = 4;
+    const VALUES: usize = 2_000;
+    let (sender, receiver) = channel::bounded::<(usize, usize)>(8);
+
+    let received: Vec<Vec<(usize, usize)>> = thread::scope(|scope| {
+        for p in 0..PRODUCERS {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                for i in 0..VALUES {
+                    sender.send((p, i)).unwrap();
+                }
+            });
+        }
+        drop(sender);
+        let consumers: Vec<_> = (0..3)
+            .map(|_| {
+                let receiver = receiver.clone();
+                scope.spawn(move || receiver.iter().collect::<Vec<_>>())
+            })
+            .collect();
+        consumers.into_iter().map(|consumer| consumer.join().unwrap()).collect()
+    });
+
+    let mut per_producer: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for consumer in &received {
+        let mut last_seen: BTreeMap<usize, usize> = BTreeMap::new();
+        for &(p, i) in consumer {
+            // a consumer sees the values of a producer in increasing order
+            if let Some(previous) = last_seen.insert(p, i) {
+                assert!(previous < i);
+            }
+            per_producer.entry(p).or_default().push(i);
+        }
+    }
+    assert_eq!(per_producer.len(), PRODUCERS);
+    for values in per_producer.values_mut() {
+        values.sort();
+        assert_eq!(*values, (0..VALUES).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn receivers_get_the_queued_values_then_an_error_once_every_sender_is_gone() {
+    let (sender, receiver) = channel::bounded(4);
+    let other = sender.clone();
+    sender.send("a").unwrap();
+    other.send("b").unwrap();
+    drop(sender);
+    // one sender is left
+    assert_eq!(receiver.recv(), Ok("a"));
+    drop(other);
+    assert_eq!(receiver.recv(), Ok("b"));
+    assert_eq!(receiver.recv(), Err(RecvError));
+    assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn a_waiting_receiver_wakes_up_when_the_last_sender_leaves() {
+    let (sender, receiver) = channel::bounded::<u8>(1);
+    let receivers: Vec<_> = (0..3)
+        .map(|_| {
+            let receiver = receiver.clone();
+            thread::spawn(move || receiver.recv())
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(20));
+    drop(sender);
+    for receiver in receivers {
+        assert_eq!(receiver.join().unwrap(), Err(RecvError));
+    }
+}
+
+#[test]
+fn senders_get_their_value_back_once_every_receiver_is_gone() {
+    let (sender, receiver) = channel::bounded(1);
+    sender.send(1).unwrap();
+    // the channel is full: these senders wait...
+    let senders: Vec<_> = (2..5)
+        .map(|value| {
+            let sender = sender.clone();
+            thread::spawn(move || sender.send(value))
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(20));
+    // ...until nobody can read anymore
+    drop(receiver);
+    let mut errors: Vec<i32> = senders.into_iter().map(|sender| sender.join().unwrap().unwrap_err().0).collect();
+    errors.sort();
+    assert_eq!(errors, vec![2, 3, 4]);
+    assert_eq!(sender.send(5), Err(SendError(5)));
+    assert_eq!(sender.try_send(6), Err(TrySendError::Disconnected(6)));
+}
+
+#[test]
+#[should_panic(expected = "at least 1")]
+fn a_channel_needs_room_for_a_value() {
+    channel::bounded::<()>(0);
+}
@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use condvar_primitives::barrier::Barrier;
+use condvar_primitives::semaphore::Semaphore;
+
+#[test]
+fn permits_are_taken_and_given_back() {
+    let semaphore = Semaphore::new(2);
+    let first = semaphore.acquire();
+    let second = semaphore.try_acquire().unwrap();
+    assert!(semaphore.try_acquire().is_none());
+    assert_eq!(semaphore.available_permits(), 0);
+    drop(first);
+    assert_eq!(semaphore.available_permits(), 1);
+    drop(second);
+    semaphore.add_permits(3);
+    assert_eq!(semaphore.available_permits(), 5);
+}
+
+#[test]
+fn no_more_threads_than_permits_at_the_same_time() {
+    let semaphore = Semaphore::new(3);
+    let (inside, most) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    thread::scope(|scope| {
+        for _ in 0..20 {
+            scope.spawn(|| {
+                for _ in 0..20 {
+                    let _permit = semaphore.acquire();
+                    let now = inside.fetch_add(1, Ordering::SeqCst) + 1;
+                    most.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_micros(200));
+                    inside.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    assert_eq!(most.load(Ordering::SeqCst), 3);
+    assert_eq!(semaphore.available_permits(), 3);
+}
+
+// Every thread must see every other thread's work of a round before starting the next one,
+// round after round with the same barrier.
+#[test]
+fn barrier_can_be_reused_round_after_round() {
+    const THREADS: usize = 5;
+    const ROUNDS: usize = 50;
+    let barrier = Barrier::new(THREADS);
+    let done = AtomicUsize::new(0);
+    let leaders = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for round in 0..ROUNDS {
+                    done.fetch_add(1, Ordering::SeqCst);
+                    if barrier.wait().is_leader() {
+                        leaders.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // everybody has done this round, and nobody can start the next one before
+                    // everybody has checked (the second wait)
+                    assert_eq!(done.load(Ordering::SeqCst), (round + 1) * THREADS, "round {}", round);
+                    barrier.wait();
+                }
+            });
+        }
+    });
+    // one leader per round (each round has two waits, only the first one is counted)
+    assert_eq!(leaders.load(Ordering::SeqCst), ROUNDS);
+}
@@ -0,0 +1,3 @@
+// The parallel functions used by main.rs, in a library so the tests in tests/ can use them too.
+
+pub mod parallel;
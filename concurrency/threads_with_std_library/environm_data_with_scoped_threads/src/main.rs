@@ -1,4 +1,7 @@
 use std::thread;
+use std::time::Instant;
+
+use environm_data_with_scoped_threads::parallel::{self, par_map_reduce, par_sort};
 
 
 // Scope in rust: 
@@ -56,9 +59,51 @@ fn main() {
      });
 
       // get results of both threads and return
-      return (t1.join().unwrap(), t2.join().unwrap());
+      (t1.join().unwrap(), t2.join().unwrap())
     });
 
     println!("thread 1: {}\nthread 2: {}", t1, t2);
 
+    // 3) The same idea on large inputs: map-reduce and sort in N chunks (see src/parallel.rs)
+    println!("Example 3:");
+    parallel_versus_sequential(10_000_000);
+}
+
+// Times the parallel functions against the sequential ones. Run with --release, otherwise the
+// times mostly measure unoptimized code.
+fn parallel_versus_sequential(len: usize) {
+    let chunks = parallel::default_chunks();
+    println!("{} elements, {} chunks (available parallelism)", len, chunks);
+
+    // pseudo-random numbers (xorshift), always the same ones
+    let mut x: u64 = 0x2545F4914F6CDD1D;
+    let numbers: Vec<u64> = (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x % 1_000_000
+        })
+        .collect();
+
+    // sum of squares: the threads borrow `numbers`, nothing is copied
+    let start = Instant::now();
+    let sequential = numbers.iter().map(|n| n * n).reduce(|a, b| a + b);
+    let sequential_time = start.elapsed();
+    let start = Instant::now();
+    let parallel = par_map_reduce(&numbers, chunks, |n| n * n, |a, b| a + b);
+    let parallel_time = start.elapsed();
+    assert_eq!(sequential, parallel);
+    println!("sum of squares  sequential {:>10.2?}   parallel {:>10.2?}", sequential_time, parallel_time);
+
+    let mut sequential = numbers.clone();
+    let start = Instant::now();
+    sequential.sort();
+    let sequential_time = start.elapsed();
+    let mut parallel = numbers;
+    let start = Instant::now();
+    par_sort(&mut parallel, chunks);
+    let parallel_time = start.elapsed();
+    assert_eq!(sequential, parallel);
+    println!("sort            sequential {:>10.2?}   parallel {:>10.2?}", sequential_time, parallel_time);
 }
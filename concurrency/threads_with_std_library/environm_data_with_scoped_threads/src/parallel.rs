@@ -0,0 +1,130 @@
+// Parallel map-reduce and merge sort over slices, with scoped threads.
+//
+// The examples in main.rs split a vector in two halves for two scoped threads. The same idea, generalized:
+// cut the slice into N chunks (one per CPU core by default), give each chunk to a scoped thread, and
+// combine the results of the chunks.
+//
+//      [ .......... slice .......... ]
+//      [ chunk 0 ][ chunk 1 ][ chunk 2 ]       each chunk is borrowed by one thread (no copy)
+//          |          |          |
+//         r0         r1         r2             map + reduce inside every chunk, in parallel
+//          \_________ | ________/
+//                  reduce                      then the results of the chunks, in order
+//
+// Scoped threads can borrow the slice because thread::scope() waits for every thread before it returns:
+// the borrow cannot outlive the data. With thread::spawn the data would have to be moved or put in an Arc.
+//
+// Threads are not free: below a few thousand elements, a chunk is faster done by the current thread.
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// Below this length, a slice is not split any further.
+pub const MIN_CHUNK_LEN: usize = 4096;
+
+/// The number of chunks to use: the number of threads the machine can run in parallel.
+pub fn default_chunks() -> usize {
+    thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1)
+}
+
+/// Maps every element and combines the results with `reduce`, in up to `chunks` threads.
+/// Returns None for an empty slice.
+///
+/// The chunks are combined in order, so `reduce` only needs to be associative
+/// (`reduce(a, reduce(b, c)) == reduce(reduce(a, b), c)`), not commutative: the result is the same as
+/// `slice.iter().map(map).reduce(reduce)`.
+pub fn par_map_reduce<T, R, M, F>(slice: &[T], chunks: usize, map: M, reduce: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    M: Fn(&T) -> R + Sync,
+    F: Fn(R, R) -> R + Sync,
+{
+    let chunk_len = slice.len().div_ceil(chunks.max(1)).max(MIN_CHUNK_LEN);
+    // the closures are shared (borrowed) by all the threads: that is why they must be Sync
+    let (map, reduce) = (&map, &reduce);
+    let mut chunks = slice.chunks(chunk_len);
+    let last = chunks.next_back();
+    let results: Vec<Option<R>> = thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .map(|chunk| scope.spawn(move || chunk.iter().map(map).reduce(reduce)))
+            .collect();
+        // the last chunk is done by the current thread while the others run: one chunk, no thread at all
+        let last = last.and_then(|chunk| chunk.iter().map(map).reduce(reduce));
+        let mut results: Vec<Option<R>> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        results.push(last);
+        results
+    });
+    results.into_iter().flatten().reduce(reduce)
+}
+
+/// Sorts the slice with a merge sort: the two halves are sorted in parallel (recursively, up to
+/// `chunks` threads), then merged. Stable, like `slice.sort()`. The elements are moved, never cloned.
+pub fn par_sort<T: Ord + Send>(slice: &mut [T], chunks: usize) {
+    if chunks <= 1 || slice.len() < 2 * MIN_CHUNK_LEN {
+        slice.sort();
+        return;
+    }
+    let mid = slice.len() / 2;
+    // two disjoint &mut: one for the other thread, one for this one
+    let (left, right) = slice.split_at_mut(mid);
+    thread::scope(|scope| {
+        scope.spawn(|| par_sort(left, chunks / 2));
+        par_sort(right, chunks - chunks / 2);
+    });
+    merge(slice, mid);
+}
+
+// Merges the sorted slice[..mid] and slice[mid..], like the merge of std's sort: the left half is
+// moved (bitwise copied) to a scratch buffer, which leaves a gap in the slice, and the elements are moved
+// back into the gap, the smallest first. The gap [dest, right) is always as long as what is left of the
+// left half, so writing at `dest` never overwrites an element of the right half not merged yet.
+//
+//      scratch: [ l1 l2 ]          slice: [ r0 .. .. | r1 r2 r3 ]      r0 < l1: r0 moved, then l1 < r1 ...
+//                 ^start                     ^dest      ^right
+fn merge<T: Ord>(slice: &mut [T], mid: usize) {
+    let len = slice.len();
+    let mut scratch: Vec<T> = Vec::with_capacity(mid);
+    let base = slice.as_mut_ptr();
+    // SAFETY: the scratch buffer has room for `mid` elements, and does not overlap the slice. Its length
+    // stays 0: the Vec only frees its memory, it never drops the elements. Every element is read and
+    // written through raw pointers only, and ends up in the slice exactly once, even if `<=` panics
+    // (see Gap).
+    unsafe {
+        std::ptr::copy_nonoverlapping(base, scratch.as_mut_ptr(), mid);
+        let mut gap = Gap { start: scratch.as_mut_ptr(), end: scratch.as_mut_ptr().add(mid), dest: base };
+        let (mut right, right_end) = (base.add(mid), base.add(len));
+        while gap.start < gap.end && right < right_end {
+            // <= keeps equal elements in their order (stable)
+            if *gap.start <= *right {
+                std::ptr::copy_nonoverlapping(gap.start, gap.dest, 1);
+                gap.start = gap.start.add(1);
+            } else {
+                std::ptr::copy_nonoverlapping(right, gap.dest, 1);
+                right = right.add(1);
+            }
+            gap.dest = gap.dest.add(1);
+        }
+        // dropping the gap moves what is left of the left half into place;
+        // what is left of the right half is already in place
+    }
+}
+
+// The elements of the scratch buffer not merged yet, [start, end), and where they go in the slice.
+// When it is dropped, at the end of merge() or while a panicking `<=` unwinds, it moves them into
+// the gap: no element is lost or left twice in the slice.
+struct Gap<T> {
+    start: *mut T,
+    end: *mut T,
+    dest: *mut T,
+}
+
+impl<T> Drop for Gap<T> {
+    fn drop(&mut self) {
+        // SAFETY: [start, end) are the elements not merged yet, and the gap at `dest` has room for them
+        unsafe {
+            let count = self.end.offset_from(self.start) as usize;
+            std::ptr::copy_nonoverlapping(self.start, self.dest, count);
+        }
+    }
+}
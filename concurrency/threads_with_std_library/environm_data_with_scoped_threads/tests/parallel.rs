@@ -0,0 +1,157 @@
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use environm_data_with_scoped_threads::parallel::{self, par_map_reduce, par_sort, MIN_CHUNK_LEN};
+
+// pseudo-random numbers, always the same ones
+fn numbers(len: usize, seed: u64) -> Vec<u64> {
+    let mut x = seed | 1;
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x % 1_000
+        })
+        .collect()
+}
+
+#[test]
+fn map_reduce_gives_the_sequential_result() {
+    let values = numbers(100_000, 1);
+    let expected = values.iter().map(|n| n * 3).reduce(|a, b| a + b);
+    for chunks in [0, 1, 2, 3, 7, 64] {
+        assert_eq!(par_map_reduce(&values, chunks, |n| n * 3, |a, b| a + b), expected, "{} chunks", chunks);
+    }
+    assert_eq!(par_map_reduce(&values, parallel::default_chunks(), |n| *n, u64::max), values.iter().copied().max());
+}
+
+#[test]
+fn map_reduce_keeps_the_order_of_the_chunks() {
+    // concatenation is associative but not commutative: the chunks must be combined in order
+    let words: Vec<String> = (0..3 * MIN_CHUNK_LEN).map(|i| (i % 10).to_string()).collect();
+    let joined = par_map_reduce(&words, 4, |word| word.clone(), |a, b| a + &b).unwrap();
+    assert_eq!(joined, words.concat());
+}
+
+#[test]
+fn map_reduce_of_an_empty_slice() {
+    let empty: [u32; 0] = [];
+    assert_eq!(par_map_reduce(&empty, 4, |n| *n, |a, b| a + b), None);
+}
+
+#[test]
+fn a_single_chunk_runs_on_the_current_thread() {
+    let values = numbers(MIN_CHUNK_LEN, 3);
+    let current = thread::current().id();
+    assert_eq!(par_map_reduce(&values, 8, |_| thread::current().id() == current, |a, b| a && b), Some(true));
+}
+
+#[test]
+fn par_sort_sorts_like_sort() {
+    for (len, chunks) in [(0, 4), (1, 4), (1_000, 4), (100_003, 1), (100_003, 3), (100_003, 8)] {
+        let mut values = numbers(len, len as u64);
+        let mut expected = values.clone();
+        expected.sort();
+        par_sort(&mut values, chunks);
+        assert_eq!(values, expected, "{} elements, {} chunks", len, chunks);
+    }
+}
+
+#[test]
+fn par_sort_is_stable() {
+    // sorted by key only: equal keys must keep their original order (the index)
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Item {
+        key: u64,
+        index: usize,
+    }
+    impl PartialOrd for Item {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Item {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    let mut items: Vec<Item> = numbers(50_000, 7).into_iter().enumerate().map(|(index, n)| Item { key: n % 10, index }).collect();
+    par_sort(&mut items, 8);
+    assert!(items.windows(2).all(|pair| pair[0].key < pair[1].key || (pair[0].key == pair[1].key && pair[0].index < pair[1].index)));
+}
+
+// Not Clone: the merge moves the elements. Every Item alive is counted, to check that none is lost or
+// duplicated, even when a comparison panics in the middle of a merge.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Counted(u64);
+
+static ALIVE: AtomicUsize = AtomicUsize::new(0);
+
+impl Counted {
+    fn new(value: u64) -> Self {
+        ALIVE.fetch_add(1, Ordering::SeqCst);
+        Counted(value)
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        ALIVE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn par_sort_moves_elements_without_cloning_them() {
+    let mut values: Vec<Counted> = numbers(100_003, 5).into_iter().map(Counted::new).collect();
+    par_sort(&mut values, 8);
+    assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+    drop(values);
+    assert_eq!(ALIVE.load(Ordering::SeqCst), 0);
+
+    // a comparison that panics in the middle of the last merge (done by the current thread, after it
+    // sorted the right half): the slice still holds every element once
+    thread_local! {
+        static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+        static PANIC_AT: Cell<usize> = const { Cell::new(usize::MAX) };
+    }
+    #[derive(Debug, PartialEq, Eq)]
+    struct Fragile(Counted);
+    impl PartialOrd for Fragile {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Fragile {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            let count = COMPARISONS.with(|count| count.replace(count.get() + 1));
+            if count == PANIC_AT.with(Cell::get) {
+                panic!("comparison failed");
+            }
+            self.0.cmp(&other.0)
+        }
+    }
+    let len = 4 * MIN_CHUNK_LEN;
+    let fragile = || numbers(len, 5).into_iter().map(|n| Fragile(Counted::new(n))).collect::<Vec<_>>();
+    // count the comparisons of the current thread: sorting the right half, then the merge
+    let mut right = fragile().split_off(len / 2);
+    right.sort();
+    let sorting = COMPARISONS.with(|count| count.replace(0));
+    par_sort(&mut fragile(), 2);
+    let merging = COMPARISONS.with(|count| count.replace(0)) - sorting;
+    drop(right);
+
+    let mut values = fragile();
+    PANIC_AT.with(|at| at.set(sorting + merging / 2));
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| par_sort(&mut values, 2))).is_err());
+    let mut kept: Vec<u64> = values.iter().map(|value| value.0 .0).collect();
+    kept.sort();
+    let mut expected = numbers(len, 5);
+    expected.sort();
+    assert_eq!(kept, expected);
+    drop(values);
+    assert_eq!(ALIVE.load(Ordering::SeqCst), 0);
+}
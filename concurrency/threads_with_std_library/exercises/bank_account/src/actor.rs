@@ -0,0 +1,131 @@
+// The bank as an actor: one thread owns all the balances, the other threads send it messages.
+//
+// bank.rs shares the balances between threads (Arc + one Mutex per account). Here nothing is shared:
+//
+//      client thread 1 ──┐
+//      client thread 2 ──┼── Command ──> [ mpsc channel ] ──> bank actor thread (owns Vec<Money>)
+//      client thread 3 ──┘                                          │
+//             ^                                                     │
+//             └──────────────── reply (one channel per command) ────┘
+//
+// The actor handles the commands one at a time, in the order they arrive, so it can use its balances
+// as plain variables: no Mutex, no lock order, no deadlock. The price is that everything goes through
+// one thread, and every operation is two channel messages (the command and its reply).
+//
+// "Do not communicate by sharing memory; instead, share memory by communicating."
+//
+// The actor stops when every BankHandle is dropped: the channel is closed, `for command in receiver`
+// ends and the thread returns the final balances.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::bank::{AccountId, BankError};
+use crate::money::{Currency, Money, MoneyError};
+
+/// What a client can ask the bank actor. Each command carries the Sender on which the actor replies.
+#[derive(Debug)]
+pub enum Command {
+    Deposit { account: AccountId, amount: Money, reply: Sender<Result<Money, BankError>> },
+    Withdraw { account: AccountId, amount: Money, reply: Sender<Result<Money, BankError>> },
+    Balance { account: AccountId, reply: Sender<Result<Money, BankError>> },
+}
+
+/// The state owned by the actor thread.
+#[derive(Debug)]
+pub struct BankActor {
+    currency: Currency,
+    balances: Vec<Money>,
+}
+
+impl BankActor {
+    /// An actor with `count` accounts, ids `0..count`, each one with the same initial balance.
+    pub fn new(count: usize, initial_balance: Money) -> Result<Self, BankError> {
+        if initial_balance.is_negative() {
+            return Err(BankError::InvalidAmount(initial_balance));
+        }
+        Ok(BankActor { currency: initial_balance.currency(), balances: vec![initial_balance; count] })
+    }
+
+    /// Starts the actor thread. The handle sends it commands (clone it for every client thread),
+    /// the JoinHandle returns the final balances once every handle is dropped.
+    pub fn spawn(self) -> (BankHandle, JoinHandle<Vec<Money>>) {
+        let (sender, receiver) = mpsc::channel();
+        let thread = thread::spawn(move || self.run(receiver));
+        (BankHandle { sender }, thread)
+    }
+
+    fn run(mut self, receiver: Receiver<Command>) -> Vec<Money> {
+        for command in receiver {
+            // the client may have stopped waiting for the reply: not an error for the bank
+            let _ = match command {
+                Command::Deposit { account, amount, reply } => reply.send(self.deposit(account, amount)),
+                Command::Withdraw { account, amount, reply } => reply.send(self.withdraw(account, amount)),
+                Command::Balance { account, reply } => reply.send(self.balance(account)),
+            };
+        }
+        self.balances
+    }
+
+    fn balance(&self, account: AccountId) -> Result<Money, BankError> {
+        self.balances.get(account).copied().ok_or(BankError::UnknownAccount(account))
+    }
+
+    fn deposit(&mut self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        self.check_amount(amount)?;
+        let balance = self.balance(account)?.checked_add(amount)?;
+        self.balances[account] = balance;
+        Ok(balance)
+    }
+
+    fn withdraw(&mut self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        self.check_amount(amount)?;
+        let balance = self.balance(account)?;
+        // no lock needed between the check and the update: only this thread touches the balances
+        if balance < amount {
+            return Err(BankError::InsufficientFunds { account, balance, requested: amount });
+        }
+        self.balances[account] = balance.checked_sub(amount)?;
+        Ok(self.balances[account])
+    }
+
+    // Amounts must be positive, in the currency of the bank (same rules as Bank)
+    fn check_amount(&self, amount: Money) -> Result<(), BankError> {
+        if amount.currency() != self.currency {
+            return Err(MoneyError::CurrencyMismatch { expected: self.currency, found: amount.currency() }.into());
+        }
+        if amount.is_positive() {
+            Ok(())
+        } else {
+            Err(BankError::InvalidAmount(amount))
+        }
+    }
+}
+
+/// The client side of the actor: sends a command and waits for the reply.
+#[derive(Debug, Clone)]
+pub struct BankHandle {
+    sender: Sender<Command>,
+}
+
+impl BankHandle {
+    pub fn deposit(&self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        self.request(|reply| Command::Deposit { account, amount, reply })
+    }
+
+    pub fn withdraw(&self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        self.request(|reply| Command::Withdraw { account, amount, reply })
+    }
+
+    pub fn balance(&self, account: AccountId) -> Result<Money, BankError> {
+        self.request(|reply| Command::Balance { account, reply })
+    }
+
+    // The actor only stops when every handle is dropped, so while this handle exists the channels
+    // can only be closed if the actor thread panicked.
+    fn request(&self, command: impl FnOnce(Sender<Result<Money, BankError>>) -> Command) -> Result<Money, BankError> {
+        let (reply, response) = mpsc::channel();
+        self.sender.send(command(reply)).expect("the bank actor has stopped");
+        response.recv().expect("the bank actor has stopped")
+    }
+}
@@ -0,0 +1,388 @@
+// A bank with many accounts, each one behind its own Mutex.
+//
+// With a single Arc<Mutex<f64>> (see main.rs), every operation waits for every other one.
+// Here each account has its own lock: operations on different accounts run in parallel.
+//
+// A transfer needs two locks (both balances must change together, otherwise another thread
+// could see the money in neither or in both accounts). Taking two locks is where deadlocks come from:
+//
+//      thread 1: transfer(A -> B)      locks A ... waits for B
+//      thread 2: transfer(B -> A)      locks B ... waits for A      => both wait forever
+//
+// To avoid it, every thread takes the locks in the same global order: the account with the lowest id first.
+// Then a thread holding a lock only ever waits for a lock with a higher id, so no cycle of threads
+// waiting for each other can exist.
+//
+// Balances are Money (src/money.rs), exact amounts in cents. All the accounts of a bank use its currency.
+// Every operation, accepted or rejected, is also recorded in the ledger of the bank (src/ledger.rs).
+//
+// NOTE: Poisoning. If a thread panics while it holds the lock of an account, the Mutex is "poisoned":
+// the panic may have happened in the middle of an update (e.g. a transfer that took the money from the
+// source and never gave it to the target), and every later lock() returns Err(PoisonError).
+// With lock().unwrap(), one panicking thread makes every other thread panic too.
+// Here the bank recovers instead (see lock_account): it takes the guard out of the PoisonError,
+// rebuilds the balance of the account by replaying the ledger (an operation is only recorded once it
+// is complete, so an interrupted one is rolled back), clears the poison and continues.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::ledger::{Event, Ledger, LedgerError};
+use crate::money::{Currency, Money, MoneyError};
+
+pub type AccountId = usize;
+
+#[derive(Debug)]
+pub struct Account {
+    id: AccountId,
+    balance: Mutex<Money>,
+}
+
+impl Account {
+    pub fn id(&self) -> AccountId {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BankError {
+    UnknownAccount(AccountId),
+    /// Amounts must be positive.
+    InvalidAmount(Money),
+    /// The balance cannot go below zero.
+    InsufficientFunds { account: AccountId, balance: Money, requested: Money },
+    /// A transfer from an account to itself.
+    SameAccount(AccountId),
+    /// Wrong currency, or a balance out of range.
+    Money(MoneyError),
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BankError::UnknownAccount(id) => write!(f, "unknown account {}", id),
+            BankError::InvalidAmount(amount) => write!(f, "invalid amount {}", amount),
+            BankError::InsufficientFunds { account, balance, requested } => write!(
+                f,
+                "not enough money in account {} to withdraw {} (balance {})",
+                account, requested, balance
+            ),
+            BankError::SameAccount(id) => write!(f, "cannot transfer from account {} to itself", id),
+            BankError::Money(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for BankError {}
+
+impl From<MoneyError> for BankError {
+    fn from(error: MoneyError) -> Self {
+        BankError::Money(error)
+    }
+}
+
+/// The accounts of the bank. Accounts are opened with `&mut self`, then the bank is shared
+/// between threads (`Arc<Bank>`) and all the operations only need `&self`.
+#[derive(Debug)]
+pub struct Bank {
+    currency: Currency,
+    accounts: Vec<Account>,
+    ledger: Ledger,
+    // number of poisoned locks recovered
+    recoveries: AtomicUsize,
+}
+
+impl Bank {
+    pub fn new(currency: Currency) -> Self {
+        Bank { currency, accounts: Vec::new(), ledger: Ledger::new(), recoveries: AtomicUsize::new(0) }
+    }
+
+    /// Rebuilds a bank by replaying a ledger (e.g. loaded from a file). The ledger is kept:
+    /// the next operations are appended to it.
+    pub fn from_ledger(currency: Currency, ledger: Ledger) -> Result<Self, LedgerError> {
+        // replay() checks that the accounts were opened with the ids 0, 1, 2, ...
+        let accounts = ledger
+            .replay(currency)?
+            .into_iter()
+            .map(|(id, balance)| Account { id, balance: Mutex::new(balance) })
+            .collect();
+        Ok(Bank { currency, accounts, ledger, recoveries: AtomicUsize::new(0) })
+    }
+
+    /// A bank with `count` accounts, ids `0..count`, each one with the same initial balance.
+    pub fn with_accounts(count: usize, initial_balance: Money) -> Result<Self, BankError> {
+        let mut bank = Bank::new(initial_balance.currency());
+        for _ in 0..count {
+            bank.open_account(initial_balance)?;
+        }
+        Ok(bank)
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn open_account(&mut self, initial_balance: Money) -> Result<AccountId, BankError> {
+        self.check_currency(initial_balance)?;
+        if initial_balance.is_negative() {
+            return Err(BankError::InvalidAmount(initial_balance));
+        }
+        let id = self.accounts.len();
+        self.accounts.push(Account { id, balance: Mutex::new(initial_balance) });
+        self.ledger.append(Event::AccountOpened { account: id, initial_balance });
+        Ok(id)
+    }
+
+    pub fn accounts(&self) -> &[Account] {
+        &self.accounts
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    fn account(&self, id: AccountId) -> Result<&Account, BankError> {
+        self.accounts.get(id).ok_or(BankError::UnknownAccount(id))
+    }
+
+    pub fn balance(&self, id: AccountId) -> Result<Money, BankError> {
+        Ok(*self.lock_account(self.account(id)?))
+    }
+
+    /// Adds money to an account, returns the new balance.
+    pub fn deposit(&self, id: AccountId, amount: Money) -> Result<Money, BankError> {
+        self.check_amount(amount)?;
+        let mut balance = self.lock_account(self.account(id)?);
+        *balance = balance.checked_add(amount)?;
+        // recorded while the account is still locked, see the NOTE in ledger.rs
+        self.ledger.append(Event::Deposited { account: id, amount });
+        Ok(*balance)
+    }
+
+    /// Takes money from an account if the balance allows it, returns the new balance.
+    pub fn withdraw(&self, id: AccountId, amount: Money) -> Result<Money, BankError> {
+        self.check_amount(amount)?;
+        let mut balance = self.lock_account(self.account(id)?);
+        // check and update while holding the lock: nobody can withdraw in between
+        if *balance < amount {
+            self.ledger.append(Event::WithdrawalRejected { account: id, amount, balance: *balance });
+            return Err(BankError::InsufficientFunds { account: id, balance: *balance, requested: amount });
+        }
+        *balance = balance.checked_sub(amount)?;
+        self.ledger.append(Event::Withdrawn { account: id, amount });
+        Ok(*balance)
+    }
+
+    /// Moves money from one account to another, atomically: no other thread can see it in neither or both.
+    pub fn transfer(&self, from: AccountId, to: AccountId, amount: Money) -> Result<(), BankError> {
+        self.check_amount(amount)?;
+        if from == to {
+            // locking the same Mutex twice would block forever
+            return Err(BankError::SameAccount(from));
+        }
+        let (mut source, mut target) = self.lock_pair(from, to)?;
+        if *source < amount {
+            self.ledger.append(Event::TransferRejected { from, to, amount, balance: *source });
+            return Err(BankError::InsufficientFunds { account: from, balance: *source, requested: amount });
+        }
+        // compute both balances before changing anything: an overflow leaves both accounts untouched
+        let new_target = target.checked_add(amount)?;
+        *source = source.checked_sub(amount)?;
+        *target = new_target;
+        self.ledger.append(Event::Transferred { from, to, amount });
+        Ok(())
+    }
+
+    // Locks two different accounts, lowest id first, and returns the guards in the order (a, b).
+    fn lock_pair(
+        &self,
+        a: AccountId,
+        b: AccountId,
+    ) -> Result<(MutexGuard<'_, Money>, MutexGuard<'_, Money>), BankError> {
+        let (account_a, account_b) = (self.account(a)?, self.account(b)?);
+        if a < b {
+            let guard_a = self.lock_account(account_a);
+            let guard_b = self.lock_account(account_b);
+            Ok((guard_a, guard_b))
+        } else {
+            let guard_b = self.lock_account(account_b);
+            let guard_a = self.lock_account(account_a);
+            Ok((guard_a, guard_b))
+        }
+    }
+
+    // Locks an account, recovering from a poisoned lock instead of panicking (see the NOTE at the top).
+    fn lock_account<'a>(&'a self, account: &'a Account) -> MutexGuard<'a, Money> {
+        match account.balance.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                // we still get the guard (and the lock): the poison is only a warning
+                let mut guard = poisoned.into_inner();
+                // The events of this account are only appended while its lock is held, and we hold it:
+                // the ledger contains exactly the operations that completed. Replaying them gives the
+                // balance before the interrupted operation.
+                // (accounts first, then the ledger: same order as everywhere else)
+                let replayed = self
+                    .ledger
+                    .replay(self.currency)
+                    .ok()
+                    .and_then(|balances| balances.get(&account.id).copied())
+                    .expect("the ledger cannot be replayed, the balance cannot be recovered");
+                *guard = replayed;
+                // the balance is valid again: the next lock() returns Ok
+                account.balance.clear_poison();
+                self.recoveries.fetch_add(1, Ordering::Relaxed);
+                guard
+            }
+        }
+    }
+
+    /// How many times a poisoned account lock was recovered.
+    pub fn poison_recoveries(&self) -> usize {
+        self.recoveries.load(Ordering::Relaxed)
+    }
+
+    // Locks every account, in id order.
+    fn lock_all(&self) -> Vec<MutexGuard<'_, Money>> {
+        self.accounts.iter().map(|account| self.lock_account(account)).collect()
+    }
+
+    /// The money of all the accounts. Every account is locked (in id order) before reading,
+    /// so the sum is consistent even while other threads are making transfers.
+    pub fn total(&self) -> Result<Money, BankError> {
+        let guards = self.lock_all();
+        Ok(Money::checked_sum(self.currency, guards.iter().map(|balance| **balance))?)
+    }
+
+    /// Checks that replaying the ledger gives the live balances. The accounts are locked while the
+    /// balances and the events are read, so it also works while other threads are using the bank.
+    pub fn verify_ledger(&self) -> Result<(), LedgerError> {
+        let (live, records) = {
+            let guards = self.lock_all();
+            let live: Vec<Money> = guards.iter().map(|balance| **balance).collect();
+            (live, self.ledger.records())
+        };
+        let replayed = Ledger::from_records(records).replay(self.currency)?;
+        for account in 0..live.len().max(replayed.len()) {
+            let (live, replayed) = (live.get(account).copied(), replayed.get(&account).copied());
+            if live != replayed {
+                return Err(LedgerError::Diverged { account, live, replayed });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_currency(&self, amount: Money) -> Result<(), BankError> {
+        if amount.currency() == self.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch { expected: self.currency, found: amount.currency() }.into())
+        }
+    }
+
+    // Amounts must be positive, in the currency of the bank
+    fn check_amount(&self, amount: Money) -> Result<(), BankError> {
+        self.check_currency(amount)?;
+        if amount.is_positive() {
+            Ok(())
+        } else {
+            Err(BankError::InvalidAmount(amount))
+        }
+    }
+}
+
+// The poisoning tests need a panic in the middle of an update, while the locks of the accounts are
+// held: they take the locks themselves (lock_pair is private), so the bank has no code only for them.
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn eur(amount: &str) -> Money {
+        format!("{} EUR", amount).parse().unwrap()
+    }
+
+    // A transfer that panics halfway, after taking the money from `from` and before giving it to `to`,
+    // like a bug in the middle of an update: it poisons the locks of both accounts.
+    // Returns without panicking if the transfer would be refused.
+    fn transfer_then_panic(bank: &Bank, from: AccountId, to: AccountId, amount: Money) {
+        if from == to {
+            return;
+        }
+        let (mut source, _target) = bank.lock_pair(from, to).unwrap();
+        if *source < amount {
+            return;
+        }
+        *source = source.checked_sub(amount).unwrap();
+        panic!("transfer from account {} to account {} interrupted", from, to);
+    }
+
+    #[test]
+    fn interrupted_transfer_is_rolled_back() {
+        let bank = Arc::new(Bank::with_accounts(2, eur("100")).unwrap());
+
+        // the transfer panics after taking 30 EUR from account 0, before giving them to account 1
+        let result = {
+            let bank = Arc::clone(&bank);
+            thread::spawn(move || transfer_then_panic(&bank, 0, 1, eur("30"))).join()
+        };
+        assert!(result.is_err(), "the thread should have panicked");
+        assert!(bank.accounts[0].balance.is_poisoned());
+
+        // with lock().unwrap() these calls would panic; the bank rolls the balance back from the ledger instead
+        assert_eq!(bank.balance(0), Ok(eur("100")));
+        assert_eq!(bank.balance(1), Ok(eur("100")));
+        assert_eq!(bank.poison_recoveries(), 2);
+        assert_eq!(bank.total(), Ok(eur("200")));
+        bank.verify_ledger().unwrap();
+
+        // the poison is cleared: the accounts work normally, without more recoveries
+        assert!(!bank.accounts[0].balance.is_poisoned());
+        assert_eq!(bank.transfer(0, 1, eur("30")), Ok(()));
+        assert_eq!(bank.balance(0), Ok(eur("70")));
+        assert_eq!(bank.poison_recoveries(), 2);
+    }
+
+    // Threads make transfers, and some of them panic in the middle. The other threads keep working,
+    // and no money is created or destroyed.
+    #[test]
+    fn panicking_threads_do_not_break_the_others() {
+        const ACCOUNTS: usize = 5;
+        let bank = Arc::new(Bank::with_accounts(ACCOUNTS, eur("1000")).unwrap());
+
+        let handles: Vec<_> = (0..40u64)
+            .map(|seed| {
+                let bank = Arc::clone(&bank);
+                thread::spawn(move || {
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    for i in 0..200 {
+                        let (from, to) = (rng.gen_range(0..ACCOUNTS), rng.gen_range(0..ACCOUNTS));
+                        let amount = Money::from_cents(rng.gen_range(1..=10_000), Currency::EUR);
+                        // every 4th thread panics in the middle of its 100th transfer
+                        if seed % 4 == 0 && i == 100 {
+                            transfer_then_panic(&bank, from, to, amount);
+                            continue;
+                        }
+                        match bank.transfer(from, to, amount) {
+                            Ok(()) | Err(BankError::InsufficientFunds { .. } | BankError::SameAccount(_)) => {}
+                            Err(error) => panic!("unexpected error: {}", error),
+                        }
+                    }
+                })
+            })
+            .collect();
+        let panicked = handles.into_iter().map(|handle| handle.join()).filter(Result::is_err).count();
+
+        // a panicking thread may have been refused first (same account, not enough money): at least one panicked
+        assert!(panicked > 0);
+        assert!(bank.poison_recoveries() > 0);
+        assert_eq!(bank.total(), Ok(eur("5000")));
+        bank.verify_ledger().unwrap();
+    }
+}
@@ -0,0 +1,218 @@
+// An append-only ledger of everything that happened in the bank (event sourcing).
+//
+// The balances of the accounts are a consequence of the operations made on them. The ledger records
+// each operation as an immutable event, in the order it happened:
+//
+//      #1 account_opened   account 0, 100.00 EUR
+//      #2 deposited        account 0,  10.00 EUR
+//      #3 withdrawal_rejected account 0, 500.00 EUR (balance 110.00 EUR)
+//      #4 transferred      0 -> 1,     20.00 EUR
+//
+// Events are never modified or removed. Replaying them from the start rebuilds the balances,
+// which gives an audit trail, and a way to check the live state: replayed == live.
+//
+// The ledger is saved as JSON lines, one event per line, so it can be reloaded on start-up.
+//
+// NOTE: Order. The bank appends an event while it still holds the locks of the accounts involved
+// (accounts first, then the ledger, always in that order). So for every account, the events are in the
+// ledger in the same order as the operations were applied, and replaying them gives the same balances.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bank::AccountId;
+use crate::money::{Currency, Money, MoneyError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    AccountOpened { account: AccountId, initial_balance: Money },
+    Deposited { account: AccountId, amount: Money },
+    Withdrawn { account: AccountId, amount: Money },
+    /// A withdrawal refused because the balance was too low. It changes no balance,
+    /// but it is part of the history of the account.
+    WithdrawalRejected { account: AccountId, amount: Money, balance: Money },
+    Transferred { from: AccountId, to: AccountId, amount: Money },
+    TransferRejected { from: AccountId, to: AccountId, amount: Money, balance: Money },
+}
+
+/// An event and its position in the ledger (starting at 1).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Io(io::Error),
+    /// A line of the file is not a valid event (line numbers start at 1).
+    Parse { line: usize, message: String },
+    /// The events cannot be replayed: unknown account, wrong currency, overflow, ...
+    Replay { seq: u64, message: String },
+    /// Replaying the ledger does not give the live balance of an account.
+    Diverged { account: AccountId, live: Option<Money>, replayed: Option<Money> },
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Io(error) => write!(f, "ledger file: {}", error),
+            LedgerError::Parse { line, message } => write!(f, "ledger line {}: {}", line, message),
+            LedgerError::Replay { seq, message } => write!(f, "cannot replay event #{}: {}", seq, message),
+            LedgerError::Diverged { account, live, replayed } => {
+                write!(f, "account {}: live balance {:?}, replayed balance {:?}", account, live, replayed)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+impl From<io::Error> for LedgerError {
+    fn from(error: io::Error) -> Self {
+        LedgerError::Io(error)
+    }
+}
+
+/// The events of a bank, in order. Appending only needs `&self`, so the ledger can be shared between threads.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    records: Mutex<Vec<Record>>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Ledger::default()
+    }
+
+    /// A ledger made of existing records.
+    pub fn from_records(records: Vec<Record>) -> Self {
+        Ledger { records: Mutex::new(records) }
+    }
+
+    // A panic while the records are locked cannot leave them half updated (a push either happens or not,
+    // and nothing else modifies them), so a poisoned lock is simply cleared.
+    fn lock(&self) -> MutexGuard<'_, Vec<Record>> {
+        self.records.lock().unwrap_or_else(|poisoned| {
+            self.records.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+
+    /// Appends an event, returns its sequence number.
+    pub fn append(&self, event: Event) -> u64 {
+        let mut records = self.lock();
+        let seq = records.len() as u64 + 1;
+        records.push(Record { seq, event });
+        seq
+    }
+
+    /// A copy of all the records, in order.
+    pub fn records(&self) -> Vec<Record> {
+        self.lock().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Rebuilds the balance of every account from the events.
+    pub fn replay(&self, currency: Currency) -> Result<BTreeMap<AccountId, Money>, LedgerError> {
+        replay(&self.records(), currency)
+    }
+
+    /// Writes the ledger to a file, one JSON event per line (the file is replaced).
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), LedgerError> {
+        let mut file = BufWriter::new(fs::File::create(path)?);
+        for record in self.lock().iter() {
+            serde_json::to_writer(&mut file, record).map_err(io::Error::from)?;
+            file.write_all(b"\n")?;
+        }
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Reads a ledger written by `save()`. Empty lines are ignored, and the sequence numbers
+    /// must follow each other (a missing line means a corrupted file).
+    pub fn load(path: impl AsRef<Path>) -> Result<Ledger, LedgerError> {
+        let file = BufReader::new(fs::File::open(path)?);
+        let mut records = Vec::new();
+        for (index, line) in file.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parse_error = |message: String| LedgerError::Parse { line: index + 1, message };
+            let record: Record = serde_json::from_str(&line).map_err(|error| parse_error(error.to_string()))?;
+            let expected = records.len() as u64 + 1;
+            if record.seq != expected {
+                return Err(parse_error(format!("expected event #{}, found #{}", expected, record.seq)));
+            }
+            records.push(record);
+        }
+        Ok(Ledger::from_records(records))
+    }
+}
+
+// Applies the events in order. The events are facts: they are not validated again (a withdrawal was
+// accepted when it happened), except that they must refer to existing accounts and keep valid amounts.
+fn replay(records: &[Record], currency: Currency) -> Result<BTreeMap<AccountId, Money>, LedgerError> {
+    let mut balances: BTreeMap<AccountId, Money> = BTreeMap::new();
+    for record in records {
+        let error = |message: String| LedgerError::Replay { seq: record.seq, message };
+        let money_error = |e: MoneyError| error(e.to_string());
+        let balance_of = |account: AccountId, balances: &BTreeMap<AccountId, Money>| {
+            balances.get(&account).copied().ok_or_else(|| error(format!("unknown account {}", account)))
+        };
+
+        match &record.event {
+            Event::AccountOpened { account, initial_balance } => {
+                // the bank opens its accounts with the ids 0, 1, 2, ...
+                if *account != balances.len() {
+                    return Err(error(format!("account {} opened, expected account {}", account, balances.len())));
+                }
+                if initial_balance.currency() != currency {
+                    return Err(money_error(MoneyError::CurrencyMismatch {
+                        expected: currency,
+                        found: initial_balance.currency(),
+                    }));
+                }
+                balances.insert(*account, *initial_balance);
+            }
+            Event::Deposited { account, amount } => {
+                let balance = balance_of(*account, &balances)?.checked_add(*amount).map_err(money_error)?;
+                balances.insert(*account, balance);
+            }
+            Event::Withdrawn { account, amount } => {
+                let balance = balance_of(*account, &balances)?.checked_sub(*amount).map_err(money_error)?;
+                balances.insert(*account, balance);
+            }
+            Event::Transferred { from, to, amount } => {
+                let source = balance_of(*from, &balances)?.checked_sub(*amount).map_err(money_error)?;
+                let target = balance_of(*to, &balances)?.checked_add(*amount).map_err(money_error)?;
+                balances.insert(*from, source);
+                balances.insert(*to, target);
+            }
+            Event::WithdrawalRejected { account, .. } => {
+                balance_of(*account, &balances)?;
+            }
+            Event::TransferRejected { from, to, .. } => {
+                balance_of(*from, &balances)?;
+                balance_of(*to, &balances)?;
+            }
+        }
+    }
+    Ok(balances)
+}
@@ -0,0 +1,7 @@
+// The bank used by main.rs, in a library so the tests in tests/ can use it too.
+
+pub mod actor;
+pub mod bank;
+pub mod ledger;
+pub mod money;
+pub mod workload;
@@ -1,9 +1,35 @@
-use std::{sync::{Arc, Mutex}, thread::{self, JoinHandle}};
+use std::sync::{Arc, Mutex, MutexGuard};
 
+use bank_account::actor::BankActor;
+use bank_account::bank::Bank;
+use bank_account::ledger::{Ledger, LedgerError};
+use bank_account::money::{Currency, Money};
+use bank_account::workload::{self, Report};
+use thread_pool::{JobHandle, ThreadPool};
 // We are using 'rand' crate to generate random values to make deposits and withdraws
-//use rand::Rng;
+use rand::Rng;
 
 
+// lock().unwrap() would panic in every task once one task panicked while holding the lock (the Mutex
+// is then "poisoned"). Instead we take the guard out of the PoisonError, check the balance the task
+// left behind, and clear the poison so the next lock() returns Ok again.
+// A balance is never negative here: a negative one is reset to zero. (src/bank.rs does better: it
+// rebuilds the balance from its ledger.)
+fn lock_balance(balance: &Mutex<Money>) -> MutexGuard<'_, Money> {
+    match balance.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            let mut guard = poisoned.into_inner();
+            if guard.is_negative() {
+                eprintln!("invalid balance {} left by a panicking task, reset to zero", *guard);
+                *guard = Money::from_cents(0, guard.currency());
+            }
+            balance.clear_poison();
+            guard
+        }
+    }
+}
+
 fn main() {
 
     // Shared bank account
@@ -11,40 +37,47 @@ fn main() {
     // Mutex: Mutual exclusion for safe mutable access. 
     //        The Mutex ensures that the balance is only modified by one thread at a time, 
     //        preventing race conditions.
-    let account_balance:Arc<Mutex<f64>> = Arc::new(Mutex::new(100.0));
+    // Money: an exact amount in cents (see src/money.rs), an f64 would accumulate rounding errors.
+    let account_balance:Arc<Mutex<Money>> = Arc::new(Mutex::new("100.00 EUR".parse().unwrap()));
 
-    // JoinHandles
+    // The deposits and withdrawals are tasks run by a pool of worker threads (see ../../thread_pool),
+    // instead of one new thread each. execute() returns a JobHandle, joined like a JoinHandle.
+    let pool = ThreadPool::with_name("teller", 3);
+
+    // JobHandles
     let mut handles = vec![];
 
-    // Spawn 3 threads that deposit money and 3 threads that withdraw money.
-    // deposit threads 
+    // Submit 3 tasks that deposit money and 3 tasks that withdraw money.
+    // deposit tasks 
     for d in 1..=3 {
         let account_balance_shared_ref = Arc::clone(&account_balance);
-        //let value:f64 = rand::thread_rng().gen_range(0.0..100.0);
-        let value:f64 = 10.0;
+        let value = Money::from_cents(1000, Currency::EUR);
 
-        let handle:JoinHandle<()> = thread::spawn(move || {
-            let mut balance = account_balance_shared_ref.lock().unwrap();
-            println!("Thread-D{} , CURRENT: {:.2} , DEPOSIT: {:.2} ", d, balance, value);
-            *balance += value; 
+        let handle:JobHandle<()> = pool.execute(move || {
+            let mut balance = lock_balance(&account_balance_shared_ref);
+            println!("Task-D{} , CURRENT: {} , DEPOSIT: {} ", d, balance, value);
+            match balance.checked_add(value) {
+                Ok(new_balance) => *balance = new_balance,
+                Err(error) => eprintln!("Task-D{}, deposit refused: {} ", d, error),
+            }
             // The lock is automatically released when the MutexGuard goes out of scope
         });
         handles.push(handle);
     }
 
-    // withdraw threads
+    // withdraw tasks
     for w in 1..=3 {
         let account_balance_shared_ref = Arc::clone(&account_balance);
-        //let value:f64 = rand::thread_rng().gen_range(0.0..100.0);
-        let value:f64 = f64::from(w) * 10.0;
+        let value = Money::from_cents(i64::from(w) * 1000, Currency::EUR);
 
-        let handle = thread::spawn(move || {
-                let mut balance = account_balance_shared_ref.lock().unwrap();
-                println!("Thread-W{} , CURRENT: {:.2} , WITHDRAW: {:.2} ", w, balance, value);
+        let handle = pool.execute(move || {
+                let mut balance = lock_balance(&account_balance_shared_ref);
+                println!("Task-W{} , CURRENT: {} , WITHDRAW: {} ", w, balance, value);
                 if *balance >= value {
-                    *balance -= value;
+                    // cannot fail: same currency, and the result is between 0 and the balance
+                    *balance = balance.checked_sub(value).unwrap();
                 }else{
-                    eprintln!("Thread-W{}, Not enough money to withdraw {:.2} ", w, value);
+                    eprintln!("Task-W{}, Not enough money to withdraw {} ", w, value);
                 }
             // The lock is automatically released when the MutexGuard goes out of scope
         });
@@ -57,6 +90,117 @@ fn main() {
         handle.join().unwrap();
     }
     
-    println!("Final Balance : {:.2} ", account_balance.lock().unwrap());
+    println!("Final Balance : {} ", lock_balance(&account_balance));
+
+    many_accounts(&pool);
+
+    actor_versus_mutex();
+}
+
+// The same idea with many accounts (see src/bank.rs): each account has its own Mutex,
+// and the tasks of the pool make deposits, withdrawals and transfers on random accounts at the same time.
+// Every operation is recorded in the ledger of the bank (see src/ledger.rs), which is saved to
+// 'bank_ledger.jsonl' (or the file given as first argument) and reloaded by the next run.
+fn many_accounts(pool: &ThreadPool) {
+    let path = std::env::args().nth(1).unwrap_or_else(|| String::from("bank_ledger.jsonl"));
+    let bank = match Ledger::load(&path) {
+        Ok(ledger) => {
+            println!("\nReplaying {} events from '{}'", ledger.len(), path);
+            Bank::from_ledger(Currency::EUR, ledger).unwrap_or_else(|error| {
+                eprintln!("Cannot rebuild the bank from '{}': {}", path, error);
+                std::process::exit(1);
+            })
+        }
+        Err(LedgerError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            println!("\nNo ledger in '{}', opening 5 new accounts", path);
+            Bank::with_accounts(5, Money::from_cents(10_000, Currency::EUR)).unwrap()
+        }
+        Err(error) => {
+            eprintln!("Cannot load '{}': {}", path, error);
+            std::process::exit(1);
+        }
+    };
+    let bank = Arc::new(bank);
+    let accounts = bank.accounts().len();
+    println!("Bank with {} accounts, total: {}", accounts, bank.total().unwrap());
+
+    // 8 tasks for the 3 workers of the pool
+    let handles: Vec<JobHandle<usize>> = (1..=8)
+        .map(|t| {
+            let bank = Arc::clone(&bank);
+            pool.execute(move || {
+                let mut rng = rand::thread_rng();
+                let mut rejected = 0;
+                for _ in 0..100 {
+                    let account = rng.gen_range(0..accounts);
+                    let other = rng.gen_range(0..accounts);
+                    // between 0.01 and 50.00 EUR
+                    let amount = Money::from_cents(rng.gen_range(1..=5_000), Currency::EUR);
+                    let result = match rng.gen_range(0..4) {
+                        0 => bank.deposit(account, amount).map(|_| ()),
+                        1 => bank.withdraw(account, amount).map(|_| ()),
+                        // a transfer to the same account or without enough money is refused
+                        _ => bank.transfer(account, other, amount),
+                    };
+                    if let Err(error) = result {
+                        if rejected == 0 {
+                            eprintln!("Task-T{}, first refused operation: {}", t, error);
+                        }
+                        rejected += 1;
+                    }
+                }
+                rejected
+            })
+        })
+        .collect();
+
+    let rejected: usize = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+    for account in bank.accounts() {
+        println!("Account {} : {}", account.id(), bank.balance(account.id()).unwrap());
+    }
+    println!("Refused operations: {}, total: {}", rejected, bank.total().unwrap());
+
+    // the balances rebuilt from the events must be the live ones
+    match bank.verify_ledger() {
+        Ok(()) => println!("Ledger: {} events, replayed balances match the live ones", bank.ledger().len()),
+        Err(error) => eprintln!("Ledger does not match the accounts: {}", error),
+    }
+    match bank.ledger().save(&path) {
+        Ok(()) => println!("Ledger saved to '{}'", path),
+        Err(error) => eprintln!("Cannot save the ledger to '{}': {}", path, error),
+    }
+}
+
+// The same random workload against the Mutex bank and the actor bank (see src/actor.rs and src/workload.rs).
+// NOTE: the Mutex bank also records every operation in its ledger, the actor does not.
+fn actor_versus_mutex() {
+    const ACCOUNTS: usize = 5;
+    let initial = Money::from_cents(10_000, Currency::EUR);
+    let operations = workload::generate(42, 8, 10_000, ACCOUNTS, Currency::EUR);
+    println!("\nActor vs Mutex: 8 clients, 10000 operations each, on {} accounts", ACCOUNTS);
+
+    let bank = Arc::new(Bank::with_accounts(ACCOUNTS, initial).unwrap());
+    let report = workload::run(&bank, &operations, Currency::EUR);
+    let balances: Vec<Money> = (0..ACCOUNTS).map(|account| bank.balance(account).unwrap()).collect();
+    print_report("Mutex", &report, &balances);
+
+    let (handle, actor) = BankActor::new(ACCOUNTS, initial).unwrap().spawn();
+    let report = workload::run(&handle, &operations, Currency::EUR);
+    // dropping the last handle closes the channel: the actor stops and returns its balances
+    drop(handle);
+    let balances = actor.join().unwrap();
+    print_report("Actor", &report, &balances);
+}
 
+fn print_report(name: &str, report: &Report, balances: &[Money]) {
+    let total = Money::checked_sum(Currency::EUR, balances.iter().copied()).unwrap();
+    println!(
+        "{:>5}: {:>10.0} operations/s, {} refused, deposited {}, withdrawn {}, final total {}",
+        name,
+        report.operations_per_second(),
+        report.rejected,
+        report.deposited,
+        report.withdrawn,
+        total
+    );
 }
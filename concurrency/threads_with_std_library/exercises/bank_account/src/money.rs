@@ -0,0 +1,224 @@
+// Money as an integer number of cents, with its currency.
+//
+// f64 cannot represent 0.10 exactly (it is stored as 0.1000000000000000055...), so adding
+// many small deposits accumulates rounding errors, and printing with {:.2} only hides them:
+//
+//      (0..1000).map(|_| 0.10).sum::<f64>()  == 99.9999999999986
+//
+// Money stores the amount in minor units (cents) in an i64: 12.34 EUR is Money { cents: 1234, EUR }.
+// Integer additions are exact. They can overflow, so the arithmetic is checked and returns an error
+// instead of wrapping around. Adding euros to dollars is also an error.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An ISO 4217 currency code, three uppercase ASCII letters ("EUR", "USD", ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub const EUR: Currency = Currency(*b"EUR");
+    pub const USD: Currency = Currency(*b"USD");
+
+    pub fn new(code: &str) -> Result<Currency, ParseMoneyError> {
+        match code.as_bytes() {
+            &[a, b, c] if code.bytes().all(|byte| byte.is_ascii_uppercase()) => Ok(Currency([a, b, c])),
+            _ => Err(ParseMoneyError::InvalidCurrency(code.to_string())),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        // only built from ASCII letters
+        std::str::from_utf8(&self.0).unwrap()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// An exact amount of money: a number of cents and a currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    cents: i64,
+    currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The result does not fit in an i64 number of cents.
+    Overflow,
+    /// Both amounts must have the same currency.
+    CurrencyMismatch { expected: Currency, found: Currency },
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::Overflow => write!(f, "amount out of range"),
+            MoneyError::CurrencyMismatch { expected, found } => {
+                write!(f, "currency mismatch: expected {}, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+impl Money {
+    pub fn from_cents(cents: i64, currency: Currency) -> Money {
+        Money { cents, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Money {
+        Money::from_cents(0, currency)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.cents
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.cents > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.cents < 0
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch { expected: self.currency, found: other.currency })
+        }
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money, MoneyError> {
+        self.same_currency(&other)?;
+        let cents = self.cents.checked_add(other.cents).ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_cents(cents, self.currency))
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money, MoneyError> {
+        self.same_currency(&other)?;
+        let cents = self.cents.checked_sub(other.cents).ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_cents(cents, self.currency))
+    }
+
+    /// Multiplies by a whole number, e.g. the price of `count` items.
+    pub fn checked_mul(self, count: i64) -> Result<Money, MoneyError> {
+        let cents = self.cents.checked_mul(count).ok_or(MoneyError::Overflow)?;
+        Ok(Money::from_cents(cents, self.currency))
+    }
+
+    /// Sums amounts of the given currency.
+    pub fn checked_sum<I: IntoIterator<Item = Money>>(currency: Currency, amounts: I) -> Result<Money, MoneyError> {
+        amounts.into_iter().try_fold(Money::zero(currency), Money::checked_add)
+    }
+}
+
+/// Amounts of different currencies cannot be compared (`partial_cmp` returns `None`).
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        if self.currency == other.currency {
+            Some(self.cents.cmp(&other.cents))
+        } else {
+            None
+        }
+    }
+}
+
+/// Formats as "12.34 EUR" (always two decimals).
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // unsigned_abs: -i64::MIN does not fit in an i64
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let cents = self.cents.unsigned_abs();
+        write!(f, "{}{}.{:02} {}", sign, cents / 100, cents % 100, self.currency)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMoneyError {
+    /// The input is not "<amount> <currency>".
+    InvalidFormat(String),
+    /// The amount is not a number with at most two decimals.
+    InvalidAmount(String),
+    InvalidCurrency(String),
+    /// The amount does not fit in an i64 number of cents.
+    Overflow(String),
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoneyError::InvalidFormat(input) => write!(f, "expected '<amount> <currency>', found '{}'", input),
+            ParseMoneyError::InvalidAmount(amount) => {
+                write!(f, "invalid amount '{}' (expected digits with at most two decimals)", amount)
+            }
+            ParseMoneyError::InvalidCurrency(code) => write!(f, "invalid currency code '{}'", code),
+            ParseMoneyError::Overflow(amount) => write!(f, "amount '{}' out of range", amount),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+/// Parses "12.34 EUR", "-0.5 USD", "100 EUR".
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(input: &str) -> Result<Money, ParseMoneyError> {
+        let mut parts = input.split_whitespace();
+        let (amount, code) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(amount), Some(code), None) => (amount, code),
+            _ => return Err(ParseMoneyError::InvalidFormat(input.to_string())),
+        };
+        let currency = Currency::new(code)?;
+        let invalid = || ParseMoneyError::InvalidAmount(amount.to_string());
+        let overflow = || ParseMoneyError::Overflow(amount.to_string());
+
+        let (negative, digits) = match amount.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, amount),
+        };
+        let (units, decimals) = digits.split_once('.').unwrap_or((digits, ""));
+        let all_digits = |s: &str| s.bytes().all(|byte| byte.is_ascii_digit());
+        if units.is_empty() || !all_digits(units) || !all_digits(decimals) || decimals.len() > 2 {
+            return Err(invalid());
+        }
+        if digits.ends_with('.') {
+            return Err(invalid());
+        }
+
+        // "12.3" is 12 units and 30 cents
+        let units: i64 = units.parse().map_err(|_| overflow())?;
+        let decimals: i64 = format!("{:0<2}", decimals).parse().map_err(|_| invalid())?;
+        let cents = units.checked_mul(100).and_then(|cents| cents.checked_add(decimals)).ok_or_else(overflow)?;
+        Ok(Money::from_cents(if negative { -cents } else { cents }, currency))
+    }
+}
+
+/// Serialized as its display string ("12.34 EUR"), which reads well in the ledger file.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
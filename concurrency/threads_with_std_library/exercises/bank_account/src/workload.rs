@@ -0,0 +1,147 @@
+// The same random workload, run against the Mutex bank (bank.rs) and the actor bank (actor.rs).
+//
+// A workload is a list of operations for every client thread, generated from a seed: both banks
+// receive exactly the same requests, so their throughput and their final balances can be compared.
+//
+// With several clients, the order in which the requests of different threads reach the bank is not
+// fixed (it depends on the scheduler), so a withdrawal may be accepted in one run and refused in the
+// other, and the final balances can differ. What must hold for both banks, whatever the order:
+//
+//      final total == initial total + accepted deposits - accepted withdrawals
+//
+// With a single client, the order is fixed and both banks must end with the same balances.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::actor::BankHandle;
+use crate::bank::{AccountId, Bank, BankError};
+use crate::money::{Currency, Money};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Deposit(AccountId, Money),
+    Withdraw(AccountId, Money),
+    Balance(AccountId),
+}
+
+/// The operations both banks can run, so the workload does not care which one it talks to.
+pub trait Teller {
+    fn deposit(&self, account: AccountId, amount: Money) -> Result<Money, BankError>;
+    fn withdraw(&self, account: AccountId, amount: Money) -> Result<Money, BankError>;
+    fn balance(&self, account: AccountId) -> Result<Money, BankError>;
+}
+
+// Every client thread gets its own clone: a clone of the Arc for the Mutex bank...
+impl Teller for Arc<Bank> {
+    fn deposit(&self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        Bank::deposit(self, account, amount)
+    }
+
+    fn withdraw(&self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        Bank::withdraw(self, account, amount)
+    }
+
+    fn balance(&self, account: AccountId) -> Result<Money, BankError> {
+        Bank::balance(self, account)
+    }
+}
+
+// ...and a clone of the Sender for the actor.
+impl Teller for BankHandle {
+    fn deposit(&self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        BankHandle::deposit(self, account, amount)
+    }
+
+    fn withdraw(&self, account: AccountId, amount: Money) -> Result<Money, BankError> {
+        BankHandle::withdraw(self, account, amount)
+    }
+
+    fn balance(&self, account: AccountId) -> Result<Money, BankError> {
+        BankHandle::balance(self, account)
+    }
+}
+
+/// `clients` lists of `operations` random operations on the accounts `0..accounts`:
+/// 40% deposits, 40% withdrawals and 20% balance queries, of 0.01 to 50.00.
+pub fn generate(seed: u64, clients: usize, operations: usize, accounts: usize, currency: Currency) -> Vec<Vec<Operation>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..clients)
+        .map(|_| {
+            (0..operations)
+                .map(|_| {
+                    let account = rng.gen_range(0..accounts);
+                    let amount = Money::from_cents(rng.gen_range(1..=5_000), currency);
+                    match rng.gen_range(0..10) {
+                        0..=3 => Operation::Deposit(account, amount),
+                        4..=7 => Operation::Withdraw(account, amount),
+                        _ => Operation::Balance(account),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// What happened during a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub operations: usize,
+    /// Refused operations (withdrawals without enough money).
+    pub rejected: usize,
+    pub deposited: Money,
+    pub withdrawn: Money,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn operations_per_second(&self) -> f64 {
+        self.operations as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Runs every list of operations in its own thread, with its own clone of the teller.
+pub fn run<T: Teller + Clone + Send + 'static>(teller: &T, workload: &[Vec<Operation>], currency: Currency) -> Report {
+    let start = Instant::now();
+    let handles: Vec<_> = workload
+        .iter()
+        .map(|operations| {
+            let (teller, operations) = (teller.clone(), operations.clone());
+            thread::spawn(move || run_client(&teller, &operations, currency))
+        })
+        .collect();
+
+    let mut report =
+        Report { operations: 0, rejected: 0, deposited: Money::zero(currency), withdrawn: Money::zero(currency), elapsed: Duration::ZERO };
+    for handle in handles {
+        let client = handle.join().unwrap();
+        report.operations += client.operations;
+        report.rejected += client.rejected;
+        report.deposited = report.deposited.checked_add(client.deposited).unwrap();
+        report.withdrawn = report.withdrawn.checked_add(client.withdrawn).unwrap();
+    }
+    report.elapsed = start.elapsed();
+    report
+}
+
+fn run_client<T: Teller>(teller: &T, operations: &[Operation], currency: Currency) -> Report {
+    let start = Instant::now();
+    let (mut deposited, mut withdrawn, mut rejected) = (Money::zero(currency), Money::zero(currency), 0);
+    for operation in operations {
+        let result = match *operation {
+            Operation::Deposit(account, amount) => teller.deposit(account, amount).map(|_| deposited = deposited.checked_add(amount).unwrap()),
+            Operation::Withdraw(account, amount) => teller.withdraw(account, amount).map(|_| withdrawn = withdrawn.checked_add(amount).unwrap()),
+            Operation::Balance(account) => teller.balance(account).map(|_| ()),
+        };
+        match result {
+            Ok(()) => {}
+            Err(BankError::InsufficientFunds { .. }) => rejected += 1,
+            Err(error) => panic!("unexpected error: {}", error),
+        }
+    }
+    Report { operations: operations.len(), rejected, deposited, withdrawn, elapsed: start.elapsed() }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use std::thread;
+
+use bank_account::actor::BankActor;
+use bank_account::bank::{Bank, BankError};
+use bank_account::money::{Currency, Money, MoneyError};
+use bank_account::workload::{self, Operation, Report};
+
+fn eur(amount: &str) -> Money {
+    format!("{} EUR", amount).parse().unwrap()
+}
+
+fn total(balances: &[Money]) -> Money {
+    Money::checked_sum(Currency::EUR, balances.iter().copied()).unwrap()
+}
+
+// initial + deposited - withdrawn
+fn expected_total(initial: Money, report: &Report) -> Money {
+    initial.checked_add(report.deposited).unwrap().checked_sub(report.withdrawn).unwrap()
+}
+
+#[test]
+fn actor_handles_commands() {
+    let (bank, actor) = BankActor::new(2, eur("100")).unwrap().spawn();
+    assert_eq!(bank.deposit(0, eur("50.10")), Ok(eur("150.10")));
+    assert_eq!(bank.withdraw(0, eur("30.10")), Ok(eur("120")));
+    assert_eq!(
+        bank.withdraw(1, eur("100.01")),
+        Err(BankError::InsufficientFunds { account: 1, balance: eur("100"), requested: eur("100.01") })
+    );
+    assert_eq!(bank.balance(1), Ok(eur("100")));
+    assert_eq!(bank.balance(2), Err(BankError::UnknownAccount(2)));
+    assert_eq!(bank.deposit(0, eur("0")), Err(BankError::InvalidAmount(eur("0"))));
+    assert_eq!(
+        bank.deposit(0, "1 USD".parse().unwrap()),
+        Err(BankError::Money(MoneyError::CurrencyMismatch { expected: Currency::EUR, found: Currency::USD }))
+    );
+
+    // the actor keeps running while a clone of the handle exists
+    let client = bank.clone();
+    drop(bank);
+    assert_eq!(client.deposit(1, eur("1")), Ok(eur("101")));
+    drop(client);
+    assert_eq!(actor.join().unwrap(), vec![eur("120"), eur("101")]);
+}
+
+#[test]
+fn concurrent_clients_are_all_served() {
+    let (bank, actor) = BankActor::new(4, eur("0")).unwrap().spawn();
+    let handles: Vec<_> = (0..100)
+        .map(|t| {
+            let bank = bank.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    bank.deposit((t + i) % 4, eur("0.10")).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(bank);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(actor.join().unwrap(), vec![eur("250"); 4]);
+}
+
+// One client: the requests arrive in the same order, both banks must give the same results.
+#[test]
+fn same_workload_same_balances_with_one_client() {
+    let operations = workload::generate(7, 1, 5_000, 3, Currency::EUR);
+
+    let bank = Arc::new(Bank::with_accounts(3, eur("100")).unwrap());
+    let mutex_report = workload::run(&bank, &operations, Currency::EUR);
+    let mutex_balances: Vec<Money> = (0..3).map(|account| bank.balance(account).unwrap()).collect();
+
+    let (handle, actor) = BankActor::new(3, eur("100")).unwrap().spawn();
+    let actor_report = workload::run(&handle, &operations, Currency::EUR);
+    drop(handle);
+    let actor_balances = actor.join().unwrap();
+
+    assert_eq!(mutex_balances, actor_balances);
+    assert_eq!(
+        (mutex_report.operations, mutex_report.rejected, mutex_report.deposited, mutex_report.withdrawn),
+        (actor_report.operations, actor_report.rejected, actor_report.deposited, actor_report.withdrawn)
+    );
+    assert!(mutex_report.rejected > 0, "the workload should also refuse some withdrawals");
+}
+
+// Many clients: the order differs between runs, but no operation is lost in either bank.
+#[test]
+fn same_workload_conserves_money_with_many_clients() {
+    let operations = workload::generate(11, 16, 2_000, 5, Currency::EUR);
+    let requested = operations.iter().flatten().filter(|operation| !matches!(operation, Operation::Balance(_))).count();
+    assert!(requested > 0);
+
+    let bank = Arc::new(Bank::with_accounts(5, eur("100")).unwrap());
+    let report = workload::run(&bank, &operations, Currency::EUR);
+    assert_eq!(report.operations, 16 * 2_000);
+    assert_eq!(bank.total(), Ok(expected_total(eur("500"), &report)));
+
+    let (handle, actor) = BankActor::new(5, eur("100")).unwrap().spawn();
+    let report = workload::run(&handle, &operations, Currency::EUR);
+    drop(handle);
+    let balances = actor.join().unwrap();
+    assert_eq!(report.operations, 16 * 2_000);
+    assert_eq!(total(&balances), expected_total(eur("500"), &report));
+    assert!(balances.iter().all(|balance| !balance.is_negative()));
+}
@@ -0,0 +1,142 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use bank_account::bank::{Bank, BankError};
+use bank_account::money::{Currency, Money, MoneyError};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn eur(amount: &str) -> Money {
+    format!("{} EUR", amount).parse().unwrap()
+}
+
+#[test]
+fn deposit_withdraw_and_transfer() {
+    let mut bank = Bank::new(Currency::EUR);
+    let alice = bank.open_account(eur("100")).unwrap();
+    let bob = bank.open_account(eur("0")).unwrap();
+
+    assert_eq!(bank.deposit(alice, eur("50.10")), Ok(eur("150.10")));
+    assert_eq!(bank.withdraw(alice, eur("30.10")), Ok(eur("120")));
+    assert_eq!(
+        bank.withdraw(bob, eur("0.01")),
+        Err(BankError::InsufficientFunds { account: bob, balance: eur("0"), requested: eur("0.01") })
+    );
+    assert_eq!(bank.transfer(alice, bob, eur("20")), Ok(()));
+    assert_eq!(bank.balance(alice), Ok(eur("100")));
+    assert_eq!(bank.balance(bob), Ok(eur("20")));
+    assert_eq!(bank.total(), Ok(eur("120")));
+
+    assert_eq!(
+        bank.transfer(bob, alice, eur("20.01")),
+        Err(BankError::InsufficientFunds { account: bob, balance: eur("20"), requested: eur("20.01") })
+    );
+    assert_eq!(bank.transfer(bob, bob, eur("1")), Err(BankError::SameAccount(bob)));
+    assert_eq!(bank.transfer(bob, 7, eur("1")), Err(BankError::UnknownAccount(7)));
+    assert_eq!(bank.deposit(alice, eur("-5")), Err(BankError::InvalidAmount(eur("-5"))));
+    assert_eq!(bank.deposit(alice, eur("0")), Err(BankError::InvalidAmount(eur("0"))));
+    assert_eq!(
+        bank.deposit(alice, "1 USD".parse().unwrap()),
+        Err(BankError::Money(MoneyError::CurrencyMismatch { expected: Currency::EUR, found: Currency::USD }))
+    );
+    assert_eq!(bank.total(), Ok(eur("120")));
+}
+
+#[test]
+fn overflowing_balances_are_refused() {
+    let mut bank = Bank::new(Currency::EUR);
+    let rich = bank.open_account(Money::from_cents(i64::MAX, Currency::EUR)).unwrap();
+    let other = bank.open_account(eur("1")).unwrap();
+
+    assert_eq!(bank.deposit(rich, eur("0.01")), Err(BankError::Money(MoneyError::Overflow)));
+    // the transfer fails as a whole: no money leaves the source account
+    assert_eq!(bank.transfer(other, rich, eur("1")), Err(BankError::Money(MoneyError::Overflow)));
+    assert_eq!(bank.balance(other), Ok(eur("1")));
+    assert_eq!(bank.total(), Err(BankError::Money(MoneyError::Overflow)));
+}
+
+#[test]
+fn many_small_deposits_are_exact() {
+    let bank = Bank::with_accounts(1, eur("0")).unwrap();
+    for _ in 0..1000 {
+        bank.deposit(0, eur("0.10")).unwrap();
+    }
+    assert_eq!(bank.balance(0), Ok(eur("100.00")));
+}
+
+// Hundreds of threads transfer money between a few accounts (lots of contention, and transfers
+// in both directions between the same accounts) while an auditor checks the total.
+#[test]
+fn concurrent_transfers_conserve_money() {
+    const ACCOUNTS: usize = 10;
+    const THREADS: u64 = 300;
+    let bank = Arc::new(Bank::with_accounts(ACCOUNTS, eur("1000")).unwrap());
+    let expected = bank.total().unwrap();
+    let done = Arc::new(AtomicBool::new(false));
+
+    let auditor = {
+        let (bank, done) = (Arc::clone(&bank), Arc::clone(&done));
+        thread::spawn(move || {
+            while !done.load(Ordering::SeqCst) {
+                assert_eq!(bank.total(), Ok(expected));
+            }
+        })
+    };
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|seed| {
+            let bank = Arc::clone(&bank);
+            thread::spawn(move || {
+                let mut rng = StdRng::seed_from_u64(seed);
+                for _ in 0..200 {
+                    let from = rng.gen_range(0..ACCOUNTS);
+                    let to = rng.gen_range(0..ACCOUNTS);
+                    let amount = Money::from_cents(rng.gen_range(1..=10_000), Currency::EUR);
+                    match bank.transfer(from, to, amount) {
+                        Ok(()) | Err(BankError::InsufficientFunds { .. }) | Err(BankError::SameAccount(_)) => {}
+                        Err(error) => panic!("unexpected error: {}", error),
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    done.store(true, Ordering::SeqCst);
+    auditor.join().unwrap();
+
+    assert_eq!(bank.total(), Ok(expected));
+    for account in bank.accounts() {
+        assert!(!bank.balance(account.id()).unwrap().is_negative());
+    }
+}
+
+#[test]
+fn concurrent_deposits_and_withdrawals_are_all_counted() {
+    let bank = Arc::new(Bank::with_accounts(4, eur("0")).unwrap());
+    let handles: Vec<_> = (0..200u64)
+        .map(|seed| {
+            let bank = Arc::clone(&bank);
+            thread::spawn(move || {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut net = 0;
+                for _ in 0..100 {
+                    let account = rng.gen_range(0..4);
+                    let cents = rng.gen_range(1..=1_000);
+                    let amount = Money::from_cents(cents, Currency::EUR);
+                    if rng.gen_bool(0.5) {
+                        bank.deposit(account, amount).unwrap();
+                        net += cents;
+                    } else if bank.withdraw(account, amount).is_ok() {
+                        net -= cents;
+                    }
+                }
+                net
+            })
+        })
+        .collect();
+    let net: i64 = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+    assert_eq!(bank.total(), Ok(Money::from_cents(net, Currency::EUR)));
+}
@@ -0,0 +1,177 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use bank_account::bank::Bank;
+use bank_account::ledger::{Event, Ledger, LedgerError, Record};
+use bank_account::money::{Currency, Money};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn eur(amount: &str) -> Money {
+    format!("{} EUR", amount).parse().unwrap()
+}
+
+// A file in the temporary directory, removed at the end of the test.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        TempFile(env::temp_dir().join(format!("bank_account-{}-{}.jsonl", name, std::process::id())))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn operations_are_recorded_in_order() {
+    let mut bank = Bank::new(Currency::EUR);
+    let alice = bank.open_account(eur("100")).unwrap();
+    let bob = bank.open_account(eur("0")).unwrap();
+    bank.deposit(alice, eur("10")).unwrap();
+    bank.withdraw(bob, eur("5")).unwrap_err();
+    bank.transfer(alice, bob, eur("20")).unwrap();
+    bank.transfer(bob, alice, eur("50")).unwrap_err();
+    bank.withdraw(alice, eur("0.50")).unwrap();
+    // invalid requests are not operations on an account: nothing is recorded
+    bank.deposit(alice, eur("-1")).unwrap_err();
+    bank.transfer(alice, alice, eur("1")).unwrap_err();
+
+    let events: Vec<Event> = bank.ledger().records().into_iter().map(|record| record.event).collect();
+    assert_eq!(
+        events,
+        vec![
+            Event::AccountOpened { account: alice, initial_balance: eur("100") },
+            Event::AccountOpened { account: bob, initial_balance: eur("0") },
+            Event::Deposited { account: alice, amount: eur("10") },
+            Event::WithdrawalRejected { account: bob, amount: eur("5"), balance: eur("0") },
+            Event::Transferred { from: alice, to: bob, amount: eur("20") },
+            Event::TransferRejected { from: bob, to: alice, amount: eur("50"), balance: eur("20") },
+            Event::Withdrawn { account: alice, amount: eur("0.50") },
+        ]
+    );
+    let seqs: Vec<u64> = bank.ledger().records().iter().map(|record| record.seq).collect();
+    assert_eq!(seqs, (1..=7).collect::<Vec<u64>>());
+
+    let balances = bank.ledger().replay(Currency::EUR).unwrap();
+    assert_eq!(balances[&alice], eur("89.50"));
+    assert_eq!(balances[&bob], eur("20"));
+    bank.verify_ledger().unwrap();
+}
+
+#[test]
+fn replay_matches_live_balances_after_concurrent_operations() {
+    let bank = Arc::new(Bank::with_accounts(10, eur("100")).unwrap());
+
+    let handles: Vec<_> = (0..16u64)
+        .map(|t| {
+            let bank = Arc::clone(&bank);
+            thread::spawn(move || {
+                let mut rng = StdRng::seed_from_u64(t);
+                for _ in 0..500 {
+                    let (a, b) = (rng.gen_range(0..10), rng.gen_range(0..10));
+                    let amount = Money::from_cents(rng.gen_range(1..=8_000), Currency::EUR);
+                    let _ = match rng.gen_range(0..3) {
+                        0 => bank.deposit(a, amount).map(|_| ()),
+                        1 => bank.withdraw(a, amount).map(|_| ()),
+                        _ => bank.transfer(a, b, amount),
+                    };
+                    if rng.gen_range(0..100) == 0 {
+                        // also works while the other threads are running
+                        bank.verify_ledger().unwrap();
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    bank.verify_ledger().unwrap();
+    let replayed = bank.ledger().replay(Currency::EUR).unwrap();
+    for account in bank.accounts() {
+        assert_eq!(replayed[&account.id()], bank.balance(account.id()).unwrap());
+    }
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    let file = TempFile::new("round_trip");
+    let bank = Bank::with_accounts(3, eur("50")).unwrap();
+    bank.transfer(0, 2, eur("12.34")).unwrap();
+    bank.withdraw(1, eur("60")).unwrap_err();
+    bank.ledger().save(&file.0).unwrap();
+
+    let text = fs::read_to_string(&file.0).unwrap();
+    assert_eq!(text.lines().count(), 5);
+    assert_eq!(text.lines().next().unwrap(), r#"{"seq":1,"type":"account_opened","account":0,"initial_balance":"50.00 EUR"}"#);
+    assert_eq!(text.lines().nth(3).unwrap(), r#"{"seq":4,"type":"transferred","from":0,"to":2,"amount":"12.34 EUR"}"#);
+
+    let loaded = Ledger::load(&file.0).unwrap();
+    assert_eq!(loaded.records(), bank.ledger().records());
+
+    // the rebuilt bank has the same balances, and its next operations follow the loaded events
+    let rebuilt = Bank::from_ledger(Currency::EUR, loaded).unwrap();
+    assert_eq!(rebuilt.accounts().len(), 3);
+    assert_eq!(rebuilt.balance(0), Ok(eur("37.66")));
+    assert_eq!(rebuilt.balance(2), Ok(eur("62.34")));
+    rebuilt.deposit(1, eur("1")).unwrap();
+    let last: Record = rebuilt.ledger().records().pop().unwrap();
+    assert_eq!(last, Record { seq: 6, event: Event::Deposited { account: 1, amount: eur("1") } });
+    rebuilt.verify_ledger().unwrap();
+}
+
+#[test]
+fn load_reports_the_line_of_an_invalid_event() {
+    let file = TempFile::new("invalid");
+    let lines = [
+        r#"{"seq":1,"type":"account_opened","account":0,"initial_balance":"10.00 EUR"}"#,
+        "",
+        r#"{"seq":2,"type":"deposited","account":0,"amount":"1.234 EUR"}"#,
+    ];
+    fs::write(&file.0, lines.join("\n")).unwrap();
+    match Ledger::load(&file.0) {
+        Err(LedgerError::Parse { line: 3, message }) => assert!(message.contains("1.234"), "{}", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // a missing event: the sequence numbers jump from 1 to 3
+    let lines = [
+        r#"{"seq":1,"type":"account_opened","account":0,"initial_balance":"10.00 EUR"}"#,
+        r#"{"seq":3,"type":"deposited","account":0,"amount":"1.00 EUR"}"#,
+    ];
+    fs::write(&file.0, lines.join("\n")).unwrap();
+    match Ledger::load(&file.0) {
+        Err(LedgerError::Parse { line: 2, message }) => assert_eq!(message, "expected event #2, found #3"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let missing = TempFile::new("missing");
+    assert!(matches!(Ledger::load(&missing.0), Err(LedgerError::Io(_))));
+}
+
+#[test]
+fn replay_rejects_inconsistent_events() {
+    let opened = |account| Event::AccountOpened { account, initial_balance: eur("10") };
+    let records = |events: Vec<Event>| {
+        let records = events.into_iter().zip(1..).map(|(event, seq)| Record { seq, event }).collect();
+        Ledger::from_records(records)
+    };
+
+    let unknown = records(vec![opened(0), Event::Deposited { account: 1, amount: eur("1") }]);
+    assert!(matches!(unknown.replay(Currency::EUR), Err(LedgerError::Replay { seq: 2, .. })));
+
+    let gap = records(vec![opened(0), opened(2)]);
+    assert!(matches!(gap.replay(Currency::EUR), Err(LedgerError::Replay { seq: 2, .. })));
+
+    let currency = records(vec![opened(0)]);
+    assert!(matches!(currency.replay(Currency::USD), Err(LedgerError::Replay { seq: 1, .. })));
+    assert!(Bank::from_ledger(Currency::USD, currency).is_err());
+}
@@ -0,0 +1,89 @@
+use bank_account::money::{Currency, Money, MoneyError, ParseMoneyError};
+
+fn money(input: &str) -> Money {
+    input.parse().unwrap()
+}
+
+#[test]
+fn parse_and_display() {
+    assert_eq!(money("12.34 EUR"), Money::from_cents(1234, Currency::EUR));
+    assert_eq!(money("12.3 EUR").cents(), 1230);
+    assert_eq!(money("12 USD"), Money::from_cents(1200, Currency::USD));
+    assert_eq!(money("-0.05 EUR").cents(), -5);
+    assert_eq!(money("  7.00   EUR ").cents(), 700);
+
+    assert_eq!(money("12.34 EUR").to_string(), "12.34 EUR");
+    assert_eq!(money("12.3 EUR").to_string(), "12.30 EUR");
+    assert_eq!(money("-0.05 EUR").to_string(), "-0.05 EUR");
+    assert_eq!(Money::from_cents(i64::MIN, Currency::EUR).to_string(), "-92233720368547758.08 EUR");
+    // Display and FromStr round trip
+    for cents in [0, 1, -1, 99, 100, -12345, i64::MAX] {
+        let value = Money::from_cents(cents, Currency::USD);
+        assert_eq!(money(&value.to_string()), value);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let error = |input: &str| input.parse::<Money>().unwrap_err();
+    assert_eq!(error("12.34"), ParseMoneyError::InvalidFormat("12.34".into()));
+    assert_eq!(error("12.34 EUR extra"), ParseMoneyError::InvalidFormat("12.34 EUR extra".into()));
+    assert_eq!(error("12.345 EUR"), ParseMoneyError::InvalidAmount("12.345".into()));
+    assert_eq!(error("12. EUR"), ParseMoneyError::InvalidAmount("12.".into()));
+    assert_eq!(error(".5 EUR"), ParseMoneyError::InvalidAmount(".5".into()));
+    assert_eq!(error("1e3 EUR"), ParseMoneyError::InvalidAmount("1e3".into()));
+    assert_eq!(error("+1 EUR"), ParseMoneyError::InvalidAmount("+1".into()));
+    assert_eq!(error("12 eur"), ParseMoneyError::InvalidCurrency("eur".into()));
+    assert_eq!(error("12 EURO"), ParseMoneyError::InvalidCurrency("EURO".into()));
+    assert_eq!(error("92233720368547758.08 EUR"), ParseMoneyError::Overflow("92233720368547758.08".into()));
+    assert_eq!(error("99999999999999999999 EUR"), ParseMoneyError::Overflow("99999999999999999999".into()));
+    assert_eq!(error("12.34 EUR extra").to_string(), "expected '<amount> <currency>', found '12.34 EUR extra'");
+}
+
+#[test]
+fn checked_arithmetic() {
+    let a = money("10.50 EUR");
+    let b = money("0.75 EUR");
+    assert_eq!(a.checked_add(b), Ok(money("11.25 EUR")));
+    assert_eq!(b.checked_sub(a), Ok(money("-9.75 EUR")));
+    assert_eq!(b.checked_mul(3), Ok(money("2.25 EUR")));
+    assert_eq!(Money::checked_sum(Currency::EUR, vec![a, b, b]), Ok(money("12.00 EUR")));
+    assert_eq!(Money::checked_sum(Currency::EUR, vec![]), Ok(Money::zero(Currency::EUR)));
+
+    let max = Money::from_cents(i64::MAX, Currency::EUR);
+    assert_eq!(max.checked_add(money("0.01 EUR")), Err(MoneyError::Overflow));
+    assert_eq!(Money::from_cents(i64::MIN, Currency::EUR).checked_sub(money("0.01 EUR")), Err(MoneyError::Overflow));
+    assert_eq!(max.checked_mul(2), Err(MoneyError::Overflow));
+
+    let dollars = money("1 USD");
+    assert_eq!(
+        a.checked_add(dollars),
+        Err(MoneyError::CurrencyMismatch { expected: Currency::EUR, found: Currency::USD })
+    );
+    assert_eq!(a.checked_add(dollars).unwrap_err().to_string(), "currency mismatch: expected EUR, found USD");
+}
+
+#[test]
+fn comparisons_only_within_a_currency() {
+    assert!(money("1.00 EUR") < money("1.01 EUR"));
+    assert!(money("-1 EUR") < Money::zero(Currency::EUR));
+    assert_eq!(money("1 EUR").partial_cmp(&money("1 USD")), None);
+    // neither smaller nor greater
+    assert!(!money("1 EUR").lt(&money("2 USD")) && !money("1 EUR").gt(&money("2 USD")));
+    assert_ne!(money("1 EUR"), money("1 USD"));
+}
+
+#[test]
+fn currency_codes() {
+    assert_eq!(Currency::new("GBP").unwrap().code(), "GBP");
+    assert_eq!(Currency::new("Gb"), Err(ParseMoneyError::InvalidCurrency("Gb".into())));
+    assert_eq!(Currency::new("ÉUR"), Err(ParseMoneyError::InvalidCurrency("ÉUR".into())));
+}
+
+#[test]
+fn f64_drifts_where_money_does_not() {
+    let float: f64 = (0..1000).map(|_| 0.10).sum();
+    assert_ne!(float, 100.0);
+    let exact = Money::checked_sum(Currency::EUR, (0..1000).map(|_| money("0.10 EUR"))).unwrap();
+    assert_eq!(exact, money("100.00 EUR"));
+}
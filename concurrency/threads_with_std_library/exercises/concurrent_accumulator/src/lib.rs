@@ -0,0 +1,4 @@
+// The shared Vec strategies and the workload used by main.rs, in a library so the tests in tests/ can use them too.
+
+pub mod strategies;
+pub mod workload;
@@ -1,9 +1,22 @@
-use std::{sync::{Arc, RwLock}, thread};
+use std::sync::{Arc, RwLock};
+
+use concurrent_accumulator::strategies::{CowVec, MutexVec, ReaderPreferringVec, RwLockVec};
+use concurrent_accumulator::workload::{self, Config};
+use thread_pool::ThreadPool;
 // we can use the rand crate to generate random values to be added by writer threads.
 // use rand::Rng;
 
 
 fn main(){
+    // options for the comparison at the end, see Config::from_args in src/workload.rs
+    let config = Config::from_args(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        eprintln!(
+            "usage: concurrent_accumulator [--readers N] [--writers N] [--iterations N] [--read-hold-us N] \
+             [--write-hold-us N] [--write-ratio R] [--starvation-ms N] [--seed N]"
+        );
+        std::process::exit(2);
+    });
 
     // My vector of numbers (i've chosen i32 as type, could be something else)
     // let shared_vec:Vec<i32> = Vec::new();
@@ -15,15 +28,19 @@ fn main(){
     // thread-safe read-write lock allowing multiple readers or one writer, finally my vector is:
     let shared_vec: Arc<RwLock<Vec<i32>>> = Arc::new(RwLock::new(Vec::new()));
 
-    // In order to make the main thread to wait all the other threads to finish before finishing the main thread
-    // we use the JoinHandle object returned by the spawn methods. We use this vector of join handlers in order to call
+    // The readers and writers are tasks given to a pool of worker threads (see ../../thread_pool),
+    // instead of one new thread each.
+    let pool = ThreadPool::with_name("accumulator", 4);
+
+    // In order to make the main thread to wait all the tasks to finish before finishing the main thread
+    // we use the JobHandle object returned by the execute method. We use this vector of job handles in order to call
     // the join method, to make the main thread to wait. 
     let mut handles = vec![];
 
-    // Spawn 5 reader threads
+    // Submit 5 reader tasks
     for r in 1..=5  {
         let shared_vec_ref = Arc::clone(&shared_vec);
-        let handle = thread::spawn( move || {
+        let handle = pool.execute( move || {
             let current_vec = shared_vec_ref.read().unwrap();
             println!("Reader {} sees Vec: {:?}" , r, *current_vec); 
 
@@ -31,13 +48,13 @@ fn main(){
         handles.push(handle);
     }
 
-    // Spawn 2 writer threads
+    // Submit 2 writer tasks
     for w in 1..=2 {
         let shared_vec_ref = Arc::clone(&shared_vec);
         // Create a random number generator and Generate a random number between 10 and 50
         //let random_number = rand::thread_rng().gen_range(1..10);
 
-        let handle = thread::spawn(move || {
+        let handle = pool.execute(move || {
             let mut current_vec = shared_vec_ref.write().unwrap();
             println!("Writer {} sees current Vec: {:?}" , w, current_vec); 
             //current_vec.push(random_number);
@@ -48,13 +65,26 @@ fn main(){
         handles.push(handle);
     }
 
-    // Next we join all the JoinHandle objects to make the main thread to wait
+    // Next we join all the JobHandle objects to make the main thread to wait
     for handle in handles{
-        handle.join().expect("Error joinning threads!");
-        // join returns a Result. we use expect here to catch the error if it panics, 
+        handle.join().expect("Error joinning tasks!");
+        // join returns a Result (Err if the task panicked). we use expect here to catch the error, 
         // it is better to handle this error gracefully , by using for instance a match pattern.
     }
 
     println!("Main Thread sees SHARED VEC: {:?}" , shared_vec);
 
+    compare_strategies(&config);
+}
+
+// The same workload against an RwLock (std, and reader-preferring), a Mutex and a copy-on-write Arc (see src/strategies.rs).
+fn compare_strategies(config: &Config) {
+    println!(
+        "\n{} readers, {} writers, {} operations each, read hold {:?}, write hold {:?}, write ratio {}",
+        config.readers, config.writers, config.iterations, config.read_hold, config.write_hold, config.write_ratio
+    );
+    println!("{}", workload::run(&RwLockVec::new(), config));
+    println!("{}", workload::run(&ReaderPreferringVec::new(), config));
+    println!("{}", workload::run(&MutexVec::new(), config));
+    println!("{}", workload::run(&CowVec::new(), config));
 }
@@ -0,0 +1,202 @@
+// Four ways to share a Vec<i32> between readers and writers.
+//
+//      RwLockVec             RwLock<Vec<i32>>: many readers at the same time, or one writer
+//      ReaderPreferringVec   the same, with a lock that always lets new readers in (to show starvation)
+//      MutexVec              Mutex<Vec<i32>>: one thread at a time, readers included
+//      CowVec                copy-on-write: readers take a snapshot (an Arc) of the current Vec and read it
+//                            without any lock; a writer copies the Vec, changes the copy, then swaps the Arc
+//
+// With an RwLock, a writer has to wait until no reader holds the lock. If readers keep arriving,
+// there may never be such a moment: the writer "starves". Whether it does depends on the RwLock:
+// a reader-preferring lock lets new readers in while a writer waits, a writer-preferring lock makes new
+// readers wait behind the writer. The std RwLock does not promise either (it depends on the OS; on Linux
+// it prefers writers), so ReaderPreferringVec builds a reader-preferring one to see the difference.
+//
+// With copy-on-write, readers never wait for writers and writers never wait for readers (only for
+// other writers). The price: every write copies the whole Vec, and a reader may read a snapshot that
+// a writer has already replaced.
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, RwLock};
+
+/// A Vec<i32> shared between threads. `read` and `write` run the closure with access to the Vec.
+pub trait SharedVec: Sync {
+    fn name(&self) -> &'static str;
+
+    fn read<R>(&self, read: impl FnOnce(&[i32]) -> R) -> R;
+
+    fn write(&self, write: impl FnOnce(&mut Vec<i32>));
+}
+
+// NOTE: a panic in a closure cannot break a Vec<i32> (push either happens or not), so every strategy
+// uses a poisoned lock as it is instead of panicking too.
+
+#[derive(Debug, Default)]
+pub struct RwLockVec {
+    values: RwLock<Vec<i32>>,
+}
+
+impl RwLockVec {
+    pub fn new() -> Self {
+        RwLockVec::default()
+    }
+}
+
+impl SharedVec for RwLockVec {
+    fn name(&self) -> &'static str {
+        "RwLock"
+    }
+
+    fn read<R>(&self, read: impl FnOnce(&[i32]) -> R) -> R {
+        read(&self.values.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write(&self, write: impl FnOnce(&mut Vec<i32>)) {
+        write(&mut self.values.write().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+// Who holds the Vec of a ReaderPreferringVec.
+#[derive(Debug, Default)]
+struct Holders {
+    readers: usize,
+    writer: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ReaderPreferringVec {
+    // Readers and writers first agree here on who may use the Vec: a reader comes in as soon as no writer
+    // *holds* the Vec, even if writers are waiting. A writer needs the Vec free of every reader.
+    holders: Mutex<Holders>,
+    // notified when the last reader or the writer leaves
+    released: Condvar,
+    // Never contended: `holders` only lets in many readers or one writer. It is there so the
+    // Vec can be shared without unsafe code.
+    values: RwLock<Vec<i32>>,
+}
+
+impl ReaderPreferringVec {
+    pub fn new() -> Self {
+        ReaderPreferringVec::default()
+    }
+
+    fn holders(&self) -> MutexGuard<'_, Holders> {
+        self.holders.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn wait<'a>(&self, holders: MutexGuard<'a, Holders>) -> MutexGuard<'a, Holders> {
+        self.released.wait(holders).unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// Leaves the Vec when dropped, also if the closure panicked.
+struct Leave<'a> {
+    shared: &'a ReaderPreferringVec,
+    writer: bool,
+}
+
+impl Drop for Leave<'_> {
+    fn drop(&mut self) {
+        let mut holders = self.shared.holders();
+        if self.writer {
+            holders.writer = false;
+        } else {
+            holders.readers -= 1;
+        }
+        if holders.readers == 0 {
+            self.shared.released.notify_all();
+        }
+    }
+}
+
+impl SharedVec for ReaderPreferringVec {
+    fn name(&self) -> &'static str {
+        "reader-preferring RwLock"
+    }
+
+    fn read<R>(&self, read: impl FnOnce(&[i32]) -> R) -> R {
+        let mut holders = self.holders();
+        while holders.writer {
+            holders = self.wait(holders);
+        }
+        holders.readers += 1;
+        drop(holders);
+        let _leave = Leave { shared: self, writer: false };
+        read(&self.values.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write(&self, write: impl FnOnce(&mut Vec<i32>)) {
+        let mut holders = self.holders();
+        // while the readers overlap, `readers` never gets back to 0: the writer starves
+        while holders.writer || holders.readers > 0 {
+            holders = self.wait(holders);
+        }
+        holders.writer = true;
+        drop(holders);
+        let _leave = Leave { shared: self, writer: true };
+        write(&mut self.values.write().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MutexVec {
+    values: Mutex<Vec<i32>>,
+}
+
+impl MutexVec {
+    pub fn new() -> Self {
+        MutexVec::default()
+    }
+}
+
+impl SharedVec for MutexVec {
+    fn name(&self) -> &'static str {
+        "Mutex"
+    }
+
+    fn read<R>(&self, read: impl FnOnce(&[i32]) -> R) -> R {
+        read(&self.values.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write(&self, write: impl FnOnce(&mut Vec<i32>)) {
+        write(&mut self.values.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CowVec {
+    // the current version; the Mutex is only held to clone or replace the Arc, never while reading the Vec
+    current: Mutex<Arc<Vec<i32>>>,
+    // writers copy, change and swap one at a time, otherwise two writers would copy the same
+    // version and the second swap would lose the change of the first one
+    writer: Mutex<()>,
+}
+
+impl CowVec {
+    pub fn new() -> Self {
+        CowVec::default()
+    }
+
+    fn current(&self) -> MutexGuard<'_, Arc<Vec<i32>>> {
+        self.current.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SharedVec for CowVec {
+    fn name(&self) -> &'static str {
+        "copy-on-write Arc"
+    }
+
+    fn read<R>(&self, read: impl FnOnce(&[i32]) -> R) -> R {
+        // the lock is released at the end of this statement: the snapshot is read without any lock,
+        // and it stays valid (the Arc keeps it alive) even if a writer swaps in a new version meanwhile
+        let snapshot = Arc::clone(&self.current());
+        read(&snapshot)
+    }
+
+    fn write(&self, write: impl FnOnce(&mut Vec<i32>)) {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut copy = Vec::clone(&self.current());
+        write(&mut copy);
+        *self.current() = Arc::new(copy);
+    }
+}
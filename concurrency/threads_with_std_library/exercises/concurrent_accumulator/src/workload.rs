@@ -0,0 +1,215 @@
+// A configurable reader/writer workload, to see how long threads wait for the shared Vec.
+//
+// `readers` threads and `writers` threads each make `iterations` operations on the same SharedVec
+// (src/strategies.rs). A read sums the Vec, a write pushes a number. To look like real work, every
+// operation keeps the Vec for a while (`read_hold` / `write_hold`, a sleep inside the critical section).
+// Writer threads write with probability `write_ratio` and read otherwise, so a workload can go from
+// "writers only write" (1.0) to "writers mostly read" (0.1).
+//
+// For every operation we record its wait: the time between asking for the Vec and getting it.
+//
+//      wait = time the closure starts - time read()/write() is called
+//
+// Writer starvation shows up as writes waiting much longer than reads: the readers keep going while
+// the writers wait. `Report::writers_starved` flags a run where a write waited over `starvation_threshold`
+// and the writes waited 10 times longer than the reads (p99). If reads wait as long as writes (a Mutex
+// under heavy contention), everybody is slow, but the writers are not singled out.
+
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::strategies::SharedVec;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub readers: usize,
+    pub writers: usize,
+    /// Operations per thread.
+    pub iterations: usize,
+    /// How long a read keeps the Vec.
+    pub read_hold: Duration,
+    /// How long a write keeps the Vec.
+    pub write_hold: Duration,
+    /// Probability that an operation of a writer thread is a write (the others are reads).
+    pub write_ratio: f64,
+    /// A write waiting longer than this, while the reads do not wait, means writers are starved.
+    pub starvation_threshold: Duration,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            readers: 8,
+            writers: 2,
+            iterations: 100,
+            read_hold: Duration::from_micros(500),
+            write_hold: Duration::from_micros(500),
+            write_ratio: 1.0,
+            starvation_threshold: Duration::from_millis(50),
+            seed: 42,
+        }
+    }
+}
+
+impl Config {
+    /// Reads `--readers N --writers N --iterations N --read-hold-us N --write-hold-us N
+    /// --write-ratio R --starvation-ms N --seed N`, every option is optional.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut args = args.into_iter();
+        while let Some(option) = args.next() {
+            let value = args.next().ok_or_else(|| format!("missing value for {}", option))?;
+            let invalid = || format!("invalid value '{}' for {}", value, option);
+            let micros = || value.parse().map(Duration::from_micros).map_err(|_| invalid());
+            match option.as_str() {
+                "--readers" => config.readers = value.parse().map_err(|_| invalid())?,
+                "--writers" => config.writers = value.parse().map_err(|_| invalid())?,
+                "--iterations" => config.iterations = value.parse().map_err(|_| invalid())?,
+                "--read-hold-us" => config.read_hold = micros()?,
+                "--write-hold-us" => config.write_hold = micros()?,
+                "--write-ratio" => {
+                    let ratio = value.parse().ok().filter(|ratio| (0.0..=1.0).contains(ratio));
+                    config.write_ratio = ratio.ok_or_else(invalid)?
+                }
+                "--starvation-ms" => {
+                    config.starvation_threshold = value.parse().map(Duration::from_millis).map_err(|_| invalid())?
+                }
+                "--seed" => config.seed = value.parse().map_err(|_| invalid())?,
+                _ => return Err(format!("unknown option {}", option)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Statistics about the waits of a kind of operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Latency {
+    pub count: usize,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl Latency {
+    pub fn from_waits(mut waits: Vec<Duration>) -> Latency {
+        if waits.is_empty() {
+            return Latency::default();
+        }
+        waits.sort();
+        // nearest rank: the smallest wait such that p% of the waits are lower or equal
+        let percentile = |p: usize| waits[(waits.len() * p).div_ceil(100).max(1) - 1];
+        Latency {
+            count: waits.len(),
+            mean: waits.iter().sum::<Duration>() / waits.len() as u32,
+            p50: percentile(50),
+            p99: percentile(99),
+            max: waits[waits.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Latency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>6} ops, wait mean {:>10.2?} p50 {:>10.2?} p99 {:>10.2?} max {:>10.2?}",
+            self.count, self.mean, self.p50, self.p99, self.max
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub strategy: &'static str,
+    pub reads: Latency,
+    pub writes: Latency,
+    pub elapsed: Duration,
+    /// Length of the Vec at the end: the number of writes.
+    pub final_len: usize,
+    pub writers_starved: bool,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({:.2?}, {} values)", self.strategy, self.elapsed, self.final_len)?;
+        writeln!(f, "  reads  {}", self.reads)?;
+        write!(f, "  writes {}", self.writes)?;
+        if self.writers_starved {
+            write!(f, "  <- writers starved")?;
+        }
+        Ok(())
+    }
+}
+
+// The waits recorded by one thread.
+#[derive(Default)]
+struct Waits {
+    reads: Vec<Duration>,
+    writes: Vec<Duration>,
+}
+
+/// Runs the workload against `shared` and reports the waits.
+pub fn run<S: SharedVec>(shared: &S, config: &Config) -> Report {
+    let start = Instant::now();
+    // scoped threads: they borrow `shared` and `config`, no Arc needed
+    let waits: Vec<Waits> = thread::scope(|scope| {
+        let readers = (0..config.readers).map(|_| scope.spawn(|| worker(shared, config, None)));
+        // wrapping: '--seed 18446744073709551615' is a valid seed, the next writer's seed wraps to 0
+        let writers = (0..config.writers).map(|w| {
+            let seed = config.seed.wrapping_add(w as u64);
+            scope.spawn(move || worker(shared, config, Some(seed)))
+        });
+        // collect first: every thread must be spawned before we wait for the first one
+        let handles: Vec<_> = readers.chain(writers).collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    let elapsed = start.elapsed();
+
+    let (mut reads, mut writes) = (Vec::new(), Vec::new());
+    for thread in waits {
+        reads.extend(thread.reads);
+        writes.extend(thread.writes);
+    }
+    let (reads, writes) = (Latency::from_waits(reads), Latency::from_waits(writes));
+    Report {
+        strategy: shared.name(),
+        writers_starved: writes.max > config.starvation_threshold && writes.p99 > reads.p99 * 10,
+        reads,
+        writes,
+        elapsed,
+        final_len: shared.read(|values| values.len()),
+    }
+}
+
+// A reader thread (seed None) only reads, a writer thread writes with probability `write_ratio`.
+fn worker<S: SharedVec>(shared: &S, config: &Config, writer_seed: Option<u64>) -> Waits {
+    let mut rng = writer_seed.map(StdRng::seed_from_u64);
+    let mut waits = Waits::default();
+    for i in 0..config.iterations {
+        let write = rng.as_mut().is_some_and(|rng| rng.gen_bool(config.write_ratio));
+        let asked = Instant::now();
+        if write {
+            shared.write(|values| {
+                waits.writes.push(asked.elapsed());
+                values.push(i as i32);
+                thread::sleep(config.write_hold);
+            });
+        } else {
+            let sum = shared.read(|values| {
+                waits.reads.push(asked.elapsed());
+                thread::sleep(config.read_hold);
+                values.iter().map(|value| i64::from(*value)).sum::<i64>()
+            });
+            // use the sum, so reading the Vec is not optimized away
+            std::hint::black_box(sum);
+        }
+    }
+    waits
+}
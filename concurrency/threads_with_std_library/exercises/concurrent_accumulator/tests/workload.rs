@@ -0,0 +1,129 @@
+use std::sync::Barrier;
+use std::thread;
+use std::time::Duration;
+
+use concurrent_accumulator::strategies::{CowVec, MutexVec, ReaderPreferringVec, RwLockVec, SharedVec};
+use concurrent_accumulator::workload::{self, Config, Latency};
+
+fn small_config() -> Config {
+    Config {
+        readers: 4,
+        writers: 2,
+        iterations: 50,
+        read_hold: Duration::ZERO,
+        write_hold: Duration::ZERO,
+        ..Config::default()
+    }
+}
+
+fn check_every_write_is_kept<S: SharedVec>(shared: &S) {
+    let config = small_config();
+    let report = workload::run(shared, &config);
+    assert_eq!(report.reads.count + report.writes.count, (config.readers + config.writers) * config.iterations, "{}", report.strategy);
+    // write_ratio 1.0: writers only write
+    assert_eq!(report.writes.count, config.writers * config.iterations);
+    assert_eq!(report.final_len, report.writes.count, "{}", report.strategy);
+    // every value written by the writers (0..iterations, once per writer) is there
+    let mut values = shared.read(|values| values.to_vec());
+    values.sort();
+    let expected: Vec<i32> = (0..config.iterations as i32).flat_map(|i| [i, i]).collect();
+    assert_eq!(values, expected, "{}", report.strategy);
+}
+
+#[test]
+fn every_strategy_keeps_every_write() {
+    check_every_write_is_kept(&RwLockVec::new());
+    check_every_write_is_kept(&ReaderPreferringVec::new());
+    check_every_write_is_kept(&MutexVec::new());
+    check_every_write_is_kept(&CowVec::new());
+}
+
+#[test]
+fn write_ratio_mixes_reads_into_writer_threads() {
+    let config = Config { readers: 0, writers: 4, iterations: 500, write_ratio: 0.25, ..small_config() };
+    let report = workload::run(&MutexVec::new(), &config);
+    assert_eq!(report.reads.count + report.writes.count, 2_000);
+    assert!((300..700).contains(&report.writes.count), "{} writes", report.writes.count);
+
+    let config = Config { write_ratio: 0.0, ..config };
+    assert_eq!(workload::run(&MutexVec::new(), &config).writes.count, 0);
+}
+
+#[test]
+fn the_largest_seed_does_not_overflow() {
+    let config = Config { seed: u64::MAX, write_ratio: 0.5, ..small_config() };
+    let report = workload::run(&MutexVec::new(), &config);
+    assert_eq!(report.reads.count + report.writes.count, (config.readers + config.writers) * config.iterations);
+}
+
+// Two readers take turns so that one of them always holds the Vec: a reader-preferring lock
+// never lets the writer in until they stop, the copy-on-write Vec does not make it wait at all.
+#[test]
+fn overlapping_readers_starve_a_writer_of_a_reader_preferring_lock() {
+    fn writer_wait<S: SharedVec>(shared: &S) -> Duration {
+        let start = Barrier::new(3);
+        thread::scope(|scope| {
+            for reader in 0..2 {
+                let start = &start;
+                scope.spawn(move || {
+                    start.wait();
+                    // out of step: one reader is always halfway through its read when the other one leaves
+                    thread::sleep(Duration::from_micros(2_500) * reader);
+                    for _ in 0..20 {
+                        shared.read(|_| thread::sleep(Duration::from_millis(5)));
+                    }
+                });
+            }
+            start.wait();
+            // let the readers get in first
+            thread::sleep(Duration::from_millis(4));
+            let asked = std::time::Instant::now();
+            let mut waited = Duration::ZERO;
+            shared.write(|values| {
+                waited = asked.elapsed();
+                values.push(1);
+            });
+            waited
+        })
+    }
+
+    // the readers overlap for about 100 ms, the writer only gets in once they are done
+    assert!(writer_wait(&ReaderPreferringVec::new()) > Duration::from_millis(40));
+    assert!(writer_wait(&CowVec::new()) < Duration::from_millis(40));
+}
+
+#[test]
+fn latency_percentiles() {
+    let waits: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+    let latency = Latency::from_waits(waits);
+    assert_eq!(latency.count, 100);
+    assert_eq!(latency.p50, Duration::from_millis(50));
+    assert_eq!(latency.p99, Duration::from_millis(99));
+    assert_eq!(latency.max, Duration::from_millis(100));
+    assert_eq!(latency.mean, Duration::from_micros(50_500));
+    assert_eq!(Latency::from_waits(Vec::new()), Latency::default());
+    assert_eq!(Latency::from_waits(vec![Duration::from_millis(3)]).p99, Duration::from_millis(3));
+}
+
+#[test]
+fn config_from_args() {
+    let args = "--readers 3 --writers 1 --iterations 10 --read-hold-us 20 --write-hold-us 30 --write-ratio 0.5 --starvation-ms 7 --seed 9";
+    let config = Config::from_args(args.split(' ').map(String::from)).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            readers: 3,
+            writers: 1,
+            iterations: 10,
+            read_hold: Duration::from_micros(20),
+            write_hold: Duration::from_micros(30),
+            write_ratio: 0.5,
+            starvation_threshold: Duration::from_millis(7),
+            seed: 9,
+        }
+    );
+    assert_eq!(Config::from_args(Vec::new()), Ok(Config::default()));
+    assert!(Config::from_args(["--write-ratio", "1.5"].map(String::from)).is_err());
+    assert!(Config::from_args(["--readers"].map(String::from)).is_err());
+    assert!(Config::from_args(["--verbose", "1"].map(String::from)).is_err());
+}
@@ -0,0 +1,211 @@
+// Many ways to count from many threads, behind one trait, to compare their speed.
+//
+// The exercise uses Arc<Mutex<i32>>: correct, but every increment takes the lock, and with many
+// threads they spend most of their time waiting for each other. The backends here:
+//
+//      MutexCounter     one Mutex<u64>: lock, +1, unlock
+//      AtomicCounter    one AtomicU64: fetch_add(1), no lock, with a chosen memory Ordering
+//      ShardedCounter   one AtomicU64 per shard, each thread adds to its own shard, get() sums them
+//      LocalCounter     each thread counts in a local variable, and adds its total once, when it is done
+//
+// NOTE: Cache lines. Even with an atomic, all the threads write to the same memory, and the CPU cores pass
+// the cache line (64 bytes, on most CPUs) that contains it from one to another at every increment.
+// ShardedCounter gives every thread its own shard, and pads every shard to 128 bytes (CachePadded):
+// two shards never share a cache line, otherwise the cores would still fight for it ("false sharing").
+//
+// NOTE: Memory ordering. A counter only needs its increments to be atomic: Relaxed is enough, since no
+// other memory is published with the count. SeqCst, AcqRel, ... add guarantees (and, on some CPUs, fences)
+// that a counter does not use. AtomicCounter takes the Ordering to compare them.
+
+use std::hint::black_box;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A counter shared by worker threads.
+pub trait Counter: Sync {
+    fn name(&self) -> String;
+
+    /// Called once by every worker thread: adds 1, `increments` times.
+    /// `thread` is the number of the worker (0, 1, 2, ...).
+    fn count(&self, thread: usize, increments: u64);
+
+    /// The total, once the workers are done.
+    fn get(&self) -> u64;
+}
+
+#[derive(Debug, Default)]
+pub struct MutexCounter {
+    value: Mutex<u64>,
+}
+
+impl MutexCounter {
+    pub fn new() -> Self {
+        MutexCounter::default()
+    }
+}
+
+impl Counter for MutexCounter {
+    fn name(&self) -> String {
+        String::from("Mutex<u64>")
+    }
+
+    fn count(&self, _thread: usize, increments: u64) {
+        for _ in 0..increments {
+            // a u64 cannot be left half updated by a panic: a poisoned lock can be used as it is
+            *self.value.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+        }
+    }
+
+    fn get(&self) -> u64 {
+        *self.value.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Debug)]
+pub struct AtomicCounter {
+    value: AtomicU64,
+    ordering: Ordering,
+}
+
+impl AtomicCounter {
+    /// `ordering` is used by every `fetch_add` (all the orderings are valid for a read-modify-write).
+    pub fn new(ordering: Ordering) -> Self {
+        AtomicCounter { value: AtomicU64::new(0), ordering }
+    }
+}
+
+impl Counter for AtomicCounter {
+    fn name(&self) -> String {
+        format!("AtomicU64 ({:?})", self.ordering)
+    }
+
+    fn count(&self, _thread: usize, increments: u64) {
+        for _ in 0..increments {
+            self.value.fetch_add(1, self.ordering);
+        }
+    }
+
+    fn get(&self) -> u64 {
+        // SeqCst: the workers are joined before get(), the ordering only matters for the increments
+        self.value.load(Ordering::SeqCst)
+    }
+}
+
+/// A value alone in its cache line(s): 128 bytes covers the 64-byte lines of most CPUs,
+/// and the pairs of lines some of them prefetch together.
+#[derive(Debug, Default)]
+#[repr(align(128))]
+pub struct CachePadded<T>(pub T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[derive(Debug)]
+pub struct ShardedCounter {
+    shards: Vec<CachePadded<AtomicU64>>,
+}
+
+impl ShardedCounter {
+    /// A counter with `shards` shards (at least 1). With as many shards as threads, no two threads share one.
+    pub fn new(shards: usize) -> Self {
+        ShardedCounter { shards: (0..shards.max(1)).map(|_| CachePadded::default()).collect() }
+    }
+}
+
+impl Counter for ShardedCounter {
+    fn name(&self) -> String {
+        format!("sharded ({} shards)", self.shards.len())
+    }
+
+    fn count(&self, thread: usize, increments: u64) {
+        let shard = &self.shards[thread % self.shards.len()];
+        for _ in 0..increments {
+            shard.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn get(&self) -> u64 {
+        // only exact once the workers are done: the shards are read one after the other
+        self.shards.iter().map(|shard| shard.load(Ordering::Relaxed)).sum()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LocalCounter {
+    total: AtomicU64,
+}
+
+impl LocalCounter {
+    pub fn new() -> Self {
+        LocalCounter::default()
+    }
+}
+
+impl Counter for LocalCounter {
+    fn name(&self) -> String {
+        String::from("thread-local, merged at join")
+    }
+
+    fn count(&self, _thread: usize, increments: u64) {
+        let mut local: u64 = 0;
+        for _ in 0..increments {
+            // black_box: otherwise the compiler replaces the loop with `local = increments`
+            local = black_box(local) + 1;
+        }
+        // the only write to shared memory, when the thread is done
+        self.total.fetch_add(local, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+}
+
+/// One counter of each kind, for `threads` worker threads.
+pub fn all_backends(threads: usize) -> Vec<Box<dyn Counter>> {
+    vec![
+        Box::new(MutexCounter::new()),
+        Box::new(AtomicCounter::new(Ordering::Relaxed)),
+        Box::new(AtomicCounter::new(Ordering::AcqRel)),
+        Box::new(AtomicCounter::new(Ordering::SeqCst)),
+        Box::new(ShardedCounter::new(threads)),
+        Box::new(LocalCounter::new()),
+    ]
+}
+
+/// The result of one run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub name: String,
+    pub total: u64,
+    pub expected: u64,
+    pub elapsed: Duration,
+}
+
+impl Measurement {
+    /// Millions of increments per second.
+    pub fn throughput(&self) -> f64 {
+        self.expected as f64 / self.elapsed.as_secs_f64() / 1_000_000.0
+    }
+}
+
+/// Runs `threads` workers making `increments` increments each, and times them until they are all joined.
+pub fn measure(counter: &dyn Counter, threads: usize, increments: u64) -> Measurement {
+    let start = Instant::now();
+    // scoped threads: they can borrow the counter, no Arc needed
+    thread::scope(|scope| {
+        for t in 0..threads {
+            scope.spawn(move || counter.count(t, increments));
+        }
+    });
+    let elapsed = start.elapsed();
+    Measurement { name: counter.name(), total: counter.get(), expected: threads as u64 * increments, elapsed }
+}
@@ -0,0 +1,4 @@
+// The counters used by main.rs, in a library so the tests in tests/ can use them too.
+
+pub mod counters;
+pub mod recovering;
@@ -1,7 +1,17 @@
-use std::sync::{Arc, Mutex};
-use::std::thread;
+use std::sync::Arc;
+use std::thread;
+
+use concurrent_counter::counters::{self, Measurement};
+use concurrent_counter::recovering::RecoveringCounter;
+use thread_pool::{JobError, JobHandle, ThreadPool};
 
 fn main() {
+    // cargo run --release -- <threads> <increments per thread>   (for the comparison at the end)
+    let (threads, increments) = parse_args().unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        eprintln!("usage: concurrent_counter [threads] [increments per thread]");
+        std::process::exit(2);
+    });
     
     // My counter must a value with shared ownnership among all the threads ->  counter: Arc<i32>; 
     // so I could declare my counter like this:   let mut counter: Arc<i32> = Arc::new(0);
@@ -10,46 +20,134 @@ fn main() {
     // The Mutex guarantees that only one thread can lock the mutex at a time, 
     // ensuring that only one thread can access or modify the data.
     // Thus, I declare my counter like this: 
+    //      let counter: Arc<Mutex<i32>> = Arc::new(Mutex::new(0));
+    // but with lock().unwrap(), a task that panics while it holds the lock poisons the Mutex,
+    // and every task after it panics too (see panicking_worker below).
+    // A RecoveringCounter (src/recovering.rs) is that Mutex<i32>, and it handles the PoisonError:
+    // it rolls back the interrupted update, clears the poison and continues.
     
-    let counter: Arc<Mutex<i32>> = Arc::new(Mutex::new(0));
+    let counter: Arc<RecoveringCounter> = Arc::new(RecoveringCounter::new(0));
     // We use an Arc to share memory among threads, and the data inside the Arc is protected with a mutex.
 
-    let mut handles = vec![];
+    // Instead of spawning a thread per task, the tasks are given to a pool of 4 worker threads
+    // (see ../../thread_pool): the workers are created once and run the tasks one after the other.
+    let pool = ThreadPool::with_name("counter", 4);
+
+    let mut handles: Vec<JobHandle<()>> = vec![];
 
-    // Spawn 10 threads
+    // Submit 10 tasks
     for t in 1..=10 {
-        // a shared reference created for each thread
+        // a shared reference created for each task
         let counter_shared_ref = Arc::clone(&counter);    
 
-        let handle = thread::spawn( move || { 
-            // lock() : Acquires a mutex, blocking the current thread until it is able to do so.
-            let mut counter_value = counter_shared_ref.lock().unwrap();
-            // update the value
-            println!("Thread {} reading counter: {}", t, *counter_value);
-            *counter_value += 1;
-            println!("Thread {} updated counter: {}", t, *counter_value);
-
+        let handle = pool.execute( move || { 
+            // update() locks the mutex, blocking the current thread until it is able to do so,
+            // and runs the closure with the lock held
+            counter_shared_ref.update(|counter_value| {
+                // update the value
+                let worker = thread::current().name().unwrap_or("?").to_string();
+                println!("Task {} on {} reading counter: {}", t, worker, *counter_value);
+                *counter_value += 1;
+                println!("Task {} on {} updated counter: {}", t, worker, *counter_value);
+            })
         });
         // add the handle to a vector of handles 
         handles.push(handle);
     }
 
-    // After spawning the threads, the main thread waits for each of them to finish 
+    // After submitting the tasks, the main thread waits for each of them to finish 
     // by calling join on each handle stored in the handles
     for handle in handles {
-        handle.join().expect("Error joinning threads!");
+        handle.join().expect("Error joinning tasks!");
     }
 
-    // as our counter is a mutex, we lock() it in order to read its value.
-    println!("Counter is : {:?}", counter.lock().unwrap());
+    // as our counter is a mutex, get() locks it in order to read its value.
+    println!("Counter is : {}", counter.get());
     
 
     // Some notes: 
+    // A JobHandle works like the JoinHandle of a thread:
     // To learn when a thread completes, it is necessary to capture the JoinHandle object that is returned by the call to spawn, 
     // which provides a join method that allows the caller to wait for the completion of the spawned thread. 
     // spawn() : Spawns a new thread, returning a JoinHandle for it.
     // The join method returns a thread::Result containing Ok of the final value produced by the spawned thread, 
     // or Err of the value given to a call to panic! if the thread panicked.
 
+    panicking_worker(&pool);
+
+    compare_backends(threads, increments);
+}
+
+// The same counter, but task 5 panics in the middle of its update, while it holds the lock.
+// The Mutex is poisoned: with lock().unwrap() the tasks after it would panic too.
+// RecoveringCounter (src/recovering.rs) rolls back the interrupted update, clears the poison and continues.
+// The pool catches the panic: the worker that ran task 5 keeps running the next tasks.
+fn panicking_worker(pool: &ThreadPool) {
+    let counter = Arc::new(RecoveringCounter::new(0));
+
+    // join each task before the next one, so the order is easy to follow
+    for t in 1..=10 {
+        let counter_shared_ref = Arc::clone(&counter);
+        let handle = pool.execute(move || {
+            counter_shared_ref.update(|value| {
+                *value += 1;
+                if t == 5 {
+                    panic!("task {} failed in the middle of its update (counter at {})", t, value);
+                }
+            })
+        });
+        match handle.join() {
+            Ok(()) => println!("Task {} updated counter: {}", t, counter.get()),
+            // get() found the lock poisoned: it rolled back the update of task 5 and cleared the poison
+            Err(JobError::Panicked(message)) => {
+                println!("Task {} panicked ({}), counter rolled back to: {}", t, message, counter.get())
+            }
+            Err(error) => println!("Task {}: {}", t, error),
+        }
+    }
+
+    println!(
+        "Counter is : {} (9 successful updates, {} recovery from a poisoned lock)",
+        counter.get(),
+        counter.recoveries()
+    );
+}
+
+fn parse_args() -> Result<(usize, u64), String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() > 2 {
+        return Err(format!("too many arguments: {}", args.join(" ")));
+    }
+    let threads = match args.first() {
+        Some(arg) => arg
+            .parse()
+            .ok()
+            .filter(|threads| *threads > 0)
+            .ok_or(format!("invalid number of threads '{}'", arg))?,
+        None => 4,
+    };
+    let increments = match args.get(1) {
+        Some(arg) => arg.parse().map_err(|_| format!("invalid number of increments '{}'", arg))?,
+        None => 1_000_000,
+    };
+    Ok((threads, increments))
+}
 
+// Every Counter backend (see src/counters.rs) with the same number of threads and increments.
+// Build with --release: in debug mode the loops are not optimized and the numbers mean little.
+fn compare_backends(threads: usize, increments: u64) {
+    println!("\n{} threads x {} increments", threads, increments);
+    println!("{:<30} {:>14} {:>10} {:>12}", "backend", "total", "time (ms)", "M incr/s");
+    for counter in counters::all_backends(threads) {
+        let measurement: Measurement = counters::measure(counter.as_ref(), threads, increments);
+        let check = if measurement.total == measurement.expected { "" } else { "  WRONG TOTAL" };
+        println!(
+            "{:<30} {:>14} {:>10.1} {:>12.1}{}",
+            measurement.name,
+            measurement.total,
+            measurement.elapsed.as_secs_f64() * 1000.0,
+            measurement.throughput(),
+            check
+        );
+    }
 }
@@ -0,0 +1,88 @@
+// A counter that survives a thread panicking while it holds the lock.
+//
+// If a thread panics while it holds a MutexGuard, the Mutex is "poisoned": the panic may have happened
+// in the middle of an update, so the data may be half modified. Every later lock() returns
+// Err(PoisonError), and with lock().unwrap() every other thread panics too: one bug stops the whole program.
+//
+// A PoisonError still contains the guard (poisoned.into_inner()), so we can decide what to do:
+//
+//      1. detect:     lock() returned Err
+//      2. repair:     validate the data, or roll back the update that was interrupted
+//      3. clear:      mutex.clear_poison(), the next lock() returns Ok again
+//      4. continue
+//
+// To know what to roll back, the counter keeps an undo log next to the value: before an update it
+// saves the current value, after the update it removes it. If the log is still there when the lock
+// is taken, the previous update never finished, and the saved value is restored.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug)]
+struct State {
+    value: i32,
+    // the value before the update in progress (None when no update is in progress)
+    before_update: Option<i32>,
+}
+
+/// An `i32` counter behind a Mutex, which rolls back an update interrupted by a panic.
+#[derive(Debug)]
+pub struct RecoveringCounter {
+    state: Mutex<State>,
+    recoveries: AtomicUsize,
+}
+
+impl RecoveringCounter {
+    pub fn new(value: i32) -> Self {
+        RecoveringCounter { state: Mutex::new(State { value, before_update: None }), recoveries: AtomicUsize::new(0) }
+    }
+
+    /// Runs `update` on the value, with the lock held. If `update` panics, the change is rolled back
+    /// by the next thread that locks the counter.
+    pub fn update<R>(&self, update: impl FnOnce(&mut i32) -> R) -> R {
+        let mut state = self.lock();
+        state.before_update = Some(state.value);
+        let result = update(&mut state.value);
+        // not reached if update() panicked: the undo log stays
+        state.before_update = None;
+        result
+    }
+
+    /// Adds 1, returns the new value.
+    pub fn increment(&self) -> i32 {
+        self.update(|value| {
+            *value += 1;
+            *value
+        })
+    }
+
+    pub fn get(&self) -> i32 {
+        self.lock().value
+    }
+
+    /// `true` if a thread panicked while holding the lock, and nobody has locked the counter since.
+    pub fn is_poisoned(&self) -> bool {
+        self.state.is_poisoned()
+    }
+
+    /// How many times the counter recovered from a poisoned lock.
+    pub fn recoveries(&self) -> usize {
+        self.recoveries.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => {
+                // we hold the lock, the poison is only a warning that the data may be inconsistent
+                let mut state = poisoned.into_inner();
+                if let Some(before_update) = state.before_update.take() {
+                    state.value = before_update;
+                }
+                self.state.clear_poison();
+                self.recoveries.fetch_add(1, Ordering::Relaxed);
+                state
+            }
+        }
+    }
+}
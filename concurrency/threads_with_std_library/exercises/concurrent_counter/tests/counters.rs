@@ -0,0 +1,42 @@
+use std::sync::atomic::Ordering;
+
+use concurrent_counter::counters::{self, AtomicCounter, CachePadded, Counter, ShardedCounter};
+
+#[test]
+fn every_backend_counts_every_increment() {
+    for threads in [1, 3, 8] {
+        for counter in counters::all_backends(threads) {
+            let measurement = counters::measure(counter.as_ref(), threads, 20_000);
+            assert_eq!(measurement.expected, threads as u64 * 20_000);
+            assert_eq!(measurement.total, measurement.expected, "{} with {} threads", measurement.name, threads);
+        }
+    }
+}
+
+#[test]
+fn counters_add_up_across_runs() {
+    let counter = AtomicCounter::new(Ordering::Relaxed);
+    counters::measure(&counter, 2, 10);
+    counters::measure(&counter, 3, 10);
+    assert_eq!(counter.get(), 50);
+    assert_eq!(counter.name(), "AtomicU64 (Relaxed)");
+}
+
+#[test]
+fn sharded_counter_with_more_threads_than_shards() {
+    // threads 0 and 2 share shard 0
+    let counter = ShardedCounter::new(2);
+    let measurement = counters::measure(&counter, 5, 1_000);
+    assert_eq!(measurement.total, 5_000);
+    // at least one shard
+    assert_eq!(ShardedCounter::new(0).name(), "sharded (1 shards)");
+}
+
+#[test]
+fn padded_values_do_not_share_a_cache_line() {
+    assert_eq!(std::mem::align_of::<CachePadded<u64>>(), 128);
+    let shards: Vec<CachePadded<u64>> = (0..3).map(CachePadded).collect();
+    let addresses: Vec<usize> = shards.iter().map(|shard| &**shard as *const u64 as usize).collect();
+    assert!(addresses.windows(2).all(|pair| pair[1] - pair[0] >= 128));
+    assert_eq!(*shards[2], 2);
+}
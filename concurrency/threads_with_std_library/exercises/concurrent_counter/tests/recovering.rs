@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::thread;
+
+use concurrent_counter::recovering::RecoveringCounter;
+
+#[test]
+fn panic_in_the_middle_of_an_update_is_rolled_back() {
+    let counter = Arc::new(RecoveringCounter::new(10));
+    let result = {
+        let counter = Arc::clone(&counter);
+        thread::spawn(move || {
+            counter.update(|value| {
+                *value += 5;
+                panic!("injected panic");
+            })
+        })
+        .join()
+    };
+    assert!(result.is_err());
+    assert!(counter.is_poisoned());
+
+    // the next lock detects the poison, restores the value and clears the poison
+    assert_eq!(counter.get(), 10);
+    assert!(!counter.is_poisoned());
+    assert_eq!(counter.recoveries(), 1);
+    assert_eq!(counter.increment(), 11);
+    assert_eq!(counter.recoveries(), 1);
+}
+
+#[test]
+fn update_returns_the_result_of_the_closure() {
+    let counter = RecoveringCounter::new(0);
+    assert_eq!(counter.update(|value| std::mem::replace(value, 7)), 0);
+    assert_eq!(counter.get(), 7);
+    assert_eq!(counter.recoveries(), 0);
+}
+
+// Many threads increment the counter, and some of them panic in the middle of an update:
+// every successful increment is counted, every interrupted one is rolled back.
+#[test]
+fn panicking_threads_do_not_break_the_others() {
+    let counter = Arc::new(RecoveringCounter::new(0));
+    let handles: Vec<_> = (0..50)
+        .map(|t| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for i in 0..1_000 {
+                    counter.update(|value| {
+                        // a half-done update: +2 then -1 only counts 1 if both happen
+                        *value += 2;
+                        if t % 10 == 0 && i == 500 {
+                            panic!("thread {} interrupted", t);
+                        }
+                        *value -= 1;
+                    });
+                }
+            })
+        })
+        .collect();
+    let panicked = handles.into_iter().map(|handle| handle.join()).filter(Result::is_err).count();
+
+    assert_eq!(panicked, 5);
+    // 45 threads made 1000 increments, 5 threads made 500 before panicking
+    assert_eq!(counter.get(), 45 * 1_000 + 5 * 500);
+    assert_eq!(counter.recoveries(), 5);
+    assert!(!counter.is_poisoned());
+}
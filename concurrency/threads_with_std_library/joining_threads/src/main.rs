@@ -8,6 +8,11 @@ use std::time::Duration;
 // the spawned thread will run at all, 
 // because there is no guarantee on the order in which threads run.
 
+// As in 'basic_threads', the threads record what they do with the tracer in '../thread_tracer', and the
+// time main spends in join is a span: in the timeline, 'join' starts in the [MAIN] column, the
+// [SPAWNED] column goes on until its 'run' ends, and only then does 'join' end.
+// 'cargo run -- --chrome trace.json' also saves the trace for chrome://tracing or https://ui.perfetto.dev
+
 fn main() {
 
     // The return type of thread::spawn is JoinHandle.
@@ -17,9 +22,11 @@ fn main() {
     println!("   Calling join will block the currently running thread until the thread terminates.\n");
 
     // thread::spawn returns a JoinHandle 
-   let handle = thread::spawn(|| {
+   thread_tracer::event("spawn");
+   let handle = thread_tracer::spawn("spawned", || {
        for i in 1..20 {
            println!("hi number {} from the [SPAWNED] thread!", i);
+           thread_tracer::event(format!("message {}", i));
            thread::sleep(Duration::from_millis(10));
        }
    });
@@ -27,6 +34,7 @@ fn main() {
    println!("-> Main thread will print 10 times a message every 10 miliseconds!\n");
    for i in 1..10 {
        println!("hi number {} from the [MAIN] thread!", i);
+       thread_tracer::event(format!("message {}", i));
        thread::sleep(Duration::from_millis(10));
    }
 
@@ -37,11 +45,15 @@ fn main() {
    // Blocking a thread means that thread is prevented from performing work or exiting.
    // The two threads will alternate, but the main thread waits and does not end until the spawned thread is finished.
    // In this example all the spawned threads will be executed , different from the previous example in 'basic_threads'.
+   let join = thread_tracer::span("join");
    handle.join().unwrap();
+   drop(join);
    println!("NOTE: Calling join on the handle, blocks the thread currently running until the thread represented by the handle terminates!\n      All the threads will run until the end now.");
    // Small details, such as where join is called, can affect whether or not your threads run at the same time
    // Check what happens if we  move handle.join() before the for loop in main. 
    // the executiion will wait for all the spawned threads to finish running before print the message from the main thread, 
 
+   thread_tracer::event("main exits");
+   thread_tracer::show(&thread_tracer::snapshot(), std::env::args());
 
 }
@@ -0,0 +1,179 @@
+// A pool of worker threads that run jobs from a queue.
+//
+// The examples of this folder spawn one thread per task and keep its JoinHandle in a Vec.
+// Creating a thread is not free (a stack, a system call), and spawning one per task does not limit
+// how many run at the same time. A pool starts a fixed number of threads once and reuses them:
+//
+//      execute(job) ──> [ queue: mpsc channel ] ──> worker-0 ─┐
+//                                                 ──> worker-1 ─┼─> result ──> JobHandle::join()
+//                                                 ──> worker-2 ─┘
+//
+// The workers share the receiving end of the channel (behind a Mutex: a Receiver can only be used by
+// one thread at a time). Each one waits for a job, runs it, and waits for the next one.
+//
+// - Every job sends its result on its own channel: `execute` returns a JobHandle to wait for it.
+// - A job that panics does not kill its worker: the panic is caught (catch_unwind) and given to the
+//   JobHandle as an error, and the worker goes on with the next job.
+// - Dropping the pool is a graceful shutdown: the queue is closed, the workers run the jobs already
+//   queued, see that the queue is closed and empty, and stop; drop() waits for all of them.
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed number of named worker threads running the jobs given to [`ThreadPool::execute`].
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    // None once the pool is being dropped: closing the channel tells the workers to stop
+    queue: Option<Sender<Job>>,
+}
+
+struct Worker {
+    name: String,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// A pool of `size` workers named "worker-0", "worker-1", ...
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> ThreadPool {
+        ThreadPool::with_name("worker", size)
+    }
+
+    /// A pool of `size` workers named "<name>-0", "<name>-1", ... (the names show up in panic messages).
+    ///
+    /// Panics if `size` is 0, or if a thread cannot be created.
+    pub fn with_name(name: &str, size: usize) -> ThreadPool {
+        assert!(size > 0, "a thread pool needs at least one worker");
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size)
+            .map(|id| {
+                let name = format!("{}-{}", name, id);
+                let receiver = Arc::clone(&receiver);
+                let thread = thread::Builder::new()
+                    .name(name.clone())
+                    .spawn(move || work(&receiver))
+                    .expect("cannot create a worker thread");
+                Worker { name, thread: Some(thread) }
+            })
+            .collect();
+        ThreadPool { workers, queue: Some(sender) }
+    }
+
+    /// Number of workers.
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn worker_names(&self) -> Vec<&str> {
+        self.workers.iter().map(|worker| worker.name.as_str()).collect()
+    }
+
+    /// Queues a job. It runs as soon as a worker is free; its result (or its panic) is returned by
+    /// [`JobHandle::join`].
+    pub fn execute<F, T>(&self, job: F) -> JobHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job: Job = Box::new(move || {
+            // AssertUnwindSafe: if the job panics, whatever it was changing is only seen again through
+            // the JobHandle (as an error) or through its own shared state (e.g. a poisoned Mutex)
+            let result = panic::catch_unwind(AssertUnwindSafe(job))
+                .map_err(|payload| JobError::Panicked(panic_message(&*payload)));
+            // the JobHandle may have been dropped: nobody wants the result, not an error
+            let _ = result_sender.send(result);
+        });
+        // the queue is only closed in drop(), it is open as long as `self` exists
+        self.queue.as_ref().expect("the pool is shutting down").send(job).expect("the workers have stopped");
+        JobHandle { result: result_receiver }
+    }
+}
+
+// The loop of a worker: takes the next job, runs it, until the queue is closed and empty.
+fn work(queue: &Mutex<Receiver<Job>>) {
+    loop {
+        // The lock is released at the end of this statement, before the job runs, so the other workers
+        // can take jobs meanwhile. (Jobs never run with the lock held, so it is never poisoned by a panic.)
+        let job = queue.lock().unwrap_or_else(PoisonError::into_inner).recv();
+        match job {
+            Ok(job) => job(),
+            // every Sender is dropped: the pool is shutting down
+            Err(_) => break,
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // closes the queue: the workers stop once it is empty
+        drop(self.queue.take());
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                // jobs cannot panic the worker (catch_unwind), so join() only fails in extreme cases
+                if thread.join().is_err() {
+                    eprintln!("{} stopped abnormally", worker.name);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadPool").field("workers", &self.worker_names()).finish()
+    }
+}
+
+/// Why a job gave no result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobError {
+    /// The job panicked, with this message.
+    Panicked(String),
+    /// The job was dropped without running (its worker stopped abnormally).
+    Lost,
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobError::Panicked(message) => write!(f, "job panicked: {}", message),
+            JobError::Lost => write!(f, "job lost before it could run"),
+        }
+    }
+}
+
+impl std::error::Error for JobError {}
+
+/// The result of a job given to [`ThreadPool::execute`], like the JoinHandle of a thread.
+#[derive(Debug)]
+pub struct JobHandle<T> {
+    result: Receiver<Result<T, JobError>>,
+}
+
+impl<T> JobHandle<T> {
+    /// Waits for the job to finish and returns its result.
+    pub fn join(self) -> Result<T, JobError> {
+        // the job always sends a result, unless it was dropped without running
+        self.result.recv().unwrap_or(Err(JobError::Lost))
+    }
+}
+
+// panic!("...") gives a &str, panic!("{}", x) a String, anything else is unusual
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("(not a string)")
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+use thread_pool::{JobError, ThreadPool};
+
+#[test]
+fn jobs_return_their_results() {
+    let pool = ThreadPool::new(3);
+    let handles: Vec<_> = (0..20u64).map(|i| pool.execute(move || i * i)).collect();
+    let results: Vec<u64> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(results, (0..20u64).map(|i| i * i).collect::<Vec<_>>());
+}
+
+#[test]
+fn jobs_run_on_the_named_workers() {
+    let pool = ThreadPool::with_name("bank", 2);
+    assert_eq!(pool.size(), 2);
+    assert_eq!(pool.worker_names(), vec!["bank-0", "bank-1"]);
+
+    // both jobs must be running at the same time to get past the barrier: two different workers
+    let barrier = Arc::new(Barrier::new(2));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let barrier = Arc::clone(&barrier);
+            pool.execute(move || {
+                barrier.wait();
+                thread::current().name().unwrap().to_string()
+            })
+        })
+        .collect();
+    let names: BTreeSet<String> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(names, BTreeSet::from([String::from("bank-0"), String::from("bank-1")]));
+}
+
+#[test]
+fn a_panicking_job_does_not_stop_its_worker() {
+    // a single worker: if the panic killed it, the next jobs would never run
+    let pool = ThreadPool::new(1);
+    let failed = pool.execute(|| -> u32 { panic!("boom") });
+    let formatted = pool.execute(|| -> u32 { panic!("job {} failed", 7) });
+    let next = pool.execute(|| 42);
+
+    assert_eq!(failed.join(), Err(JobError::Panicked(String::from("boom"))));
+    assert_eq!(formatted.join(), Err(JobError::Panicked(String::from("job 7 failed"))));
+    assert_eq!(next.join(), Ok(42));
+}
+
+#[test]
+fn drop_runs_the_queued_jobs_before_stopping() {
+    let done = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::new(2);
+    for _ in 0..20 {
+        let done = Arc::clone(&done);
+        // the handles are dropped: nobody waits for these results
+        pool.execute(move || {
+            thread::sleep(Duration::from_millis(2));
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+    // most jobs are still in the queue here; drop() waits for all of them
+    drop(pool);
+    assert_eq!(done.load(Ordering::SeqCst), 20);
+}
+
+#[test]
+#[should_panic(expected = "at least one worker")]
+fn an_empty_pool_is_refused() {
+    ThreadPool::new(0);
+}
@@ -0,0 +1,291 @@
+// A small tracer: threads record what they do, with a timestamp, and the trace is shown afterwards
+// as a timeline (one column per thread) or saved for chrome://tracing.
+//
+// Printing from many threads interleaves the lines in a different order every run, and the printing
+// itself (stdout is locked for each println!) changes the scheduling. Recording is cheaper and the
+// order can be rebuilt from the timestamps:
+//
+//      event("message 1")  ->  (thread "spawned", "message 1", 12.3 ms after the start)
+//
+// NOTE: Per-thread buffers. Every thread appends to its own buffer (a "lane"), so threads never wait for
+// each other to record an event. The buffers are registered in a global list the first time a thread
+// records something, so they stay readable after their thread is gone (or still running, when the
+// main thread takes the trace just before it exits). The Mutex of a lane is only contended while
+// snapshot() reads it.
+//
+// - event(label): something happened now
+// - span(label): something that lasts, from now until the returned guard is dropped
+// - spawn(name, f): thread::spawn with a name and a span "run" covering the whole thread: a thread
+//   without the end of its "run" span had not finished when the trace was taken
+
+use std::cell::OnceCell;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Something that happened at one point in time.
+    Instant,
+    /// The start of a span.
+    Begin,
+    /// The end of a span.
+    End,
+}
+
+/// An event of the trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub thread: String,
+    /// The lanes are numbered in the order the threads recorded their first event.
+    pub lane: usize,
+    pub kind: Kind,
+    pub label: String,
+    /// Time since the first event of the trace.
+    pub at: Duration,
+}
+
+struct Record {
+    kind: Kind,
+    label: String,
+    at: Duration,
+}
+
+struct Lane {
+    thread: String,
+    index: usize,
+    records: Mutex<Vec<Record>>,
+}
+
+static START: OnceLock<Instant> = OnceLock::new();
+static LANES: Mutex<Vec<Arc<Lane>>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static LANE: OnceCell<Arc<Lane>> = const { OnceCell::new() };
+}
+
+// Recording never runs user code with a lock held: a poisoned lock still holds valid data.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn record(kind: Kind, label: String) {
+    let at = START.get_or_init(Instant::now).elapsed();
+    LANE.with(|lane| {
+        let lane = lane.get_or_init(|| {
+            let current = thread::current();
+            let thread = current.name().map(String::from).unwrap_or_else(|| format!("{:?}", current.id()));
+            let mut lanes = lock(&LANES);
+            let lane = Arc::new(Lane { thread, index: lanes.len(), records: Mutex::new(Vec::new()) });
+            lanes.push(Arc::clone(&lane));
+            lane
+        });
+        lock(&lane.records).push(Record { kind, label, at });
+    });
+}
+
+/// Records that something happened now, in the current thread.
+pub fn event(label: impl Into<String>) {
+    record(Kind::Instant, label.into());
+}
+
+/// Records the start of a span; its end is recorded when the returned guard is dropped.
+pub fn span(label: impl Into<String>) -> Span {
+    let label = label.into();
+    record(Kind::Begin, label.clone());
+    Span { label }
+}
+
+/// A span in progress, see [`span`].
+#[derive(Debug)]
+#[must_use = "the span ends when this guard is dropped"]
+pub struct Span {
+    label: String,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        record(Kind::End, std::mem::take(&mut self.label));
+    }
+}
+
+/// Spawns a thread with this name, whose whole run is recorded as a span "run".
+///
+/// Panics if the thread cannot be created, like `thread::spawn`.
+pub fn spawn<F, T>(name: &str, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            let _run = span("run");
+            f()
+        })
+        .expect("cannot create a thread")
+}
+
+/// Every event recorded until now, sorted by time.
+pub fn snapshot() -> Vec<Event> {
+    let lanes: Vec<Arc<Lane>> = lock(&LANES).clone();
+    let mut events: Vec<Event> = lanes
+        .iter()
+        .flat_map(|lane| {
+            lock(&lane.records)
+                .iter()
+                .map(|record| Event { thread: lane.thread.clone(), lane: lane.index, kind: record.kind, label: record.label.clone(), at: record.at })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    // stable: the events of a thread keep their order when two timestamps are equal
+    events.sort_by_key(|event| event.at);
+    events
+}
+
+// The lanes present in the events, in lane order: (index, thread name).
+fn lanes_of(events: &[Event]) -> Vec<(usize, &str)> {
+    let mut lanes: Vec<(usize, &str)> = events.iter().map(|event| (event.lane, event.thread.as_str())).collect();
+    lanes.sort();
+    lanes.dedup();
+    lanes
+}
+
+/// The spans begun and not ended in these events: (thread, label).
+pub fn unfinished_spans(events: &[Event]) -> Vec<(String, String)> {
+    let mut open: Vec<(usize, &Event)> = Vec::new();
+    for event in events {
+        match event.kind {
+            Kind::Begin => open.push((event.lane, event)),
+            Kind::End => {
+                // the last span begun with this label in this thread
+                if let Some(position) = open.iter().rposition(|(lane, begin)| *lane == event.lane && begin.label == event.label) {
+                    open.remove(position);
+                }
+            }
+            Kind::Instant => {}
+        }
+    }
+    open.into_iter().map(|(_, begin)| (begin.thread.clone(), begin.label.clone())).collect()
+}
+
+/// Renders the events as swim lanes: time goes down, one column per thread.
+/// `>` marks the start of a span, `<` its end.
+///
+/// ```text
+///         time | main               | spawned            |
+///       0.00ms | > join             |                    |
+///       0.05ms |                    | > run              |
+/// ```
+pub fn render_timeline(events: &[Event], column_width: usize) -> String {
+    let lanes = lanes_of(events);
+    let width = column_width.max(4);
+    let cell = |text: &str| {
+        // truncated to the column, counting characters (not bytes)
+        let text: String = if text.chars().count() > width { text.chars().take(width - 1).chain(['~']).collect() } else { text.to_string() };
+        format!(" {:<width$} |", text, width = width)
+    };
+
+    let mut out = String::new();
+    out.push_str(&format!("{:>12} |", "time"));
+    for (_, thread) in &lanes {
+        out.push_str(&cell(thread));
+    }
+    out.push('\n');
+    out.push_str(&format!("{}+", "-".repeat(13)));
+    out.push_str(&format!("{}+", "-".repeat(width + 2)).repeat(lanes.len()));
+    out.push('\n');
+
+    for event in events {
+        out.push_str(&format!("{:>10.2}ms |", event.at.as_secs_f64() * 1000.0));
+        for (lane, _) in &lanes {
+            let text = if *lane == event.lane {
+                match event.kind {
+                    Kind::Instant => event.label.clone(),
+                    Kind::Begin => format!("> {}", event.label),
+                    Kind::End => format!("< {}", event.label),
+                }
+            } else {
+                String::new()
+            };
+            out.push_str(&cell(&text));
+        }
+        out.push('\n');
+    }
+
+    for (thread, label) in unfinished_spans(events) {
+        out.push_str(&format!("unfinished: '{}' in thread '{}'\n", label, thread));
+    }
+    out
+}
+
+/// The events in the Chrome trace event format (JSON), to open in chrome://tracing or https://ui.perfetto.dev.
+pub fn chrome_trace_json(events: &[Event]) -> String {
+    let mut entries: Vec<String> = lanes_of(events)
+        .into_iter()
+        .map(|(lane, thread)| {
+            format!(r#"{{"name":"thread_name","ph":"M","pid":1,"tid":{},"args":{{"name":{}}}}}"#, lane, json_string(thread))
+        })
+        .collect();
+    for event in events {
+        let phase = match event.kind {
+            Kind::Instant => "i",
+            Kind::Begin => "B",
+            Kind::End => "E",
+        };
+        // timestamps in microseconds; "s":"t": an instant event belongs to its thread
+        let scope = if event.kind == Kind::Instant { r#","s":"t""# } else { "" };
+        entries.push(format!(
+            r#"{{"name":{},"ph":"{}","ts":{:.3},"pid":1,"tid":{}{}}}"#,
+            json_string(&event.label),
+            phase,
+            event.at.as_secs_f64() * 1_000_000.0,
+            event.lane,
+            scope
+        ));
+    }
+    format!("{{\"traceEvents\":[\n{}\n]}}\n", entries.join(",\n"))
+}
+
+/// Writes [`chrome_trace_json`] of the events to a file.
+pub fn write_chrome_trace(events: &[Event], path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, chrome_trace_json(events))
+}
+
+/// Prints the timeline of the events and, with `--chrome <file>` in the command line `args`, saves them
+/// with [`write_chrome_trace`]. `args` starts with the program, like `std::env::args()`.
+pub fn show(events: &[Event], args: impl IntoIterator<Item = String>) {
+    println!("\n{}", render_timeline(events, 20));
+    let args: Vec<String> = args.into_iter().collect();
+    if let Some(position) = args.iter().position(|arg| arg == "--chrome") {
+        let Some(path) = args.get(position + 1) else {
+            eprintln!("usage: {} [--chrome <file>]", args[0]);
+            return;
+        };
+        match write_chrome_trace(events, path) {
+            Ok(()) => println!("Chrome trace saved in {}", path),
+            Err(error) => eprintln!("cannot save the trace in {}: {}", path, error),
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
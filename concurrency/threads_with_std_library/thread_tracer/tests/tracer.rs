@@ -0,0 +1,113 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use thread_tracer::{Event, Kind};
+
+fn of_thread(events: &[Event], thread: &str) -> Vec<(Kind, String)> {
+    events.iter().filter(|event| event.thread == thread).map(|event| (event.kind, event.label.clone())).collect()
+}
+
+fn event(thread: &str, lane: usize, kind: Kind, label: &str, micros: u64) -> Event {
+    Event { thread: thread.to_string(), lane, kind, label: label.to_string(), at: Duration::from_micros(micros) }
+}
+
+#[test]
+fn threads_record_into_their_own_lane() {
+    let handle = thread_tracer::spawn("tracer-test-worker", || {
+        thread_tracer::event("one");
+        let _span = thread_tracer::span("work");
+        thread_tracer::event("two");
+    });
+    handle.join().unwrap();
+
+    let events = thread_tracer::snapshot();
+    assert!(events.windows(2).all(|pair| pair[0].at <= pair[1].at));
+    assert_eq!(
+        of_thread(&events, "tracer-test-worker"),
+        vec![
+            (Kind::Begin, String::from("run")),
+            (Kind::Instant, String::from("one")),
+            (Kind::Begin, String::from("work")),
+            (Kind::Instant, String::from("two")),
+            (Kind::End, String::from("work")),
+            (Kind::End, String::from("run")),
+        ]
+    );
+}
+
+#[test]
+fn a_thread_still_running_has_an_unfinished_run_span() {
+    let (sender, receiver) = mpsc::channel::<()>();
+    let (started, is_started) = mpsc::channel();
+    let handle = thread_tracer::spawn("tracer-test-blocked", move || {
+        started.send(()).unwrap();
+        // waits until the test lets it go
+        receiver.recv().unwrap();
+    });
+    is_started.recv().unwrap();
+
+    let unfinished = thread_tracer::unfinished_spans(&thread_tracer::snapshot());
+    assert!(unfinished.contains(&(String::from("tracer-test-blocked"), String::from("run"))));
+
+    sender.send(()).unwrap();
+    handle.join().unwrap();
+    let unfinished = thread_tracer::unfinished_spans(&thread_tracer::snapshot());
+    assert!(!unfinished.iter().any(|(thread, _)| thread == "tracer-test-blocked"));
+}
+
+#[test]
+fn timeline_has_one_column_per_thread() {
+    let events = vec![
+        event("main", 0, Kind::Instant, "spawn", 0),
+        event("spawned", 1, Kind::Begin, "run", 50),
+        event("main", 0, Kind::Begin, "join", 1_000),
+        event("spawned", 1, Kind::Instant, "a very long message", 1_500),
+        event("spawned", 1, Kind::End, "run", 2_000),
+        event("main", 0, Kind::End, "join", 2_010),
+        event("main", 0, Kind::Begin, "exit", 2_020),
+    ];
+    let expected = "        time | main     | spawned  |
+-------------+----------+----------+
+      0.00ms | spawn    |          |
+      0.05ms |          | > run    |
+      1.00ms | > join   |          |
+      1.50ms |          | a very ~ |
+      2.00ms |          | < run    |
+      2.01ms | < join   |          |
+      2.02ms | > exit   |          |
+unfinished: 'exit' in thread 'main'
+";
+    assert_eq!(thread_tracer::render_timeline(&events, 8), expected);
+}
+
+#[test]
+fn chrome_trace_format() {
+    let events = vec![event("main", 0, Kind::Instant, "say \"hi\"", 1_500), event("worker", 1, Kind::Begin, "run", 2_000)];
+    let json = thread_tracer::chrome_trace_json(&events);
+    let expected = r#"{"traceEvents":[
+{"name":"thread_name","ph":"M","pid":1,"tid":0,"args":{"name":"main"}},
+{"name":"thread_name","ph":"M","pid":1,"tid":1,"args":{"name":"worker"}},
+{"name":"say \"hi\"","ph":"i","ts":1500.000,"pid":1,"tid":0,"s":"t"},
+{"name":"run","ph":"B","ts":2000.000,"pid":1,"tid":1}
+]}
+"#;
+    assert_eq!(json, expected);
+
+    let path = std::env::temp_dir().join(format!("thread_tracer-{}.json", std::process::id()));
+    thread_tracer::write_chrome_trace(&events, &path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn show_saves_the_trace_with_the_chrome_option() {
+    let events = vec![event("main", 0, Kind::Instant, "main exits", 1_000)];
+    let path = std::env::temp_dir().join(format!("thread_tracer-show-{}.json", std::process::id()));
+    let args = ["program", "--chrome", path.to_str().unwrap()].map(String::from);
+    thread_tracer::show(&events, args);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), thread_tracer::chrome_trace_json(&events));
+    std::fs::remove_file(&path).unwrap();
+
+    // without a file after --chrome: only the timeline, and a usage message
+    thread_tracer::show(&events, ["program", "--chrome"].map(String::from));
+}
@@ -13,7 +13,7 @@ pub fn run(){
     // Example: 
     {
         let number: [i32; 5] = [1,2,3,4,5];    // Array of 5 integers
-        println!("{:?}", number);
+        outln!("{:?}", number);
         // NOTE: the following wont work
         // let not_allowed_array = [];
         // The reson is that we need to knwow the size 
@@ -36,26 +36,26 @@ pub fn run(){
         // or more formally a repeat expression [T; N]
         // where T is the type of the elements, and N is the size of the array.
 
-        let mut array_numbers = [3;5];
+        let array_numbers = [3;5];
         // the array will have 5 elements with value 3
 
         // accessing a single value
-        println!("Printing a single value {}", array_numbers[0]);
+        outln!("Printing a single value {}", array_numbers[0]);
     }
     
     {   // Reassigning values:
         let mut numbers: [i32; 5] = [ 1,2,3,4,5 ];
-        println!("array_numbers: {:?}", numbers);
+        outln!("array_numbers: {:?}", numbers);
         numbers[2] = 20;
-        println!("array_numbers after change: {:?}", numbers);
+        outln!("array_numbers after change: {:?}", numbers);
 
         // get array length 
-        println!("Array length: {}", numbers.len());
+        outln!("Array length: {}", numbers.len());
 
         // arrays are stack allocated 
-        println!("Array occupies {} bytes", std::mem::size_of_val(&numbers)); 
+        outln!("Array occupies {} bytes", std::mem::size_of_val(&numbers)); 
         let new_numbers: [i32; 3] = [1,2,3];
-        println!("Array occupies {} bytes", std::mem::size_of_val(&new_numbers));
+        outln!("Array occupies {} bytes", std::mem::size_of_val(&new_numbers));
     }
 
     // arrays are more useful when you know the number of elements will not need to change.
@@ -72,17 +72,24 @@ pub fn run(){
         // rather than the whole collection
         // the size for values of type `str` cannot be known at compilation time
         
-        let mut numbers: [i32; 5] = [ 1,2,3,4,5 ];
+        let numbers: [i32; 5] = [ 1,2,3,4,5 ];
         // get slice of an array
         let slice1: &[i32] = &numbers[0..2];
-        println!("Slice1: {:?} ", slice1);
+        outln!("Slice1: {:?} ", slice1);
         // the slice1 is a simple reference on the stack 
         // that points to the element 0 and has lenght of 2 , definied by the range 0..2
 
         // get another slice , this is another reference (pointer created on the stack)
         let slice2: &[i32] = &numbers[0..3];
-        println!("Slice2: {:?} ", slice2);
+        outln!("Slice2: {:?} ", slice2);
     }
 
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "arrays",
+    title: "Arrays: fixed-size collections of one type",
+    tags: &["basics", "collections"],
+    run,
+};
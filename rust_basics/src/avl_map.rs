@@ -0,0 +1,709 @@
+// An ordered map on a self-balancing binary search tree (AVL tree)
+//
+// The Box lessons stop at linear lists, but Option<Box<Node>> works just as well with two children:
+//
+//      struct Node { key, value, left: Option<Box<Node>>, right: Option<Box<Node>> }
+//
+// Binary search tree: for every node, the keys on its left are smaller and the keys on its right are greater,
+// so a key is found by going down from the root, left or right, one comparison per level.
+// The catch is that inserting already sorted keys (1, 2, 3, ...) builds a tree that is a linked list,
+// and every operation becomes O(n).
+//
+// AVL tree: every node also stores its height, and after every insertion/removal the nodes on the way back
+// up are "rotated" so the heights of the two children of any node never differ by more than 1.
+// That keeps the height of the tree under ~1.44 * log2(n), so insert, remove and get are O(log n).
+//
+// Rotations only move Boxes around (the node itself stays where it is on the heap), e.g. rotate right:
+//
+//          y                x
+//         / \              / \
+//        x   C    ==>     A   y
+//       / \                  / \
+//      A   B                B   C
+//
+// Every node also stores the size of its subtree, which gives the position (rank) of a key in the map.
+// The entry API uses it to find a value again after an insertion has rebalanced the tree.
+//
+// `validate()` walks the whole tree and checks every invariant (order, heights, balance, sizes),
+// the tests call it after each operation.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+type Tree<K, V> = Option<Box<Node<K, V>>>;
+
+#[derive(Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // height of the subtree starting at this node (a leaf has height 1)
+    height: usize,
+    // number of nodes in the subtree starting at this node
+    size: usize,
+    left: Tree<K, V>,
+    right: Tree<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    fn leaf(key: K, value: V) -> Box<Self> {
+        Box::new(Node { key, value, height: 1, size: 1, left: None, right: None })
+    }
+
+    // Recomputes height and size from the children, after one of them changed.
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+
+    // > 0 when the left side is higher, < 0 when the right side is higher
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+}
+
+fn height<K, V>(tree: &Tree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.height)
+}
+
+fn size<K, V>(tree: &Tree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.size)
+}
+
+fn rotate_right<K, V>(mut y: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut x = y.left.take().expect("rotate_right needs a left child");
+    y.left = x.right.take();
+    y.update();
+    x.right = Some(y);
+    x.update();
+    x
+}
+
+fn rotate_left<K, V>(mut x: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut y = x.right.take().expect("rotate_left needs a right child");
+    x.right = y.left.take();
+    x.update();
+    y.left = Some(x);
+    y.update();
+    y
+}
+
+// Restores the AVL property of a node whose children are balanced but may differ in height by 2.
+fn rebalance<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    node.update();
+    let balance = node.balance_factor();
+    if balance > 1 {
+        // left-right case: turn it into a left-left case first
+        if node.left.as_ref().is_some_and(|left| left.balance_factor() < 0) {
+            node.left = node.left.take().map(rotate_left);
+        }
+        rotate_right(node)
+    } else if balance < -1 {
+        // right-left case: turn it into a right-right case first
+        if node.right.as_ref().is_some_and(|right| right.balance_factor() > 0) {
+            node.right = node.right.take().map(rotate_right);
+        }
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+// Inserts (or replaces) the key in the subtree and returns the previous value.
+fn insert<K: Ord, V>(tree: &mut Tree<K, V>, key: K, value: V) -> Option<V> {
+    let Some(mut node) = tree.take() else {
+        *tree = Some(Node::leaf(key, value));
+        return None;
+    };
+    let previous = match key.cmp(&node.key) {
+        Ordering::Less => insert(&mut node.left, key, value),
+        Ordering::Greater => insert(&mut node.right, key, value),
+        Ordering::Equal => Some(mem::replace(&mut node.value, value)),
+    };
+    *tree = Some(rebalance(node));
+    previous
+}
+
+// Removes the smallest node of a (non empty) subtree and returns it, detached.
+fn remove_min<K, V>(tree: &mut Tree<K, V>) -> Box<Node<K, V>> {
+    let mut node = tree.take().expect("remove_min needs a non empty tree");
+    if node.left.is_some() {
+        let min = remove_min(&mut node.left);
+        *tree = Some(rebalance(node));
+        min
+    } else {
+        *tree = node.right.take();
+        node
+    }
+}
+
+// Removes the node selected by `target` and returns its key and value.
+// `target` is called once on every node of the path, it says in which direction the node to remove is.
+fn remove_by<K, V, F>(tree: &mut Tree<K, V>, target: &mut F) -> Option<(K, V)>
+where
+    F: FnMut(&Node<K, V>) -> Ordering,
+{
+    let mut node = tree.take()?;
+    let removed = match target(&node) {
+        Ordering::Less => remove_by(&mut node.left, target),
+        Ordering::Greater => remove_by(&mut node.right, target),
+        Ordering::Equal => {
+            let Node { key, value, left, right, .. } = *node;
+            *tree = match (left, right) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    // the successor (smallest key on the right) takes the place of the removed node
+                    let mut right = Some(right);
+                    let mut successor = remove_min(&mut right);
+                    successor.left = Some(left);
+                    successor.right = right;
+                    Some(rebalance(successor))
+                }
+            };
+            return Some((key, value));
+        }
+    };
+    *tree = Some(rebalance(node));
+    removed
+}
+
+// Selects the node by key, for get/remove.
+fn by_key<K, V, Q>(key: &Q) -> impl FnMut(&Node<K, V>) -> Ordering + '_
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    move |node| key.cmp(node.key.borrow())
+}
+
+// Selects the node by its position in the map (0 is the smallest key).
+fn by_rank<K, V>(mut rank: usize) -> impl FnMut(&Node<K, V>) -> Ordering {
+    move |node| {
+        let left = size(&node.left);
+        match rank.cmp(&left) {
+            Ordering::Greater => {
+                // the nodes on the left and this node come before the target
+                rank -= left + 1;
+                Ordering::Greater
+            }
+            other => other,
+        }
+    }
+}
+
+fn find_by<K, V, F>(tree: &Tree<K, V>, mut target: F) -> Option<&Node<K, V>>
+where
+    F: FnMut(&Node<K, V>) -> Ordering,
+{
+    let mut current = tree.as_deref();
+    while let Some(node) = current {
+        current = match target(node) {
+            Ordering::Less => node.left.as_deref(),
+            Ordering::Greater => node.right.as_deref(),
+            Ordering::Equal => return Some(node),
+        };
+    }
+    None
+}
+
+fn find_mut_by<K, V, F>(tree: &mut Tree<K, V>, mut target: F) -> Option<&mut Node<K, V>>
+where
+    F: FnMut(&Node<K, V>) -> Ordering,
+{
+    let mut current = tree.as_deref_mut();
+    while let Some(node) = current {
+        current = match target(node) {
+            Ordering::Less => node.left.as_deref_mut(),
+            Ordering::Greater => node.right.as_deref_mut(),
+            Ordering::Equal => return Some(node),
+        };
+    }
+    None
+}
+
+/// An ordered map on an AVL tree.
+#[derive(Clone)]
+pub struct AvlMap<K, V> {
+    root: Tree<K, V>,
+}
+
+impl<K, V> AvlMap<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        AvlMap { root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Height of the tree (0 when empty).
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    /// In-order iterator: the entries sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new(), len: self.len() };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+
+    /// Pre-order iterator: a node first, then its left subtree, then its right subtree.
+    /// Inserting the entries in this order into an empty map builds the same tree.
+    pub fn pre_order(&self) -> PreOrder<'_, K, V> {
+        PreOrder { stack: self.root.as_deref().into_iter().collect(), len: self.len() }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K: Ord, V> AvlMap<K, V> {
+    /// Inserts a key/value pair and returns the previous value of the key, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        insert(&mut self.root, key, value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        find_by(&self.root, by_key(key)).map(|node| &node.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        find_by(&self.root, by_key(key)).map(|node| (&node.key, &node.value))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        find_mut_by(&mut self.root, by_key(key)).map(|node| &mut node.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Removes a key and returns its value, if it was in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove_by(&mut self.root, &mut by_key(key))
+    }
+
+    /// Iterator over the entries whose key is in `range`, sorted by key.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let after_start = |key: &K| match range.start_bound() {
+            Bound::Included(start) => key.borrow() >= start,
+            Bound::Excluded(start) => key.borrow() > start,
+            Bound::Unbounded => true,
+        };
+        let before_end = |key: &K| match range.end_bound() {
+            Bound::Included(end) => key.borrow() <= end,
+            Bound::Excluded(end) => key.borrow() < end,
+            Bound::Unbounded => true,
+        };
+
+        // The stack gets the path to the first key >= start, like Iter gets the path to the smallest key.
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            if after_start(&node.key) {
+                stack.push(node);
+                current = node.left.as_deref();
+            } else {
+                current = node.right.as_deref();
+            }
+        }
+
+        // The last key <= end, the iteration stops after it.
+        let mut last = None;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            if before_end(&node.key) {
+                last = Some(&node.key);
+                current = node.right.as_deref();
+            } else {
+                current = node.left.as_deref();
+            }
+        }
+
+        Range { iter: Iter { stack, len: 0 }, last }
+    }
+
+    /// Gets the entry of a key, to read, insert or update it in place.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        // Going down the tree also counts the keys smaller than 'key', its rank:
+        // the entry finds the node again by rank, even after an insertion has rotated the tree.
+        let mut rank = 0;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            match key.cmp(&node.key) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    current = node.right.as_deref();
+                }
+                Ordering::Equal => {
+                    rank += size(&node.left);
+                    return Entry::Occupied(OccupiedEntry { map: self, rank });
+                }
+            }
+        }
+        Entry::Vacant(VacantEntry { map: self, key, rank })
+    }
+
+    /// Checks every invariant of the tree and describes the first one that is broken:
+    /// the keys are sorted, heights and sizes are correct, and no node is unbalanced.
+    pub fn validate(&self) -> Result<(), String>
+    where
+        K: fmt::Debug,
+    {
+        fn check<'a, K: Ord + fmt::Debug, V>(
+            tree: &'a Tree<K, V>,
+            lower: Option<&'a K>,
+            upper: Option<&'a K>,
+        ) -> Result<(), String> {
+            let Some(node) = tree else {
+                return Ok(());
+            };
+            if lower.is_some_and(|lower| node.key <= *lower) || upper.is_some_and(|upper| node.key >= *upper) {
+                return Err(format!("key {:?} is out of order (between {:?} and {:?})", node.key, lower, upper));
+            }
+            check(&node.left, lower, Some(&node.key))?;
+            check(&node.right, Some(&node.key), upper)?;
+
+            let expected_height = 1 + height(&node.left).max(height(&node.right));
+            if node.height != expected_height {
+                return Err(format!("node {:?} has height {}, expected {}", node.key, node.height, expected_height));
+            }
+            let expected_size = 1 + size(&node.left) + size(&node.right);
+            if node.size != expected_size {
+                return Err(format!("node {:?} has size {}, expected {}", node.key, node.size, expected_size));
+            }
+            if node.balance_factor().abs() > 1 {
+                return Err(format!("node {:?} is unbalanced (balance factor {})", node.key, node.balance_factor()));
+            }
+            Ok(())
+        }
+        check(&self.root, None, None)
+    }
+}
+
+impl<K, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        AvlMap::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for AvlMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for AvlMap<K, V> {
+    // Two maps are equal when they hold the same entries, whatever the shape of their trees.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for AvlMap<K, V> {}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = AvlMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for AvlMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a AvlMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ------------------------------------------------
+// Entry API
+// ------------------------------------------------
+
+/// A view into a single entry of the map, returned by [`AvlMap::entry`].
+pub enum Entry<'a, K: Ord, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// An entry whose key is in the map.
+pub struct OccupiedEntry<'a, K: Ord, V> {
+    map: &'a mut AvlMap<K, V>,
+    rank: usize,
+}
+
+/// An entry whose key is not in the map yet.
+pub struct VacantEntry<'a, K: Ord, V> {
+    map: &'a mut AvlMap<K, V>,
+    key: K,
+    rank: usize,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// Inserts `default` if the key is not in the map, and returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Updates the value if the key is in the map.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+    fn node(&self) -> &Node<K, V> {
+        find_by(&self.map.root, by_rank(self.rank)).expect("an occupied entry points at a node")
+    }
+
+    fn node_mut(&mut self) -> &mut Node<K, V> {
+        find_mut_by(&mut self.map.root, by_rank(self.rank)).expect("an occupied entry points at a node")
+    }
+
+    pub fn key(&self) -> &K {
+        &self.node().key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.node().value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.node_mut().value
+    }
+
+    /// Converts the entry into a mutable reference to the value, that lives as long as the map borrow.
+    pub fn into_mut(self) -> &'a mut V {
+        let node = find_mut_by(&mut self.map.root, by_rank(self.rank));
+        &mut node.expect("an occupied entry points at a node").value
+    }
+
+    /// Replaces the value and returns the previous one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map.
+    pub fn remove_entry(self) -> (K, V) {
+        remove_by(&mut self.map.root, &mut by_rank(self.rank)).expect("an occupied entry points at a node")
+    }
+
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the value and returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        insert(&mut self.map.root, self.key, value);
+        // the new key has 'rank' smaller keys, wherever the rotations moved it
+        let node = find_mut_by(&mut self.map.root, by_rank(self.rank));
+        &mut node.expect("the inserted key is in the map").value
+    }
+}
+
+// ------------------------------------------------
+// Iterators
+// ------------------------------------------------
+
+/// In-order iterator returned by [`AvlMap::iter`].
+pub struct Iter<'a, K, V> {
+    // the nodes still to visit, the next one on top; their right subtrees are not on the stack yet
+    stack: Vec<&'a Node<K, V>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left_spine(&mut self, mut current: Option<&'a Node<K, V>>) {
+        while let Some(node) = current {
+            self.stack.push(node);
+            current = node.left.as_deref();
+        }
+    }
+
+    fn next_node(&mut self) -> Option<&'a Node<K, V>> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some(node)
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next_node()?;
+        self.len -= 1;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// Pre-order iterator returned by [`AvlMap::pre_order`].
+pub struct PreOrder<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for PreOrder<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        // right pushed first, so the left subtree is visited first
+        self.stack.extend(node.right.as_deref());
+        self.stack.extend(node.left.as_deref());
+        self.len -= 1;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for PreOrder<'_, K, V> {}
+impl<K, V> FusedIterator for PreOrder<'_, K, V> {}
+
+/// Iterator returned by [`AvlMap::range`].
+pub struct Range<'a, K, V> {
+    iter: Iter<'a, K, V>,
+    // the last key of the range, None if the range is empty
+    last: Option<&'a K>,
+}
+
+impl<'a, K: Ord, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.last?;
+        let node = self.iter.next_node()?;
+        if node.key > *last {
+            // the start of the range is after its end
+            self.last = None;
+            return None;
+        }
+        if std::ptr::eq(&node.key, last) {
+            self.last = None;
+        }
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K: Ord, V> FusedIterator for Range<'_, K, V> {}
@@ -27,7 +27,7 @@ pub fn run() {
     {
         let name = "John";
         // note that, variable name is captured by reference here
-        let greeting_closure = || println!("Hello {:?}", name);
+        let greeting_closure = || outln!("Hello {:?}", name);
         greeting_closure();
     }
 
@@ -38,7 +38,7 @@ pub fn run() {
         // Captures x by mutable reference
         let mut change_x = || x += 1;
         change_x();
-        println!("x is: {}", x); // prints: x is: 6
+        outln!("x is: {}", x); // prints: x is: 6
     }
 
     // Example 3: We can use 'move' to force the closure to take ownership of captured variables
@@ -46,7 +46,7 @@ pub fn run() {
         let x = String::from("Hello World!");
 
         let consumer_closure = move || {
-            println!(
+            outln!(
                 "The value [{:?}] is now ownded and consumed by the closure.",
                 x
             );
@@ -69,7 +69,7 @@ pub fn run() {
     {
         let outer_var: i32 = 42; // environment value
         let closure_annotated = |i: i32| -> i32 { i + outer_var };
-        println!("closure_annotated: {}", closure_annotated(1));
+        outln!("closure_annotated: {}", closure_annotated(1));
     }
 
     // -----------------------------------
@@ -88,9 +88,9 @@ pub fn run() {
         let add_one_v1 = |x| x + 1; // here we define our closure
         let x = 3; // the value that will be capture by the closure
 
-        println!("closure returns: {:?} ", add_one_v1(x)); // involking the closure that captures 'x'
-        println!("x is: {:?} ", x);
-        println!("closure returns again: {:?} ", add_one_v1(x));
+        outln!("closure returns: {:?} ", add_one_v1(x)); // involking the closure that captures 'x'
+        outln!("x is: {:?} ", x);
+        outln!("closure returns again: {:?} ", add_one_v1(x));
         // involking the closure again to show that the parameter is passed by reference
     }
 
@@ -183,7 +183,7 @@ pub fn run() {
 
         // lets create our closure
         let greeting = || {
-            println!("Hello {}", name);
+            outln!("Hello {}", name);
         }; // Closure that implements Fn
            // variable 'name' is capture from the scope by reference
 
@@ -204,7 +204,7 @@ pub fn run() {
         let mut counter = 0;
         let increment = || {
             counter += 1; // we increment the captured variable
-            println!("Counter: {}", counter);
+            outln!("Counter: {}", counter);
         };
 
         call_twice_mutable(increment);
@@ -231,7 +231,7 @@ pub fn run() {
 
         // define our closure
         let consumer_closure = move || {
-            println!("{:?}", message); // this will consume the captured variable
+            outln!("{:?}", message); // this will consume the captured variable
         };
 
         // invoking the function and passing the closure into it
@@ -280,3 +280,10 @@ pub fn run() {
     // Trait object (boxed)    Box<dyn Fn...>           Need dynamic dispatch, heterogeneous returns
     // ----------------------------------------------------------------------------------------------
 }
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "closures",
+    title: "Closures and how they capture their environment",
+    tags: &["functions"],
+    run,
+};
@@ -1,6 +1,5 @@
 // Conditionals are used to check the 'condition' of somenthing and act on the result.. 
 #[allow(dead_code)]
-
 pub fn run(){
 
 
@@ -9,37 +8,44 @@ pub fn run(){
 
     // IF ELSE:
     if age >= 21 {
-        println!("Batender1 asks : what you'd like to drink?");
+        outln!("Batender1 asks : what you'd like to drink?");
     } else{
-        println!("Batender1 says: sorry! you have to leave!!!")
+        outln!("Batender1 says: sorry! you have to leave!!!")
     }
 
     // IF ELSE with more variables
     if age >= 21 && check_id {
-        println!("Batender2 asks: what you'd like to drink?");
+        outln!("Batender2 asks: what you'd like to drink?");
     } else if age < 21 && check_id{
-        println!("Batender2 says: sorry! you have to leave!!!")
+        outln!("Batender2 says: sorry! you have to leave!!!")
     } else{
-        println!("Batender2 says: sorry! you need to show your ID!!!")
+        outln!("Batender2 says: sorry! you need to show your ID!!!")
     }
 
     let is_known_major = false;
     if age >= 21 && check_id || is_known_major {
-        println!("Batender3 asks: what you'd like to drink?");
+        outln!("Batender3 asks: what you'd like to drink?");
     } else if age < 21 && check_id{
-        println!("Batender3 says: sorry! you have to leave!!!")
+        outln!("Batender3 says: sorry! you have to leave!!!")
     } else{
-        println!("Batender3 says: sorry! you need to show your ID!!!")
+        outln!("Batender3 says: sorry! you need to show your ID!!!")
     }
 
 
     // There is no ternary operator in RUST, such as in other programming languages. 
     // There is a shorthand if, though:
     let is_of_age = if age >= 21 {true} else {false};   
-    println!("Is of Age: {} ", is_of_age);
+    outln!("Is of Age: {} ", is_of_age);
+
 
 
 
 
+}
 
-}
\ No newline at end of file
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "conditionals",
+    title: "if / else conditionals",
+    tags: &["basics", "control-flow"],
+    run,
+};
@@ -0,0 +1,274 @@
+// A doubly linked list using Rc<RefCell<..>> and Weak back-pointers
+//
+// 'smart_pointers_rc.rs' shows Rc<T> (multiple owners) and 'smart_pointers_refcell.rs' shows
+// RefCell<T> (mutation through a shared reference). A doubly linked list needs both:
+// - every node is pointed at by two other places (the previous node and the next node),
+//   so it cannot be owned by a single Box: we use Rc<T> to share it.
+// - we need to update the links of a node while other nodes also point at it,
+//   so the node lives in a RefCell<T> and we borrow it mutably at runtime.
+//
+//              next (Rc)           next (Rc)
+//      head -> [A] -----------> [B] -----------> [C] <- tail
+//                 <- - - - - - -    <- - - - - - -
+//               prev (Weak)        prev (Weak)
+//
+// NOTE: if both 'next' and 'prev' were Rc, two neighbours would keep each other alive forever
+// (a reference cycle): their strong count never reaches 0 and the memory is leaked.
+// A Weak pointer does not count as an owner, so the 'prev' links are Weak:
+// the nodes are only owned through the 'next' chain (and the tail), and dropping the list frees them all.
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+type WeakLink<T> = Option<Weak<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+    prev: WeakLink<T>,
+}
+
+impl<T> Node<T> {
+    fn new(elem: T) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Node { elem, next: None, prev: None }))
+    }
+}
+
+/// A doubly linked list with O(1) push/pop at both ends.
+pub struct DoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+impl<T> DoublyLinkedList<T> {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        DoublyLinkedList { head: None, tail: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Adds an element in the beginning of the list.
+    pub fn push_front(&mut self, elem: T) {
+        let new_head = Node::new(elem);
+        match self.head.take() {
+            Some(old_head) => {
+                old_head.borrow_mut().prev = Some(Rc::downgrade(&new_head));
+                new_head.borrow_mut().next = Some(old_head);
+                self.head = Some(new_head);
+            }
+            None => {
+                self.tail = Some(Rc::clone(&new_head));
+                self.head = Some(new_head);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Adds an element at the end of the list.
+    pub fn push_back(&mut self, elem: T) {
+        let new_tail = Node::new(elem);
+        match self.tail.take() {
+            Some(old_tail) => {
+                new_tail.borrow_mut().prev = Some(Rc::downgrade(&old_tail));
+                old_tail.borrow_mut().next = Some(Rc::clone(&new_tail));
+                self.tail = Some(new_tail);
+            }
+            None => {
+                self.head = Some(Rc::clone(&new_tail));
+                self.tail = Some(new_tail);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Removes the first element and returns it, or `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|old_head| {
+            match old_head.borrow_mut().next.take() {
+                Some(new_head) => {
+                    new_head.borrow_mut().prev = None;
+                    self.head = Some(new_head);
+                }
+                None => {
+                    self.tail = None;
+                }
+            }
+            self.len -= 1;
+            Self::into_elem(old_head)
+        })
+    }
+
+    /// Removes the last element and returns it, or `None` if the list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.take().map(|old_tail| {
+            let prev = old_tail.borrow_mut().prev.take().and_then(|prev| prev.upgrade());
+            match prev {
+                Some(new_tail) => {
+                    new_tail.borrow_mut().next = None;
+                    self.tail = Some(new_tail);
+                }
+                None => {
+                    self.head = None;
+                }
+            }
+            self.len -= 1;
+            Self::into_elem(old_tail)
+        })
+    }
+
+    /// Borrows the first element. The list cannot be modified while the `Ref` is alive.
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
+        self.head.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    /// Borrows the last element.
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
+        self.tail.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    /// Mutably borrows the first element.
+    pub fn peek_front_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.head.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+    }
+
+    /// Mutably borrows the last element.
+    pub fn peek_back_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.tail.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+    }
+
+    /// Removes every element of the list.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    // Once a node is unlinked from the list, nothing else points at it anymore:
+    // its strong count is 1 and we can move the element out of the Rc and the RefCell.
+    fn into_elem(node: Rc<RefCell<Node<T>>>) -> T {
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().elem,
+            Err(_) => unreachable!("an unlinked node must not be shared"),
+        }
+    }
+}
+
+// The 'next' links are strong, so the default drop would drop the nodes recursively (one stack frame per node).
+// Popping them one by one frees the same nodes in a loop.
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        DoublyLinkedList::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            list.entry(&node.borrow().elem);
+            current = node.borrow().next.clone();
+        }
+        list.finish()
+    }
+}
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+/// Consuming iterator, from the front with `next()` and from the back with `next_back()`.
+pub struct IntoIter<T>(DoublyLinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+// The nodes are private: these tests look at their reference counts, which tests/ cannot reach.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Weak handle on every node, from the head to the tail.
+    fn weak_nodes<T>(list: &DoublyLinkedList<T>) -> Vec<Weak<RefCell<Node<T>>>> {
+        let mut nodes = Vec::new();
+        let mut current = list.head.clone();
+        while let Some(node) = current {
+            nodes.push(Rc::downgrade(&node));
+            current = node.borrow().next.clone();
+        }
+        nodes
+    }
+
+    #[test]
+    fn dropping_the_list_frees_every_node() {
+        let list: DoublyLinkedList<i32> = (0..10).collect();
+        let nodes = weak_nodes(&list);
+        let (head, tail) = (&nodes[0], &nodes[9]);
+
+        // the head is owned by the list, the other nodes by their previous node, and the tail by the list too
+        assert_eq!((head.strong_count(), tail.strong_count()), (1, 2));
+        assert!(nodes[1..9].iter().all(|node| node.strong_count() == 1));
+        // the 'prev' link of the next node, and our handle
+        assert_eq!(head.weak_count(), 2);
+        assert_eq!(tail.weak_count(), 1);
+
+        drop(list);
+        for node in &nodes {
+            assert_eq!(node.strong_count(), 0);
+            assert_eq!(node.weak_count(), 0);
+            assert!(node.upgrade().is_none());
+        }
+    }
+}
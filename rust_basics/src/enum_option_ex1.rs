@@ -34,20 +34,27 @@ pub fn run(){
     // it will run different code depending on which variant of the enum it has
 
     match some_number {
-        Some(inner_value) => println!("some_number is {:?}", inner_value),
-        None => println!("some_number: None value found"),
+        Some(inner_value) => outln!("some_number is {:?}", inner_value),
+        None => outln!("some_number: None value found"),
     }
     match some_char {
-        Some(inner_value) => println!("some_char is {:?}", inner_value),
-        None => println!("some_char: None value found"),
+        Some(inner_value) => outln!("some_char is {:?}", inner_value),
+        None => outln!("some_char: None value found"),
     }
     match absent_number {
-        Some(absent_value) => println!("absent_value is {:?}", absent_value),
-        None => println!("absent_number: is Option::None"),
+        Some(absent_value) => outln!("absent_value is {:?}", absent_value),
+        None => outln!("absent_number: is Option::None"),
 
     }
 
 
     
     
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "enum_option_ex1",
+    title: "Option<T>: encoding an absent value",
+    tags: &["enums", "option"],
+    run,
+};
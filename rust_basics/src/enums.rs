@@ -18,10 +18,10 @@ fn move_avatar(m: Movements){
     // perform action depending on info
     // in this case we use the match pattern to chose the action to take , in function of the enum value.
     match m {
-        Movements::Up =>  println!("Avatar moving UP"),
-        Movements::Down =>  println!("Avatar moving Down"),
-        Movements::Right =>  println!("Avatar moving Right"),
-        Movements::Left =>  println!("Avatar moving Left")
+        Movements::Up =>  outln!("Avatar moving UP"),
+        Movements::Down =>  outln!("Avatar moving Down"),
+        Movements::Right =>  outln!("Avatar moving Right"),
+        Movements::Left =>  outln!("Avatar moving Left")
     }
 }
 
@@ -38,7 +38,7 @@ enum MultiTypesElement {
 pub fn run(){
 
     //Example 1: 
-    println!("Example 1: ");
+    outln!("Example 1: ");
     let avatar1 = Movements::Left;
     let avatar2 = Movements::Up;
     let avatar3 = Movements::Right;
@@ -50,13 +50,20 @@ pub fn run(){
     move_avatar(avatar1);
 
     // Example 2: using enum to store different types in a vector
-    println!("Example 2: ");
+    outln!("Example 2: ");
     let multi_type_vect: Vec<MultiTypesElement> = vec![
         MultiTypesElement::Inteiro(5),
         MultiTypesElement::Floating(3.45),
         MultiTypesElement::Texto(String::from("Teste")),
     ];
 
-    println!("{:?}" , multi_type_vect);
+    outln!("{:?}" , multi_type_vect);
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "enums",
+    title: "Enums and their variants",
+    tags: &["enums"],
+    run,
+};
@@ -23,7 +23,7 @@ pub fn run(){
     // which can be used when our program fails in an unrecoverable fashion 
     // and can't handle the error gracefully. 
     {
-        println!("Example 1: UNRECOVERABLE ERRORS with panic! ");
+        outln!("Example 1: UNRECOVERABLE ERRORS with panic! ");
         // the macro will make the program panic and leave. 
         // uncomment the next line 
         // panic!("The program just panic here!");  // any code following this expression is unreachable
@@ -40,7 +40,7 @@ pub fn run(){
     // check how the error message changes if we run with "RUST_BACKTRACE=1 cargo run"
     // note that a more detail information will be given if the compiler does not let you to compile  
     {
-        println!("Example 2: UNRECOVERABLE ERRORS with Backtrace");
+        outln!("Example 2: UNRECOVERABLE ERRORS with Backtrace");
 
         a();
 
@@ -59,7 +59,7 @@ pub fn run(){
             if number == 10 { 
                 panic!("I panic with number=10!");
             }
-            println!("Example 2: I didnt panic!");
+            outln!("Example 2: I didnt panic!");
         }
     }
 
@@ -76,7 +76,7 @@ pub fn run(){
     // Functions return Result whenever errors are expected and recoverable. 
     // 
     {
-        println!("Example 3: RECOVERABLE ERRORS");
+        outln!("Example 3: RECOVERABLE ERRORS");
 
         let f = File::open("hello.txt");
         // NOTE: an error will happen because the file 'hello.txt' does not exist! 
@@ -101,7 +101,7 @@ pub fn run(){
     //              in order to do that we will use an enum called 'ErrorKind' from std::io
     // NOTE: the previous example must be commented in order to this one to run (due to the panic at line 93)
     {
-        println!("Example 3.1 : RECOVERABLE ERRORS: Matching on Different Errors");
+        outln!("Example 3.1 : RECOVERABLE ERRORS: Matching on Different Errors");
 
         let f = File::open("another_hello.txt");
         
@@ -122,7 +122,7 @@ pub fn run(){
                 }
             },
         };
-        println!("File f: {:?}", f);
+        outln!("File f: {:?}", f);
         
         
     }
@@ -135,7 +135,7 @@ pub fn run(){
     // In this example we are going to reduce the number of required 'match' statements.
     //
     {
-        println!("Example 3.2 : RECOVERABLE ERRORS: reducing the number of required 'match' with 'closures' and 'unwrap_or_else' method");
+        outln!("Example 3.2 : RECOVERABLE ERRORS: reducing the number of required 'match' with 'closures' and 'unwrap_or_else' method");
         // the unwrap_or_else method: Returns the contained 'Ok' value or computes it from a 'closure'
         // if it can't unwrap the Ok, the closure will receive the error and check the type of error.
         // inside the closure, if the error is an 'ErrorKind::NotFound' , we create the file. 
@@ -150,7 +150,7 @@ pub fn run(){
                 panic!("Problem opening file {:?} ", error);
             }
         });
-        println!("File myfile: {:?}", myfile);
+        outln!("File myfile: {:?}", myfile);
     }
 
 
@@ -178,7 +178,7 @@ pub fn run(){
         #[allow(dead_code)]
         fn read_word_from_file(filename: &str) -> Result<String, io::Error> {
 
-            println!("Example 4: PROPAGATING ERRORS");
+            outln!("Example 4: PROPAGATING ERRORS");
 
             let file_handler = File::open(filename);
             
@@ -212,7 +212,7 @@ pub fn run(){
         #[allow(unused_variables)]
         fn read_word_from_file(filename: &str) -> Result<String, io::Error> {
 
-            println!("Example 5: shortcut for PROPAGATING ERRORS with operator '?'");
+            outln!("Example 5: shortcut for PROPAGATING ERRORS with operator '?'");
             
             let mut read_word = String::new();
             let file_handler = File::open(filename)?.read_to_string(&mut read_word)?;
@@ -251,4 +251,11 @@ pub fn run(){
     // It’s understood that a call to a method like 'unwrap' (that could panic) is meant as a 'placeholder' for the way you’d want your application to handle errors.
     // The 'unwrap' and 'expect' methods are very handy when prototyping, before you’re ready to decide how to handle errors.
 
-}   
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "error_handling",
+    title: "Error handling with panic! and Result",
+    tags: &["errors"],
+    run,
+};
@@ -0,0 +1,363 @@
+// Fleet and facilities, shared between threads (Arc / Weak / Mutex)
+//
+// The same model as 'fleet_rc.rs', but the fleet can be shared between threads (Arc<Fleet>):
+// several dispatcher threads move trucks between facilities at the same time.
+//
+// - Truck: Arc<Truck>, immutable, so it needs no lock.
+// - Facility: its list of trucks is behind a Mutex, and it knows its region through a sync::Weak.
+// - Yard: trucks that are not assigned to any facility wait in the yard (also behind a Mutex).
+//
+// Every truck is always in exactly one place: the yard or one facility. A move takes the truck out of
+// one list and puts it into another while holding BOTH locks, so no other thread can see the truck
+// in two places (duplicated) or in none (lost).
+//
+// NOTE: Deadlocks. A thread moving a truck from A to B locks A then B, another thread moving from B to A
+// would lock B then A: each one can end up waiting for the lock held by the other, forever.
+// To avoid it the locks are always taken in the same order: the yard first, then the facilities by id.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::thread;
+
+pub use crate::fleet_rc::{FacilityId, FleetError, RegionId, Truck, TruckId};
+
+#[derive(Debug)]
+pub struct Region {
+    pub id: RegionId,
+    pub name: String,
+    facilities: Mutex<Vec<Arc<Facility>>>,
+}
+
+impl Region {
+    /// Ids of the facilities of this region.
+    pub fn facility_ids(&self) -> Vec<FacilityId> {
+        self.facilities.lock().unwrap().iter().map(|facility| facility.id).collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Facility {
+    pub id: FacilityId,
+    pub name: String,
+    region: Weak<Region>,
+    trucks: Mutex<Vec<Arc<Truck>>>,
+}
+
+impl Facility {
+    /// The region of the facility, `None` if the region does not exist anymore.
+    pub fn region(&self) -> Option<Arc<Region>> {
+        self.region.upgrade()
+    }
+
+    /// Ids of the trucks currently at the facility.
+    pub fn truck_ids(&self) -> Vec<TruckId> {
+        self.trucks.lock().unwrap().iter().map(|truck| truck.id).collect()
+    }
+
+    /// Sum of the capacities of the trucks currently at the facility.
+    pub fn total_capacity(&self) -> u32 {
+        self.trucks.lock().unwrap().iter().map(|truck| truck.capacity).sum()
+    }
+}
+
+/// The trucks, regions and facilities of a company. It is built with `&mut self` methods,
+/// then shared between threads in an `Arc`: moving trucks only needs `&self`.
+#[derive(Debug, Default)]
+pub struct Fleet {
+    trucks: BTreeMap<TruckId, Arc<Truck>>,
+    regions: BTreeMap<RegionId, Arc<Region>>,
+    facilities: BTreeMap<FacilityId, Arc<Facility>>,
+    yard: Mutex<Vec<Arc<Truck>>>,
+    next_id: u32,
+}
+
+// Removes a truck from a list of trucks, if it is there.
+fn take(trucks: &mut Vec<Arc<Truck>>, truck: TruckId) -> Option<Arc<Truck>> {
+    let index = trucks.iter().position(|assigned| assigned.id == truck)?;
+    Some(trucks.swap_remove(index))
+}
+
+impl Fleet {
+    pub fn new() -> Self {
+        Fleet::default()
+    }
+
+    fn next_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Adds a truck to the fleet, it starts in the yard.
+    pub fn add_truck(&mut self, capacity: u32) -> TruckId {
+        let id = self.next_id();
+        let truck = Arc::new(Truck { id, capacity });
+        self.yard.lock().unwrap().push(Arc::clone(&truck));
+        self.trucks.insert(id, truck);
+        id
+    }
+
+    pub fn add_region(&mut self, name: &str) -> RegionId {
+        let id = self.next_id();
+        let region = Region { id, name: name.to_string(), facilities: Mutex::new(Vec::new()) };
+        self.regions.insert(id, Arc::new(region));
+        id
+    }
+
+    pub fn add_facility(&mut self, region: RegionId, name: &str) -> Result<FacilityId, FleetError> {
+        if !self.regions.contains_key(&region) {
+            return Err(FleetError::UnknownRegion(region));
+        }
+        let id = self.next_id();
+        let region = &self.regions[&region];
+        let facility = Arc::new(Facility {
+            id,
+            name: name.to_string(),
+            region: Arc::downgrade(region),
+            trucks: Mutex::new(Vec::new()),
+        });
+        region.facilities.lock().unwrap().push(Arc::clone(&facility));
+        self.facilities.insert(id, facility);
+        Ok(id)
+    }
+
+    pub fn truck(&self, id: TruckId) -> Result<Arc<Truck>, FleetError> {
+        self.trucks.get(&id).cloned().ok_or(FleetError::UnknownTruck(id))
+    }
+
+    pub fn region(&self, id: RegionId) -> Result<Arc<Region>, FleetError> {
+        self.regions.get(&id).cloned().ok_or(FleetError::UnknownRegion(id))
+    }
+
+    pub fn facility(&self, id: FacilityId) -> Result<Arc<Facility>, FleetError> {
+        self.facilities.get(&id).cloned().ok_or(FleetError::UnknownFacility(id))
+    }
+
+    pub fn facility_ids(&self) -> Vec<FacilityId> {
+        self.facilities.keys().copied().collect()
+    }
+
+    /// Number of trucks in the fleet, assigned or not.
+    pub fn truck_count(&self) -> usize {
+        self.trucks.len()
+    }
+
+    /// Moves a truck from the yard to a facility. A truck already at a facility gives
+    /// `AlreadyAssigned` with that facility (use move_truck to change it).
+    pub fn assign(&self, truck: TruckId, facility: FacilityId) -> Result<(), FleetError> {
+        self.truck(truck)?;
+        let target = self.facility(facility)?;
+        let mut yard = self.yard.lock().unwrap();
+        match take(&mut yard, truck) {
+            Some(found) => {
+                target.trucks.lock().unwrap().push(found);
+                Ok(())
+            }
+            None => {
+                // Not in the yard, so at a facility. Trucks can still move between facilities, so they are
+                // all locked (in id order, after the yard) to find it, as snapshot() does.
+                let facilities = self.lock_all_facilities();
+                let current = facilities
+                    .iter()
+                    .find(|(_, trucks)| trucks.iter().any(|assigned| assigned.id == truck))
+                    .map(|(id, _)| *id)
+                    .expect("a truck is always in the yard or at a facility");
+                Err(FleetError::AlreadyAssigned { truck, facility: current })
+            }
+        }
+    }
+
+    /// Moves a truck from a facility back to the yard.
+    pub fn unassign(&self, truck: TruckId, facility: FacilityId) -> Result<(), FleetError> {
+        self.truck(truck)?;
+        let source = self.facility(facility)?;
+        let mut yard = self.yard.lock().unwrap();
+        let mut trucks = source.trucks.lock().unwrap();
+        let truck = take(&mut trucks, truck).ok_or(FleetError::NotAtFacility { truck, facility })?;
+        yard.push(truck);
+        Ok(())
+    }
+
+    /// Moves a truck from one facility to another one.
+    pub fn move_truck(&self, truck: TruckId, from: FacilityId, to: FacilityId) -> Result<(), FleetError> {
+        self.truck(truck)?;
+        let source = self.facility(from)?;
+        let target = self.facility(to)?;
+        if from == to {
+            // locking the same Mutex twice would block forever
+            let trucks = source.trucks.lock().unwrap();
+            return match trucks.iter().any(|assigned| assigned.id == truck) {
+                true => Ok(()),
+                false => Err(FleetError::NotAtFacility { truck, facility: from }),
+            };
+        }
+        let (mut source_trucks, mut target_trucks) = lock_in_order(&source, &target);
+        let truck = take(&mut source_trucks, truck).ok_or(FleetError::NotAtFacility { truck, facility: from })?;
+        target_trucks.push(truck);
+        Ok(())
+    }
+
+    /// Moves one truck chosen by `pick` (an index into the trucks of `from`) to another facility.
+    /// Returns the id of the moved truck, or `None` if `from` had no truck.
+    pub fn move_any(
+        &self,
+        from: FacilityId,
+        to: FacilityId,
+        pick: impl FnOnce(usize) -> usize,
+    ) -> Result<Option<TruckId>, FleetError> {
+        let source = self.facility(from)?;
+        let target = self.facility(to)?;
+        if from == to {
+            return Ok(None);
+        }
+        let (mut source_trucks, mut target_trucks) = lock_in_order(&source, &target);
+        if source_trucks.is_empty() {
+            return Ok(None);
+        }
+        let index = pick(source_trucks.len()) % source_trucks.len();
+        let truck = source_trucks.swap_remove(index);
+        let id = truck.id;
+        target_trucks.push(truck);
+        Ok(Some(id))
+    }
+
+    /// Sum of the capacities of the trucks assigned to a facility.
+    pub fn total_capacity(&self, facility: FacilityId) -> Result<u32, FleetError> {
+        Ok(self.facility(facility)?.total_capacity())
+    }
+
+    /// Where every truck is, taken while holding every lock, so it is a consistent snapshot
+    /// even while other threads are moving trucks. Trucks in the yard have no facility.
+    pub fn snapshot(&self) -> Vec<(TruckId, Option<FacilityId>)> {
+        let yard = self.yard.lock().unwrap();
+        let facilities = self.lock_all_facilities();
+
+        let mut places: Vec<_> = yard.iter().map(|truck| (truck.id, None)).collect();
+        for (id, trucks) in &facilities {
+            places.extend(trucks.iter().map(|truck| (truck.id, Some(*id))));
+        }
+        places.sort_unstable();
+        places
+    }
+
+    // Locks every facility, in the order of their ids (the BTreeMap iterates by id, which is the lock order).
+    fn lock_all_facilities(&self) -> Vec<(FacilityId, LockedTrucks<'_>)> {
+        self.facilities.values().map(|facility| (facility.id, facility.trucks.lock().unwrap())).collect()
+    }
+
+    /// Checks that every truck of the fleet is in exactly one place.
+    pub fn audit(&self) -> Result<(), String> {
+        let places = self.snapshot();
+        let mut seen = BTreeMap::new();
+        for (truck, place) in places {
+            if let Some(previous) = seen.insert(truck, place) {
+                return Err(format!("truck {} is both in {:?} and {:?}", truck, previous, place));
+            }
+        }
+        for id in self.trucks.keys() {
+            if !seen.contains_key(id) {
+                return Err(format!("truck {} is lost", id));
+            }
+        }
+        if seen.len() != self.trucks.len() {
+            return Err(format!("{} trucks found, the fleet has {}", seen.len(), self.trucks.len()));
+        }
+        Ok(())
+    }
+}
+
+// The locked list of trucks of a facility.
+type LockedTrucks<'a> = MutexGuard<'a, Vec<Arc<Truck>>>;
+
+// Locks two different facilities, always in the order of their ids, and returns the guards as (a, b).
+fn lock_in_order<'a>(a: &'a Facility, b: &'a Facility) -> (LockedTrucks<'a>, LockedTrucks<'a>) {
+    if a.id < b.id {
+        let a = a.trucks.lock().unwrap();
+        let b = b.trucks.lock().unwrap();
+        (a, b)
+    } else {
+        let b = b.trucks.lock().unwrap();
+        let a = a.trucks.lock().unwrap();
+        (a, b)
+    }
+}
+
+// A tiny xorshift random generator: each dispatcher gets its own seed, so a run can be replayed.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+/// Starts `dispatchers` threads, each one moving `moves` random trucks between random facilities.
+/// Returns the number of trucks actually moved (a move from an empty facility does nothing).
+pub fn dispatch(fleet: &Arc<Fleet>, dispatchers: usize, moves: usize, seed: u64) -> usize {
+    let facilities = fleet.facility_ids();
+    if facilities.len() < 2 {
+        return 0;
+    }
+    let handles: Vec<_> = (0..dispatchers)
+        .map(|dispatcher| {
+            let fleet = Arc::clone(fleet);
+            let facilities = facilities.clone();
+            thread::Builder::new()
+                .name(format!("dispatcher-{}", dispatcher))
+                .spawn(move || {
+                    let seed = seed.wrapping_add(dispatcher as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                    let mut rng = XorShift(seed | 1);
+                    let mut moved = 0;
+                    for _ in 0..moves {
+                        let from = facilities[rng.next(facilities.len())];
+                        let to = facilities[rng.next(facilities.len())];
+                        let pick = rng.next(usize::MAX);
+                        if let Ok(Some(_)) = fleet.move_any(from, to, |_| pick) {
+                            moved += 1;
+                        }
+                    }
+                    moved
+                })
+                .unwrap()
+        })
+        .collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+}
+
+#[allow(dead_code)]
+pub fn run() {
+    let mut fleet = Fleet::new();
+    let south = fleet.add_region("South");
+    let facilities: Vec<FacilityId> =
+        ["Depot", "Harbour", "Airport"].iter().map(|name| fleet.add_facility(south, name).unwrap()).collect();
+
+    // 12 trucks, with capacities 1..=12, spread over the facilities
+    for capacity in 1..=12 {
+        let truck = fleet.add_truck(capacity);
+        fleet.assign(truck, facilities[capacity as usize % facilities.len()]).unwrap();
+    }
+    let fleet = Arc::new(fleet);
+    let total = |fleet: &Fleet| facilities.iter().map(|id| fleet.total_capacity(*id).unwrap()).sum::<u32>();
+    outln!("[Arc fleet] trucks: {}, total capacity: {}", fleet.truck_count(), total(&fleet));
+
+    // The facility reaches its region through the Weak pointer, from any thread
+    let facility = fleet.facility(facilities[0]).unwrap();
+    let region = thread::spawn(move || facility.region().unwrap().name.clone()).join().unwrap();
+    outln!("[Arc fleet] facility {} is in region '{}'", facilities[0], region);
+
+    // 4 dispatchers move 1000 trucks each. Which truck ends up where depends on the scheduling of the threads,
+    // but no truck is lost or duplicated: the total capacity does not change.
+    dispatch(&fleet, 4, 1000, 42);
+    let trucks = fleet.snapshot().len();
+    outln!("[Arc fleet] after dispatching, trucks: {}, total capacity: {}", trucks, total(&fleet));
+    outln!("[Arc fleet] audit: {:?}", fleet.audit());
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "fleet_arc",
+    title: "Fleet model: dispatcher threads moving trucks with Arc and Mutex",
+    tags: &["smart-pointers", "concurrency"],
+    run,
+};
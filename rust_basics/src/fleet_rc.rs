@@ -0,0 +1,263 @@
+// Fleet and facilities, single-threaded (Rc / Weak / RefCell)
+//
+// 'smart_pointers_rc.rs' shares a few trucks between two Vec "facilities", only to print the strong counts.
+// This module turns that example into a small domain model:
+//
+//      Fleet ── owns ──> Region ── owns ──> Facility ── shares ──> Truck
+//                          ^                   │
+//                          └─── Weak ──────────┘
+//
+// - Truck: owned by the fleet (Rc<Truck>), a facility holds an Rc clone of the trucks assigned to it.
+//   The strong count of a truck is 1 (the fleet) + 1 if it is assigned to a facility.
+// - Region: owns its facilities. A facility knows its region through a Weak pointer:
+//   a Rc would make a cycle (region -> facility -> region) and neither would ever be freed.
+// - Facility: lives in an Rc<RefCell<..>> because the fleet updates its list of trucks
+//   while the region also points at it.
+//
+// A truck is assigned to at most one facility at a time, the fleet checks it on every operation.
+// The same model, shared between threads with Arc and Mutex, is in 'fleet_arc.rs'.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+pub type TruckId = u32;
+pub type FacilityId = u32;
+pub type RegionId = u32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Truck {
+    pub id: TruckId,
+    pub capacity: u32,
+}
+
+#[derive(Debug)]
+pub struct Region {
+    pub id: RegionId,
+    pub name: String,
+    facilities: RefCell<Vec<Rc<RefCell<Facility>>>>,
+}
+
+impl Region {
+    /// Ids of the facilities of this region.
+    pub fn facility_ids(&self) -> Vec<FacilityId> {
+        self.facilities.borrow().iter().map(|facility| facility.borrow().id).collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Facility {
+    pub id: FacilityId,
+    pub name: String,
+    region: Weak<Region>,
+    trucks: Vec<Rc<Truck>>,
+}
+
+impl Facility {
+    /// The region of the facility, `None` if the region does not exist anymore.
+    pub fn region(&self) -> Option<Rc<Region>> {
+        self.region.upgrade()
+    }
+
+    pub fn trucks(&self) -> &[Rc<Truck>] {
+        &self.trucks
+    }
+
+    /// Sum of the capacities of the trucks assigned to the facility.
+    pub fn total_capacity(&self) -> u32 {
+        self.trucks.iter().map(|truck| truck.capacity).sum()
+    }
+}
+
+/// Errors returned by the fleet operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FleetError {
+    UnknownRegion(RegionId),
+    UnknownFacility(FacilityId),
+    UnknownTruck(TruckId),
+    /// The truck is already assigned to a facility.
+    AlreadyAssigned { truck: TruckId, facility: FacilityId },
+    /// The truck is not assigned to any facility.
+    NotAssigned(TruckId),
+    /// The truck is not at the facility it should be moved from ('fleet_arc.rs').
+    NotAtFacility { truck: TruckId, facility: FacilityId },
+}
+
+impl fmt::Display for FleetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FleetError::UnknownRegion(id) => write!(f, "unknown region {}", id),
+            FleetError::UnknownFacility(id) => write!(f, "unknown facility {}", id),
+            FleetError::UnknownTruck(id) => write!(f, "unknown truck {}", id),
+            FleetError::AlreadyAssigned { truck, facility } => {
+                write!(f, "truck {} is already assigned to facility {}", truck, facility)
+            }
+            FleetError::NotAssigned(id) => write!(f, "truck {} is not assigned to a facility", id),
+            FleetError::NotAtFacility { truck, facility } => {
+                write!(f, "truck {} is not at facility {}", truck, facility)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FleetError {}
+
+/// The trucks, regions and facilities of a company, and which truck is assigned where.
+#[derive(Debug, Default)]
+pub struct Fleet {
+    trucks: BTreeMap<TruckId, Rc<Truck>>,
+    regions: BTreeMap<RegionId, Rc<Region>>,
+    facilities: BTreeMap<FacilityId, Rc<RefCell<Facility>>>,
+    assignments: BTreeMap<TruckId, FacilityId>,
+    next_id: u32,
+}
+
+impl Fleet {
+    pub fn new() -> Self {
+        Fleet::default()
+    }
+
+    // Ids are unique across trucks, regions and facilities, which makes mix-ups easier to spot.
+    fn next_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    pub fn add_truck(&mut self, capacity: u32) -> TruckId {
+        let id = self.next_id();
+        self.trucks.insert(id, Rc::new(Truck { id, capacity }));
+        id
+    }
+
+    pub fn add_region(&mut self, name: &str) -> RegionId {
+        let id = self.next_id();
+        let region = Region { id, name: name.to_string(), facilities: RefCell::new(Vec::new()) };
+        self.regions.insert(id, Rc::new(region));
+        id
+    }
+
+    pub fn add_facility(&mut self, region: RegionId, name: &str) -> Result<FacilityId, FleetError> {
+        if !self.regions.contains_key(&region) {
+            return Err(FleetError::UnknownRegion(region));
+        }
+        let id = self.next_id();
+        let region = &self.regions[&region];
+        let facility = Rc::new(RefCell::new(Facility {
+            id,
+            name: name.to_string(),
+            region: Rc::downgrade(region),
+            trucks: Vec::new(),
+        }));
+        region.facilities.borrow_mut().push(Rc::clone(&facility));
+        self.facilities.insert(id, facility);
+        Ok(id)
+    }
+
+    pub fn truck(&self, id: TruckId) -> Result<Rc<Truck>, FleetError> {
+        self.trucks.get(&id).cloned().ok_or(FleetError::UnknownTruck(id))
+    }
+
+    pub fn region(&self, id: RegionId) -> Result<Rc<Region>, FleetError> {
+        self.regions.get(&id).cloned().ok_or(FleetError::UnknownRegion(id))
+    }
+
+    pub fn facility(&self, id: FacilityId) -> Result<Rc<RefCell<Facility>>, FleetError> {
+        self.facilities.get(&id).cloned().ok_or(FleetError::UnknownFacility(id))
+    }
+
+    /// The facility the truck is assigned to, if any.
+    pub fn facility_of(&self, truck: TruckId) -> Result<Option<FacilityId>, FleetError> {
+        self.truck(truck)?;
+        Ok(self.assignments.get(&truck).copied())
+    }
+
+    /// Assigns an unassigned truck to a facility.
+    pub fn assign(&mut self, truck: TruckId, facility: FacilityId) -> Result<(), FleetError> {
+        let shared_truck = self.truck(truck)?;
+        let target = self.facility(facility)?;
+        if let Some(&current) = self.assignments.get(&truck) {
+            return Err(FleetError::AlreadyAssigned { truck, facility: current });
+        }
+        target.borrow_mut().trucks.push(shared_truck);
+        self.assignments.insert(truck, facility);
+        Ok(())
+    }
+
+    /// Removes a truck from its facility and returns the facility it was assigned to.
+    pub fn unassign(&mut self, truck: TruckId) -> Result<FacilityId, FleetError> {
+        self.truck(truck)?;
+        let facility = self.assignments.remove(&truck).ok_or(FleetError::NotAssigned(truck))?;
+        self.facilities[&facility].borrow_mut().trucks.retain(|assigned| assigned.id != truck);
+        Ok(facility)
+    }
+
+    /// Moves a truck from its current facility (if any) to another one.
+    pub fn move_truck(&mut self, truck: TruckId, to: FacilityId) -> Result<(), FleetError> {
+        self.facility(to)?;
+        match self.unassign(truck) {
+            Ok(_) | Err(FleetError::NotAssigned(_)) => self.assign(truck, to),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Sum of the capacities of the trucks assigned to a facility.
+    pub fn total_capacity(&self, facility: FacilityId) -> Result<u32, FleetError> {
+        Ok(self.facility(facility)?.borrow().total_capacity())
+    }
+
+    /// Total capacity of every facility, by facility id.
+    pub fn capacity_report(&self) -> BTreeMap<FacilityId, u32> {
+        self.facilities
+            .iter()
+            .map(|(id, facility)| (*id, facility.borrow().total_capacity()))
+            .collect()
+    }
+
+    /// Ids of the trucks that are not assigned to any facility.
+    pub fn unassigned_trucks(&self) -> Vec<TruckId> {
+        self.trucks.keys().filter(|id| !self.assignments.contains_key(id)).copied().collect()
+    }
+}
+
+#[allow(dead_code)]
+pub fn run() {
+    let mut fleet = Fleet::new();
+    let north = fleet.add_region("North");
+    let depot = fleet.add_facility(north, "Depot").unwrap();
+    let harbour = fleet.add_facility(north, "Harbour").unwrap();
+
+    let truck_a = fleet.add_truck(1);
+    let truck_b = fleet.add_truck(2);
+    let truck_c = fleet.add_truck(3);
+
+    fleet.assign(truck_a, depot).unwrap();
+    fleet.assign(truck_b, depot).unwrap();
+    fleet.assign(truck_c, harbour).unwrap();
+    outln!("Capacity per facility: {:?}", fleet.capacity_report());
+
+    // The facility reaches its region through the Weak pointer
+    let facility = fleet.facility(depot).unwrap();
+    let region = facility.borrow().region().unwrap();
+    outln!("Facility '{}' is in region '{}'", facility.borrow().name, region.name);
+
+    // a truck cannot be in two facilities at the same time
+    outln!("assign truck {} to the harbour: {:?}", truck_b, fleet.assign(truck_b, harbour));
+
+    // the fleet holds one reference, the facility another one
+    let truck = fleet.truck(truck_b).unwrap();
+    outln!("# of references `truck b` (strong count, without this one): {}", Rc::strong_count(&truck) - 1);
+    fleet.move_truck(truck_b, harbour).unwrap();
+    outln!("Capacity per facility after moving truck {}: {:?}", truck_b, fleet.capacity_report());
+
+    fleet.unassign(truck_b).unwrap();
+    outln!("# of references `truck b` after unassign (without this one): {}", Rc::strong_count(&truck) - 1);
+    outln!("Unassigned trucks: {:?}", fleet.unassigned_trucks());
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "fleet_rc",
+    title: "Fleet model: trucks shared with Rc, Weak back-references",
+    tags: &["smart-pointers", "structs"],
+    run,
+};
@@ -17,14 +17,14 @@ pub fn run(){
     
     // Example 2: 
     // passing arguments and getting the return back
-    println!("result: {}", add(1,2));
+    outln!("result: {}", add(1,2));
 
-    println!("sum result without biding function values: {}", add(5,15));
+    outln!("sum result without biding function values: {}", add(5,15));
 
     // Example 3: 
     // bind function values to variables
     let get_sum = add(5,5);
-    println!("sum result using biding function to variable: {}", get_sum);
+    outln!("sum result using biding function to variable: {}", get_sum);
 
     // Example 4: 
     // Closures: closures are anonymous functions.
@@ -39,15 +39,15 @@ pub fn run(){
     // https://medium.com/coding-rust/best-explanation-of-closure-in-rust-2b20210eba53
     // Ex: 
     let add_numbs = | n1: i32 , n2: i32 | { n1 + n2 };
-    println!("C sum with closures : {} ", add_numbs(4,3));
+    outln!("C sum with closures : {} ", add_numbs(4,3));
 
 
     // Example 5: generic functions
     let number_list = vec![34, 25, 11, 73, 5, 32, 13];
     let char_list = vec!['h', 'm', 'a', 'q'];
 
-    println!("The largest number is {}", largest(&number_list));
-    println!("The largest number is {}", largest(&char_list));
+    outln!("The largest number is {}", largest(&number_list));
+    outln!("The largest number is {}", largest(&char_list));
 
 
 
@@ -56,16 +56,16 @@ pub fn run(){
 
 // passing parameters 
 fn greeting(greet: &str, name: &str){
-    println!("{} {} , nice to meet you! ", greet, name)
+    outln!("{} {} , nice to meet you! ", greet, name)
 }
 
 // returning values 
 // we use the arrow to indicate the returning type of  a function
 fn add(mut number1: i32, mut number2: i32) -> i32 {
-    println!("Received number1: {} , number2: {}!", number1, number2);
+    outln!("Received number1: {} , number2: {}!", number1, number2);
     number1 +=2;
     number2 +=3; 
-    println!("Added , number1: {} , number2: {}!", number1, number2);
+    outln!("Added , number1: {} , number2: {}!", number1, number2);
 
     // to return a value just remove the semicolon to tell the function to return it
     // this will return 'number1 + number2' 
@@ -101,10 +101,9 @@ fn largest<T: std::cmp::PartialOrd>(list: &[T]) -> &T {
     largest
 }
 
-
-
-
-
-
-
-
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "functions",
+    title: "Functions, parameters and return values",
+    tags: &["basics", "functions"],
+    run,
+};
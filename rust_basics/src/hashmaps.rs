@@ -1,10 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 
 // This is a basic example showing how to use hash maps in Rust 
 //  A HashMap<K, V> stores a mapping of keys of type K to values of type V.
 //  It does this via a hashing function, which determines how it places these keys and values into memory. (source: rust book, steve klabnik)
 //
+// NOTE: the iteration order of a HashMap is arbitrary and changes from one run to another
+// (the hasher is randomly seeded). In order to get the same output every time, the examples
+// below print the maps sorted by key: collecting the (key, value) pairs into a BTreeMap does that for us.
+fn sorted_by_key<K: Ord, V>(map: &HashMap<K, V>) -> BTreeMap<&K, &V> {
+    map.iter().collect()
+}
+
 #[allow(dead_code)]
 pub fn run() {
 
@@ -14,7 +21,7 @@ pub fn run() {
         let mut exams_scores = HashMap::new();  // create a new hash map 
         exams_scores.insert(String::from("Alice"), 85);
         exams_scores.insert(String::from("Bob"), 75);  
-        println!("{:?}", exams_scores);   // prints hashmap:  {"Bob": 75, "Alice": 85}
+        outln!("{:?}", sorted_by_key(&exams_scores));   // prints hashmap:  {"Alice": 85, "Bob": 75}
 
     }
 
@@ -25,28 +32,36 @@ pub fn run() {
         // Then we could use the collect method to turn that vector of tuples into a hash map.
         let students = vec![String::from("Alice"), String::from("Bob")];
         let scores = vec![85, 75];
-        println!("Vector students: {:?}", &students);
-        println!("Vector scores: {:?}", &scores);
+        outln!("Vector students: {:?}", &students);
+        outln!("Vector scores: {:?}", &scores);
         
 
         // Creating a hash map from two other vectors 
         // The type annotation HashMap<_, _> is needed here because it’s possible to collect into many different data structures and Rust doesn’t know which you want unless you specify. 
         let exam_scores : HashMap<_, _> = students.iter().zip(scores.iter()).collect();
 
-        println!("Hash Map exam_scores, zipped from previous vectors{:?}: ", exam_scores); 
+        outln!("Hash Map exam_scores, zipped from previous vectors{:?}: ", sorted_by_key(&exam_scores)); 
         
         
         // Accessing Values in a Hash Map
         let score = exam_scores.get(&String::from("Alice"));
         //println!("Alice' score is: {:?}", score);
-        println!("Alice' score is: {:?}", score.expect("expected value corresponding to Alice's key"));
+        outln!("Alice' score is: {:?}", score.expect("expected value corresponding to Alice's key"));
 
         // We can also iterate over each key/value pair in a hash map in a similar manner as we do with vectors:
-        for (key, value) in exam_scores{
-            println!("key: {:?} , value: {:?}" , key, value);
+        // (here again sorted by key, iterating over 'exam_scores' directly yields the pairs in an arbitrary order)
+        for (key, value) in sorted_by_key(&exam_scores){
+            outln!("key: {:?} , value: {:?}" , key, value);
 
         }
 
     }
     
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "hashmaps",
+    title: "HashMap<K, V>: key/value collections",
+    tags: &["collections"],
+    run,
+};
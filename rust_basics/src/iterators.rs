@@ -3,9 +3,6 @@
 //  Iterators in Rust are lazy, meaning they don't execute until you explicitly consume them. 
 // They provide a safe and efficient way to work with collections without sacrificing performance or safety.
 
-use core::num;
-use std::result;
-
 #[allow(dead_code)]
 pub fn run(){
     // Exemple 1: 
@@ -16,22 +13,22 @@ pub fn run(){
     {
         let fruits = vec!["banana", "apple" , "passion-fruit" , "strawberry"];
         let mut fruit_iter = fruits.into_iter();
-        println!("Iterator.next(): {:?}", fruit_iter.next());
-        println!("Iterator.next(): {:?}", fruit_iter.next());
-        println!("Iterator.next(): {:?}", fruit_iter.next());
-        println!("Iterator.next(): {:?}", fruit_iter.next());
-        println!("Iterator.next(): {:?}", fruit_iter.next());
+        outln!("Iterator.next(): {:?}", fruit_iter.next());
+        outln!("Iterator.next(): {:?}", fruit_iter.next());
+        outln!("Iterator.next(): {:?}", fruit_iter.next());
+        outln!("Iterator.next(): {:?}", fruit_iter.next());
+        outln!("Iterator.next(): {:?}", fruit_iter.next());
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
     // Example 2:
     // Of course, using the method 'next()' to access elements of the iterator is quite verbose
     // An easier way of doing this is to use a for loop as shown above
     {
         let breads = vec!["baguette", "croissant" , "pain_au_chocolat" , "Brioche"];
         for item in breads {
-            println!("Item: {:?}", &item); }
+            outln!("Item: {:?}", &item); }
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
     // Example 3: Creating iterators
     // When creating iterators in Rust, choosing between `iter()`, `iter_mut()`, and `into_iter()` 
     // depends on how you want to interact with the elements of the collection.
@@ -42,19 +39,19 @@ pub fn run(){
     {   
         let numbers = vec![1, 2, 3, 4, 5];
         // Let's create an Iterator of immutable references, we wont take ownership of the vector
-        println!("Original vector : {:?}" , numbers );
+        outln!("Original vector : {:?}" , numbers );
         let immut_ref_iterator = numbers.iter();
         for item in immut_ref_iterator {
-            println!("Iterator of Immutable references - [item : {:?}]" , item );  }
-        println!("Original, no owned vector still available: {:?}" , numbers );
+            outln!("Iterator of Immutable references - [item : {:?}]" , item );  }
+        outln!("Original, no owned vector still available: {:?}" , numbers );
     }
     {   
         let mut numbers = vec![1, 2, 3, 4, 5];
         // Let's create an Iterator of mutable references, we wont take ownership of the vector either, just be able to mutate values
         let mut_ref_iterator = numbers.iter_mut();
         for item in mut_ref_iterator {
-            println!("Iterator of Immutable references - [item + 1 : {:?}]" , *item + 1 );  }
-        println!("Original, no owned vector still available: {:?}" , numbers );
+            outln!("Iterator of Immutable references - [item + 1 : {:?}]" , *item + 1 );  }
+        outln!("Original, no owned vector still available: {:?}" , numbers );
     }
     {   
         let numbers = vec![1, 2, 3, 4, 5];
@@ -62,7 +59,7 @@ pub fn run(){
         let owned_values_iterator = numbers.into_iter();
         // at this point, the original vector was consumed and we cannot access it anymore.
         for item in owned_values_iterator {
-            println!("Iterator of owned values - [item + 1 : {:?}]" , item );
+            outln!("Iterator of owned values - [item + 1 : {:?}]" , item );
         }
         // if we try to use the original vector again, we will have a compiling error.
         // because the ownership of the original vector was moved to the iterator.
@@ -94,20 +91,20 @@ pub fn run(){
         let text1 = "Hello";
         let char_iter1 = text1.chars();
         for item in char_iter1 {
-            println!("Item in char_iter1: {}", item);
+            outln!("Item in char_iter1: {}", item);
         }
-        println!("Original string of char_iter1: {}", text1);
+        outln!("Original string of char_iter1: {}", text1);
     }
     {
         let text2 = String::from("World!");
         let char_iter2 = text2.chars();
         for item in char_iter2 {
-            println!("Item in char_iter2: {}", item);
+            outln!("Item in char_iter2: {}", item);
         }
-        println!("Original string of char_iter2: {}", text2);
+        outln!("Original string of char_iter2: {}", text2);
     }
     
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
     // ------------------------------------------
     // Useful methods 
     // ------------------------------------------
@@ -119,12 +116,13 @@ pub fn run(){
     //          The most basic pattern in which collect() is used is to turn one collection into another.
     
     // ------------------------------------------   
-    println!("collect() method:");   
+    outln!("collect() method:");   
     {
         // Creates an iterator that takes ownership of the vector of characters.
         let chars = vec!['R', 'u', 's', 't'];
         // Consumes the iterator and collects the characters into a String.
         let word: String = chars.into_iter().collect();
+        outln!("{:?}", word);
     }
     // NOTE: 
     // The collect() method is very general because it can convert an iterator into many different 
@@ -138,7 +136,7 @@ pub fn run(){
         // let result = numbers.iter().map(|&x| x * 2).collect(); // Error: type inference needed
         // We need to specify that we want to collect into a Vec<i32> using the turbofish sintax `::<>`
         let result = numbers.iter().map(|&x| x * 2).collect::<Vec<i32>>();
-        println!("{:?}", result);
+        outln!("{:?}", result);
     }
 
     // ------------------------------------------
@@ -146,12 +144,12 @@ pub fn run(){
     // Chaining interators with 'chain()' method
     // Takes two iterators and creates a new iterator over both in sequence. 
     // ------------------------------------------
-    println!("chain() method:");
+    outln!("chain() method:");
     {     
         let fruits = vec!["banana", "apple" , "passion-fruit" , "strawberry"];
         let breads = vec!["baguette", "croissant" , "pain_au_chocolat" , "Brioche"];
-        println!("fruits iterator: {:?}", fruits);
-        println!("breads iterator: {:?}", breads);
+        outln!("fruits iterator: {:?}", fruits);
+        outln!("breads iterator: {:?}", breads);
         
         // chain() will return a new iterator which will first iterate over values from the first iterator 
         // and then over values from the second iterator. 
@@ -169,27 +167,27 @@ pub fn run(){
         //for food in aggregate_food {
         //    println!("{:?}" , food);    
         //}
-        println!("all_food iterator: {:?}", all_food);
+        outln!("all_food iterator: {:?}", all_food);
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
 
     // ------------------------------------------
     // Example 6:  last()
     // Get the last element of an iterator
     // last() method: Consumes the iterator, returning the last element.
     // ------------------------------------------
-    println!("last() method:");
+    outln!("last() method:");
     {
         let a = [1, 2, 3, 4, 5];
         let my_iterator =  a.iter();
         let last_element = my_iterator.last().unwrap();
-        println!("last_element of iterator: {:?}", last_element);
+        outln!("last_element of iterator: {:?}", last_element);
         // we can access the original iterator, because we create it from references, iter()
-        println!("Original iterator: {:?}", a);
+        outln!("Original iterator: {:?}", a);
         // but we cannot access the iterator after using the method last(), because last consumes the iterator.
         // println!("my_iterator: {:?}", my_iterator);
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
 
     // ------------------------------------------
     // Example 7: enumerate()
@@ -197,16 +195,16 @@ pub fn run(){
     // Creates an iterator which gives the current iteration count as well as the next value.
     // The iterator returned yields pairs (i, val), 
     // where i is the current index of iteration and val is the value returned by the iterator.
-    println!("enumerate() method:");
+    outln!("enumerate() method:");
     { 
         let numbers = vec![10, 20, 30];
         for (index, value) in numbers.iter().enumerate() {
-            println!("Index: {}, Value: {}", index, value);
+            outln!("Index: {}, Value: {}", index, value);
         }
     }
     // ------------------------------------------
 
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
     
     // ------------------------------------------
     // Example 8: filter()
@@ -220,12 +218,12 @@ pub fn run(){
     //   The filter method is used to filter elements from an iterator based on a predicate (a closure that returns a boolean). 
     //   It keeps only the elements that satisfy the predicate.
     
-    println!("filter() method");
+    outln!("filter() method");
     {   
         let numbers = vec![1, 2, 3, 4, 5];
         let even_numbers  = numbers.iter().filter(|&x| x % 2 == 0);
         for item in even_numbers {
-            println!("event item on the new iterator(even_numbers) => {:?}" , item);    
+            outln!("event item on the new iterator(even_numbers) => {:?}" , item);    
         }
 
     }
@@ -259,7 +257,7 @@ pub fn run(){
     // - filter_map: is a combination of both: it can filter out elements (by returning None) 
     //               and transform values (by returning Some(new_value)).
 
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
 
     // ------------------------------------------
     // Example 9:  skip() and take() 
@@ -269,14 +267,14 @@ pub fn run(){
     //          After that, all the remaining elements are yielded.
     //          If the original iterator is too short, then the returned iterator is empty.
     // ------------------------------------------
-    println!("skip() method");
+    outln!("skip() method");
     {
         let numbers = vec![1, 2, 3, 4, 5];
-        println!("Original collection: {:?}" , numbers);
+        outln!("Original collection: {:?}" , numbers);
 
         let skipped_iter= numbers.iter().skip(3);
         for item in skipped_iter {
-            println!("item on the new skipped iterator .skip(3 => {:?}" , item);    
+            outln!("item on the new skipped iterator .skip(3 => {:?}" , item);    
         }
 
     }
@@ -285,17 +283,17 @@ pub fn run(){
     // Take  a specified number of elements from the iterator.
     // Creates an iterator that yields the first n elements, or fewer if the underlying iterator ends sooner.
     // ------------------------------------------
-    println!("take() method:");
+    outln!("take() method:");
     {
         let numbers = vec![1, 2, 3, 4, 5];
-        println!("Original collection: {:?}" , numbers);
+        outln!("Original collection: {:?}" , numbers);
         let first_three = numbers.iter().take(3);
         for item in first_three {
-            println!("item taken iterator.take(3)=> {:?}" , item);    
+            outln!("item taken iterator.take(3)=> {:?}" , item);    
         }
 
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
     // ------------------------------------------
     // Example 10: methods all() and any() 
     // ------------------------------------------
@@ -304,21 +302,21 @@ pub fn run(){
     //         It applies this closure to each element of the iterator, and if they all return true, 
     //         then so does all(). If any of them return false, it returns false.
     // NOTE: An empty iterator will always return true.
-    println!("all() method:");
+    outln!("all() method:");
     {
         let numbers = vec![1, 2, 3, 4, 5];
-        println!("Original collection: {:?}" , numbers);
+        outln!("Original collection: {:?}" , numbers);
         let are_all_even = numbers.iter().all(|&x| {x % 2 == 0});
-        println!("All items are even ? => {:?} \n" , are_all_even);    
+        outln!("All items are even ? => {:?} \n" , are_all_even);    
 
         let text = String::from("Hello, how are you?");
-        println!("Original empty collection: {:?}" , text);
+        outln!("Original empty collection: {:?}" , text);
         // in order to show that an empty iterator returns true, 
         // I'm filtering the original iterator to yeld only numeric chars, that dont exist in the original iterator
         // this empty iterator is in turn, given to the all() methods to check if the result elements are alphabetic.
         let result = text.chars().filter(|x| x.is_numeric())
                                         .all(|x| x.is_ascii_hexdigit());
-        println!("All items are is_ascii_hexdigit? => {:?} \n" , result);    
+        outln!("All items are is_ascii_hexdigit? => {:?} \n" , result);    
         // although none of the elements are ascii hexadigit, it replies true        
     }
     // ------------------------------------------
@@ -328,21 +326,21 @@ pub fn run(){
     //          and if any of them return true, then so does any(). If they all return false, it returns false.
     // NOTE: An empty iterator returns false.
     // ------------------------------------------
-    println!("any() method:");
+    outln!("any() method:");
     {
         let numbers = vec![1, 2, 3, 4, 5];
-        println!("Original collection: {:?}" , numbers);
+        outln!("Original collection: {:?}" , numbers);
         let are_all_even = numbers.iter().any(|&x| {x % 2 == 0});
-        println!("Any item is even? => {:?} \n" , are_all_even);   
+        outln!("Any item is even? => {:?} \n" , are_all_even);   
 
         let text = String::from("Hello, how are you?");
-        println!("Original empty collection: {:?}" , text);
+        outln!("Original empty collection: {:?}" , text);
         let result = text.chars().filter(|x| x.is_numeric())
                                         .any(|x| x.is_ascii_alphabetic());
-        println!("All items are is_ascii_alphabetic? => {:?} \n" , result); 
+        outln!("All items are is_ascii_alphabetic? => {:?} \n" , result); 
         
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
     // ------------------------------------------
     // Example 11: map()
     // ------------------------------------------
@@ -357,17 +355,17 @@ pub fn run(){
     //  It does not filter the elements but maps each one to a new value.
 
 
-    println!("map() method");
+    outln!("map() method");
     {
         let numbers = vec![1, 2, 3, 4, 5];
-        println!(": Original collection {:?}", numbers);
+        outln!(": Original collection {:?}", numbers);
         // Create an iterator over the vector, square each number using 'map'
         let squared_numbers = numbers.iter().map(|&x| x * x);
         // Collect the squared numbers into a new vector
         let squared_numbers_vec: Vec<i32> = squared_numbers.collect();
-        println!("mapped elements{:?}", squared_numbers_vec); // Output: [1, 4, 9, 16, 25]
+        outln!("mapped elements{:?}", squared_numbers_vec); // Output: [1, 4, 9, 16, 25]
     }
-    println!("------------------------------------------------------");
+    outln!("------------------------------------------------------");
 
     // ------------------------------------------
     // Example 12:  method inspect()
@@ -375,18 +373,18 @@ pub fn run(){
     // The inspect() method in Rust is an iterator adapter that allows you to peek at each item as it passes through 
     // the iterator chain without modifying the items. This is particularly useful for debugging or logging the values during iteration.
     // ------------------------------------------
-    println!("inspect() method:");
+    outln!("inspect() method:");
     {
         let numbers = vec![1, 2, 3, 4, 5];
         // Chain of iterator methods with inspect
         let doubled_numbers: Vec<i32> = numbers
             .iter() // Create an iterator over immutable references to the elements
-            .inspect(|&x| println!("Original value: {}", x)) // Inspect and print each original value
+            .inspect(|&x| outln!("Original value: {}", x)) // Inspect and print each original value
             .map(|&x| x * 2) // Double each value
-            .inspect(|&x| println!("Doubled value: {}", x)) // Inspect and print each doubled value
+            .inspect(|&x| outln!("Doubled value: {}", x)) // Inspect and print each doubled value
             .collect(); // Collect the results into a new vector
         
-        println!("Resulting vector: {:?}", doubled_numbers); // Output: [2, 4, 6, 8, 10]
+        outln!("Resulting vector: {:?}", doubled_numbers); // Output: [2, 4, 6, 8, 10]
     }
    
      // ------------------------------------------
@@ -397,21 +395,28 @@ pub fn run(){
     //  and the second element comes from the second iterator.
     //  In other words, it zips two iterators together, into a single one.
     // ------------------------------------------
-    println!("zip() method:");
+    outln!("zip() method:");
     {
         let a1 = [1, 2, 3];
         let a2 = [4, 5, 6];
         
-        println!("iterator a1: {:?}" , a1);
-        println!("iterator a2: {:?}", a2);
+        outln!("iterator a1: {:?}" , a1);
+        outln!("iterator a2: {:?}", a2);
         let my_zipped_iter = a1.iter().zip(a2.iter());
-        println!("Zipper iterator ->");
+        outln!("Zipper iterator ->");
         for item in my_zipped_iter {
-            println!("item (from iterator 1, from iterator 2) => {:?}" , item);    
+            outln!("item (from iterator 1, from iterator 2) => {:?}" , item);    
         }
         //let res = my_zipped_iter.skip(1);
 
     }
-    println!("------------------------------------------------------"); 
+    outln!("------------------------------------------------------"); 
+
+}
 
-}
\ No newline at end of file
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "iterators",
+    title: "Iterators and iterator adapters",
+    tags: &["collections", "iterators"],
+    run,
+};
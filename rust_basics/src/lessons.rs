@@ -0,0 +1,80 @@
+// Lesson registry
+//
+// Every example module exposes a `LESSON` constant describing itself (name, title, tags)
+// and pointing at its `run()` function. This file gathers them in one place, so the binary
+// can list and run lessons by name instead of commenting/uncommenting code in main().
+//
+// To add a new lesson:
+// - declare the module in lib.rs
+// - add a `pub const LESSON: crate::lessons::Lesson = ...` at the end of the module
+// - add `module::LESSON` to the `ALL` slice below (keep it sorted by name)
+// - generate its snapshot with `UPDATE_SNAPSHOTS=1 cargo test --test snapshots`
+
+use crate::*;
+
+/// A runnable example, as shown by `rust_basics list`.
+#[derive(Debug, Clone, Copy)]
+pub struct Lesson {
+    /// Name used on the command line, e.g. `rust_basics run iterators`.
+    pub name: &'static str,
+    /// One line description.
+    pub title: &'static str,
+    /// Free-form tags used to group related lessons.
+    pub tags: &'static [&'static str],
+    /// Entry point of the lesson.
+    pub run: fn(),
+}
+
+/// All the lessons of the crate, sorted by name.
+pub const ALL: &[Lesson] = &[
+    arrays::LESSON,
+    cli::LESSON,
+    closures::LESSON,
+    conditionals::LESSON,
+    enum_option_ex1::LESSON,
+    enum_option_ex2::LESSON,
+    enums::LESSON,
+    error_handling::LESSON,
+    fleet_arc::LESSON,
+    fleet_rc::LESSON,
+    functions::LESSON,
+    hashmaps::LESSON,
+    iterators::LESSON,
+    loops::LESSON,
+    match_patterns::LESSON,
+    options::LESSON,
+    ownership::LESSON,
+    print::LESSON,
+    raw_pointers::LESSON,
+    rc_cycles::LESSON,
+    references::LESSON,
+    simple_boxed_list::LESSON,
+    smart_pointers::LESSON,
+    smart_pointers_arc::LESSON,
+    smart_pointers_box::LESSON,
+    smart_pointers_rc::LESSON,
+    smart_pointers_refcell::LESSON,
+    strings::LESSON,
+    structs::LESSON,
+    tuples::LESSON,
+    types::LESSON,
+    vars::LESSON,
+    vectors::LESSON,
+];
+
+/// Looks a lesson up by its name.
+pub fn find(name: &str) -> Option<&'static Lesson> {
+    ALL.iter().find(|lesson| lesson.name == name)
+}
+
+/// Resolves every name to its lesson, failing on the first unknown one
+/// (nothing is run if a single name is wrong).
+pub fn resolve<'a, I>(names: I) -> Result<Vec<&'static Lesson>, String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    names
+        .into_iter()
+        .map(|name| find(name).ok_or_else(|| format!("unknown topic '{}'", name)))
+        .collect()
+}
@@ -0,0 +1,70 @@
+//! # RUST Hands On
+//!
+//! This is a very basic hands on that drives me in my first steps with RUST.
+//! Each module below treats a specific basic topic and exposes a `run()` function
+//! with the examples, plus a `LESSON` entry registered in [`lessons::ALL`].
+//! The examples print through the `outln!` family of macros (see [`output`]) so their
+//! output can be captured and checked against the golden files in `tests/snapshots/`.
+//!
+//! The examples deliberately spell things out the long way (`expect()` on a `Some`,
+//! `!true`, `std::i32::MAX`, ...) to show what the short forms stand for, so the
+//! corresponding clippy lints are allowed on those modules.
+
+#[macro_use]
+pub mod output;
+pub mod lessons;
+
+pub mod arrays;
+pub mod cli;
+pub mod closures;
+#[allow(clippy::needless_bool)]
+pub mod conditionals;
+pub mod enum_option_ex1;
+#[allow(clippy::unnecessary_literal_unwrap)]
+pub mod enum_option_ex2;
+pub mod enums;
+#[allow(clippy::question_mark)]
+pub mod error_handling;
+pub mod functions;
+pub mod hashmaps;
+#[allow(clippy::useless_conversion, clippy::useless_vec)]
+pub mod iterators;
+pub mod loops;
+pub mod match_patterns;
+#[allow(clippy::unnecessary_literal_unwrap)]
+pub mod options;
+pub mod ownership;
+#[allow(clippy::print_literal)]
+pub mod print;
+pub mod raw_pointers;
+pub mod rc_cycles;
+pub mod references;
+pub mod simple_boxed_list;
+#[allow(clippy::get_first)]
+pub mod smart_pointers;
+pub mod smart_pointers_arc;
+pub mod smart_pointers_box;
+pub mod smart_pointers_rc;
+pub mod smart_pointers_refcell;
+pub mod strings;
+#[allow(clippy::wrong_self_convention)]
+pub mod structs;
+pub mod tuples;
+#[allow(clippy::nonminimal_bool, clippy::legacy_numeric_constants)]
+pub mod types;
+#[allow(clippy::zero_prefixed_literal)]
+pub mod vars;
+#[allow(clippy::assign_op_pattern)]
+pub mod vectors;
+
+// Data structures built from the lessons above
+pub mod avl_map;
+pub mod doubly_linked_list;
+pub mod fleet_arc;
+pub mod fleet_rc;
+pub mod linked_list;
+pub mod my_arc;
+pub mod my_rc;
+pub mod persistent_list;
+pub mod raw_deque;
+pub mod tracked_rc;
@@ -0,0 +1,274 @@
+// A reusable singly linked list
+//
+// This is the list built by hand in 'simple_boxed_list.rs' and 'smart_pointers_box.rs'
+// (a chain of Option<Box<Node>>), turned into a generic type with the usual collection API.
+//
+//      head -> [elem | next] -> [elem | next] -> [elem | None]
+//
+// Every node owns the next one through a Box, so the list owns all of its nodes and
+// no 'unsafe' is needed. Adding/removing at the front is O(1), anything at the back is O(n).
+//
+// NOTE: the Drop implementation. The default drop of a Box<Node> drops its 'next' Box, which
+// drops its own 'next' and so on: the drop is recursive and a long enough list overflows the stack.
+// That's why we implement Drop ourselves, unlinking the nodes one by one in a loop.
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+/// A singly linked list of `Box`ed nodes.
+pub struct LinkedList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> LinkedList<T> {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        LinkedList { head: None, len: 0 }
+    }
+
+    /// Number of elements in the list, O(1).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Adds an element in the beginning of the list.
+    pub fn push_front(&mut self, elem: T) {
+        // take() leaves a None in 'head' while the new node takes ownership of the old head
+        let new_node = Box::new(Node { elem, next: self.head.take() });
+        self.head = Some(new_node);
+        self.len += 1;
+    }
+
+    /// Removes the first element of the list and returns it, or `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|node| {
+            self.head = node.next;
+            self.len -= 1;
+            node.elem
+        })
+    }
+
+    /// Returns a reference to the first element.
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// Returns a mutable reference to the first element.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.elem)
+    }
+
+    /// Reverses the list in place, without allocating.
+    pub fn reverse(&mut self) {
+        let mut reversed: Link<T> = None;
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    /// Removes every element of the list.
+    pub fn clear(&mut self) {
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+        }
+        self.len = 0;
+    }
+
+    /// Iterator over references to the elements, from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref(), len: self.len }
+    }
+
+    /// Iterator over mutable references to the elements, from front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut(), len: self.len }
+    }
+
+    // Returns the (empty) link after the last node, where new elements are appended.
+    // It only borrows the head, so `len` can still be updated while the link is in use.
+    fn tail_link(head: &mut Link<T>) -> &mut Link<T> {
+        let mut link = head;
+        while let Some(node) = link {
+            link = &mut node.next;
+        }
+        link
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+/// Collects the elements keeping their order: the first element of the iterator becomes the front of the list.
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Appends the elements at the back of the list, keeping their order.
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        // walk to the end once, then keep a cursor on the last link
+        let mut link = Self::tail_link(&mut self.head);
+        for elem in iter {
+            let node = link.insert(Box::new(Node { elem, next: None }));
+            link = &mut node.next;
+            // counted at once: if `iter` panics, `len` is still the number of nodes
+            self.len += 1;
+        }
+    }
+}
+
+// ------------------------------------------------
+// Iterators
+// ------------------------------------------------
+
+/// Iterator returned by [`LinkedList::iter`].
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.len -= 1;
+            &node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { next: self.next, len: self.len }
+    }
+}
+
+/// Iterator returned by [`LinkedList::iter_mut`].
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // take() the mutable reference out of the Option: &mut is not Copy
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            self.len -= 1;
+            &mut node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Consuming iterator returned by `into_iter()`, pops the elements from the front.
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
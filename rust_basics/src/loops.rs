@@ -7,7 +7,7 @@ pub fn run(){
     // infinite loop: 
     loop {
         count += 1;
-        println!("Number: {} ", count);
+        outln!("Number: {} ", count);
 
         if count == 20 {
             break;
@@ -18,13 +18,13 @@ pub fn run(){
     count = 0;
     while count <= 100 {
         if count % 15 == 0 {
-            println!("fizzbuzz");
+            outln!("fizzbuzz");
         }else if count % 3 == 0 {
-            println!("fizz");
+            outln!("fizz");
         }else if count % 5 == 0 { 
-            println!("buzz")
+            outln!("buzz")
         }else{
-            println!("{}", count);
+            outln!("{}", count);
         }
         count+=1;
     } 
@@ -32,14 +32,21 @@ pub fn run(){
     // For Range loop 
     for x in 0..100{
         if x % 15 == 0 {
-            println!("fizzbuzz");
+            outln!("fizzbuzz");
         }else if x % 3 == 0 {
-            println!("fizz");
+            outln!("fizz");
         }else if x % 5 == 0 { 
-            println!("buzz")
+            outln!("buzz")
         }else{
-            println!("{}", x);
+            outln!("{}", x);
         }
     }
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "loops",
+    title: "loop, while and for",
+    tags: &["basics", "control-flow"],
+    run,
+};
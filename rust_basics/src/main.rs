@@ -1,82 +1,91 @@
-mod arrays;
-mod cli;
-mod closures;
-mod conditionals;
-mod enum_option_ex1;
-mod enum_option_ex2;
-mod enums;
-mod error_handling;
-mod functions;
-mod hashmaps;
-mod iterators;
-mod loops;
-mod match_patterns;
-mod options;
-mod ownership;
 /// # RUST Hands On
 ///
-/// This is a very basic hands on that drives me in my first steps with RUST.
-/// Below you will find modules imports and their corresponding files.
-/// In order to run an example, just uncomment one of the import files and
-/// its corresponding execution in the `main()` function.
+/// Command line entry point of the hands on. Every example module registers itself
+/// in `lessons::ALL`, so there is no need to edit this file to switch topics.
 ///
+/// Usage:
 ///
-/// Example: In order to run the vector example.
+/// - `cargo run -- list`                      lists every lesson with its title and tags
+/// - `cargo run -- run <topic> [<topic>...]`  runs the given lessons, in order
+/// - `cargo run -- run --all`                 runs every lesson
 ///
-/// - Uncomment `mod vectors`
-/// - Uncoment the function call `vectors::run();` in the main function.
+/// Example: In order to run the vector example: `cargo run -- run vectors`
+use std::process::ExitCode;
 
-// import modules=>  mod <path/module_name>
-#[allow(dead_code)]
-mod print;
-mod raw_pointers;
-mod references;
-mod simple_boxed_list;
-mod smart_pointers;
-mod smart_pointers_arc;
-mod smart_pointers_box;
-mod smart_pointers_rc;
-mod smart_pointers_refcell;
-mod strings;
-mod structs;
-mod tuples;
-mod types;
-mod vars;
-mod vectors;
+use rust_basics::lessons::{self, Lesson};
 
-fn main() {
-    // For each test we eill execute the function run() at the specific module.
-    // For examples the 'print' module shows how to print and format
-    // below each module treats a specific basic topic
+const USAGE: &str = "Usage:
+    rust_basics list
+    rust_basics run <topic> [<topic>...]
+    rust_basics run --all";
 
-    //arrays::run();
-    //cli::run();
-    //conditionals::run();
-    closures::run();
-    //enums::run();
-    //enum_options_ex1::run();
-    //enum_option_ex2::run();
-    //error_handling::run();
-    //functions::run();
-    //hashmaps::run();
-    //1iterators::run();
-    //loops::run();
-    //match_patterns::run();
-    //options::run();
-    //ownership::run();
-    //print::run();
-    //raw_pointers::run();
-    //references::run();
-    //simple_boxed_list::run();
-    //strings::run();
-    //smart_pointers::run();
-    //smart_pointers_box::run();
-    //smart_pointers_rc::run();
-    //smart_pointers_arc::run();
-    //smart_pointers_refcell::run();
-    //structs::run();
-    //tuples::run();
-    //types::run();
-    //vars::run();
-    //vectors::run();
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match dispatch(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn dispatch(args: &[String]) -> Result<(), String> {
+    match args.split_first() {
+        Some((command, rest)) if command == "list" => {
+            if !rest.is_empty() {
+                return Err(String::from("'list' does not take arguments"));
+            }
+            list();
+            Ok(())
+        }
+        Some((command, rest)) if command == "run" => {
+            let selected = select(rest)?;
+            for lesson in selected {
+                run(lesson);
+            }
+            Ok(())
+        }
+        Some((command, _)) if command == "help" || command == "--help" || command == "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some((command, _)) => Err(format!("unknown command '{}'", command)),
+        None => Err(String::from("missing command")),
+    }
+}
+
+// Turns the arguments of `run` into the list of lessons to execute.
+fn select(topics: &[String]) -> Result<Vec<&'static Lesson>, String> {
+    if topics.iter().any(|topic| topic == "--all") {
+        if topics.len() > 1 {
+            return Err(String::from("'--all' cannot be combined with topic names"));
+        }
+        return Ok(lessons::ALL.iter().collect());
+    }
+    if topics.is_empty() {
+        return Err(String::from("'run' expects at least one topic, or --all"));
+    }
+    lessons::resolve(topics.iter().map(String::as_str))
+        .map_err(|error| format!("{} (see 'rust_basics list' for the available topics)", error))
+}
+
+fn list() {
+    let width = lessons::ALL.iter().map(|lesson| lesson.name.len()).max().unwrap_or(0);
+    for lesson in lessons::ALL {
+        println!(
+            "{:<width$}  {}  [{}]",
+            lesson.name,
+            lesson.title,
+            lesson.tags.join(", "),
+            width = width
+        );
+    }
+}
+
+fn run(lesson: &Lesson) {
+    println!("=== {}: {} ===", lesson.name, lesson.title);
+    (lesson.run)();
+    println!();
 }
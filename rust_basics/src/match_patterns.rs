@@ -6,17 +6,17 @@
 pub fn run(){ 
 
     // Example 1:
-    println!("Using match: ");
+    outln!("Using match: ");
     let x: Option<i32> = Some(5);
     match x {
         Some(value) => {
-            println!("The value is {}", value);
+            outln!("The value is {}", value);
         },
         None => {
-            println!("The option is empty");
+            outln!("The option is empty");
         }
     }
-    println!("The x is {:?}", x);
+    outln!("The x is {:?}", x);
 
 // the x is the  'expression', the 'value', that we want to match on
 // Some(value) and None are the patterns we want to match our 'x' against on. 
@@ -34,24 +34,31 @@ pub fn run(){
 //   } else {
 //       code to execute if expression does not match Some(pattern)
 //   }
-    println!("Using if let: ");
+    outln!("Using if let: ");
     let x: Option<i32> = Some(15);
     if let Some(value) = x {
-        println!("The value is {}", value);
+        outln!("The value is {}", value);
     } else {
-        println!("The option is empty");
+        outln!("The option is empty");
     }
 
     // example using while let :
     let numbers: Vec<Option<i32>> = vec![Some(1),Some(2),Some(3),Some(4),Some(5)];
-    println!("The Vector is {:?}", numbers);
+    outln!("The Vector is {:?}", numbers);
     let mut index = 0;
     while let Some(x) = numbers[index]  {
-        println!("Vector element is {:?}", x);
+        outln!("Vector element is {:?}", x);
         index += 1;
         if index >= numbers.len() {  //this is just to avoid 'index out of bounds'
             break;
         }
     }
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "match_patterns",
+    title: "match expressions and patterns",
+    tags: &["control-flow", "option"],
+    run,
+};
@@ -0,0 +1,306 @@
+// MyArc<T>: a hand-written Arc<T>
+//
+// The same design as MyRc ('my_rc.rs'): one allocation with the value, a strong count and a weak count
+// (the weak count includes one "+1" shared by all the strong pointers). The difference is that the
+// counters are atomics, so several threads can clone and drop pointers to the same value at the same time.
+//
+// Atomic orderings, the same choices as std::sync::Arc:
+// - clone: Relaxed. A new pointer can only be made from an existing one, which already keeps the value alive,
+//   so there is nothing to synchronize with.
+// - drop: Release on the decrement, and an Acquire fence before dropping the value. Every thread that used
+//   the value "releases" its accesses when it drops its pointer, the last one "acquires" all of them:
+//   the value cannot be freed while another thread is still reading it.
+// - upgrade: a compare-exchange loop, a MyWeak must never bring back a strong count of 0.
+// - get_mut: checking "strong == 1 and weak == 1" with two loads is not enough: between the loads another
+//   thread could upgrade a weak pointer and drop it. So the weak count is "locked" (set to usize::MAX)
+//   while the strong count is checked, and downgrade() waits while it is locked.
+//
+// When the crate is built with `RUSTFLAGS="--cfg loom"`, the atomics come from the loom crate, which runs
+// the tests in 'tests/loom_my_arc.rs' under every possible interleaving of the threads.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+#[cfg(loom)]
+use loom::hint::spin_loop;
+#[cfg(loom)]
+use loom::sync::atomic::{fence, AtomicUsize, Ordering};
+#[cfg(not(loom))]
+use std::hint::spin_loop;
+#[cfg(not(loom))]
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
+
+// Above this count, clone/downgrade abort: reaching it means pointers are leaked in a loop,
+// and the margin up to usize::MAX leaves room for the threads that incremented before checking.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+// Value of the weak count while get_mut() checks the strong count
+const LOCKED: usize = usize::MAX;
+
+struct ArcInner<T> {
+    strong: AtomicUsize,
+    weak: AtomicUsize,
+    value: ManuallyDrop<T>,
+}
+
+/// A thread-safe reference-counted pointer, like `std::sync::Arc`.
+pub struct MyArc<T> {
+    ptr: NonNull<ArcInner<T>>,
+    _marker: PhantomData<ArcInner<T>>,
+}
+
+/// A non-owning pointer to the value of a `MyArc`, like `std::sync::Weak`.
+pub struct MyWeak<T> {
+    ptr: NonNull<ArcInner<T>>,
+}
+
+// SAFETY: like Arc, a MyArc<T> gives shared access to T from several threads (T: Sync),
+// and the last one to drop it may drop T on any thread (T: Send).
+unsafe impl<T: Send + Sync> Send for MyArc<T> {}
+unsafe impl<T: Send + Sync> Sync for MyArc<T> {}
+unsafe impl<T: Send + Sync> Send for MyWeak<T> {}
+unsafe impl<T: Send + Sync> Sync for MyWeak<T> {}
+
+impl<T> MyArc<T> {
+    pub fn new(value: T) -> Self {
+        let boxed = Box::new(ArcInner {
+            strong: AtomicUsize::new(1),
+            weak: AtomicUsize::new(1),
+            value: ManuallyDrop::new(value),
+        });
+        MyArc {
+            // SAFETY: Box::into_raw never returns a null pointer
+            ptr: unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) },
+            _marker: PhantomData,
+        }
+    }
+
+    fn inner(&self) -> &ArcInner<T> {
+        // SAFETY: the allocation lives as long as there is a MyArc
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Number of `MyArc` pointing at the value. Other threads may change it right after the call.
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().strong.load(Ordering::Acquire)
+    }
+
+    /// Number of `MyWeak` pointing at the value. Other threads may change it right after the call.
+    pub fn weak_count(this: &Self) -> usize {
+        let weak = this.inner().weak.load(Ordering::Acquire);
+        // while get_mut() holds the lock there is no MyWeak
+        if weak == LOCKED {
+            0
+        } else {
+            weak - 1
+        }
+    }
+
+    /// Creates a `MyWeak` pointing at the same value.
+    pub fn downgrade(this: &Self) -> MyWeak<T> {
+        let weak = &this.inner().weak;
+        let mut current = weak.load(Ordering::Relaxed);
+        loop {
+            if current == LOCKED {
+                // get_mut() is checking the counts on another thread, it only takes a moment
+                spin_loop();
+                current = weak.load(Ordering::Relaxed);
+                continue;
+            }
+            if current > MAX_REFCOUNT {
+                std::process::abort();
+            }
+            // Acquire pairs with the Release of the unlock in is_unique()
+            match weak.compare_exchange_weak(current, current + 1, Ordering::Acquire, Ordering::Relaxed) {
+                Ok(_) => return MyWeak { ptr: this.ptr },
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Returns true if both pointers point at the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+
+    // True if `this` is the only MyArc and there is no MyWeak.
+    fn is_unique(this: &mut Self) -> bool {
+        let inner = this.inner();
+        // lock the weak count: "1" means no MyWeak, and while it is locked nobody can create one
+        if inner.weak.compare_exchange(1, LOCKED, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+            // Acquire pairs with the Release decrement in drop(): the other MyArc are really gone
+            let unique = inner.strong.load(Ordering::Acquire) == 1;
+            inner.weak.store(1, Ordering::Release);
+            unique
+        } else {
+            false
+        }
+    }
+
+    /// A mutable reference to the value, if this is the only pointer to it (no other `MyArc`, no `MyWeak`).
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if Self::is_unique(this) {
+            // SAFETY: nobody else can reach the value, and `this` is borrowed mutably
+            Some(unsafe { &mut *(*this.ptr.as_ptr()).value })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value if this is the only `MyArc`, otherwise gives the pointer back.
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        // 1 -> 0: from now on no MyWeak can upgrade
+        if this.inner().strong.compare_exchange(1, 0, Ordering::Relaxed, Ordering::Relaxed).is_err() {
+            return Err(this);
+        }
+        // see the accesses made by the threads that dropped their MyArc before
+        fence(Ordering::Acquire);
+
+        let this = ManuallyDrop::new(this);
+        // SAFETY: the strong count is 0, nobody else will read or drop the value
+        let value = unsafe { std::ptr::read(&*this.inner().value) };
+        // give the "+1" of the strong pointers back
+        drop(MyWeak { ptr: this.ptr });
+        Ok(value)
+    }
+}
+
+impl<T: Clone> MyArc<T> {
+    /// A mutable reference to the value, cloning it first if it is shared ("clone on write").
+    /// If only `MyWeak` point at the value, it is moved to a new allocation and the weak pointers
+    /// can no longer be upgraded.
+    pub fn make_mut(this: &mut Self) -> &mut T {
+        let inner = this.inner();
+        if inner.strong.compare_exchange(1, 0, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            // other MyArc share the value: clone it (the assignment drops our pointer to the old one)
+            *this = MyArc::new((**this).clone());
+        } else if inner.weak.load(Ordering::Relaxed) != 1 {
+            // only MyWeak left: they see a strong count of 0 from now on, move the value out
+            // SAFETY: the strong count is 0, nobody else will read or drop the value
+            let value = unsafe { std::ptr::read(&*inner.value) };
+            let old = MyWeak { ptr: this.ptr };
+            // ptr::write: the old MyArc must not run its Drop, `old` gives its "+1" back instead
+            unsafe { std::ptr::write(this, MyArc::new(value)) };
+            drop(old);
+        } else {
+            // we were the only pointer, put the strong count back
+            inner.strong.store(1, Ordering::Release);
+        }
+        // SAFETY: `this` is now the only pointer to its value
+        unsafe { &mut (*this.ptr.as_ptr()).value }
+    }
+}
+
+impl<T> Clone for MyArc<T> {
+    fn clone(&self) -> Self {
+        let old = self.inner().strong.fetch_add(1, Ordering::Relaxed);
+        if old > MAX_REFCOUNT {
+            std::process::abort();
+        }
+        MyArc { ptr: self.ptr, _marker: PhantomData }
+    }
+}
+
+impl<T> Deref for MyArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T> Drop for MyArc<T> {
+    fn drop(&mut self) {
+        if self.inner().strong.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        fence(Ordering::Acquire);
+        // SAFETY: this was the last MyArc, nobody can reach the value anymore
+        unsafe { ManuallyDrop::drop(&mut (*self.ptr.as_ptr()).value) };
+        drop(MyWeak { ptr: self.ptr });
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MyArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MyArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T> MyWeak<T> {
+    fn inner(&self) -> &ArcInner<T> {
+        // SAFETY: the allocation lives as long as there is a MyWeak
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// A new `MyArc` if the value is still alive.
+    pub fn upgrade(&self) -> Option<MyArc<T>> {
+        let strong = &self.inner().strong;
+        let mut current = strong.load(Ordering::Relaxed);
+        loop {
+            if current == 0 {
+                return None;
+            }
+            if current > MAX_REFCOUNT {
+                std::process::abort();
+            }
+            // Acquire pairs with the Release of make_mut(), which puts the strong count back to 1
+            match strong.compare_exchange_weak(current, current + 1, Ordering::Acquire, Ordering::Relaxed) {
+                Ok(_) => return Some(MyArc { ptr: self.ptr, _marker: PhantomData }),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Number of `MyArc` pointing at the value.
+    pub fn strong_count(&self) -> usize {
+        self.inner().strong.load(Ordering::Acquire)
+    }
+
+    /// Number of `MyWeak` pointing at the value, 0 if the value was dropped (same as std).
+    pub fn weak_count(&self) -> usize {
+        let inner = self.inner();
+        let weak = inner.weak.load(Ordering::Acquire);
+        if inner.strong.load(Ordering::Acquire) == 0 {
+            0
+        } else {
+            weak - 1
+        }
+    }
+}
+
+impl<T> Clone for MyWeak<T> {
+    fn clone(&self) -> Self {
+        // there is a MyWeak, so the weak count cannot be locked by get_mut()
+        let old = self.inner().weak.fetch_add(1, Ordering::Relaxed);
+        if old > MAX_REFCOUNT {
+            std::process::abort();
+        }
+        MyWeak { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for MyWeak<T> {
+    fn drop(&mut self) {
+        if self.inner().weak.fetch_sub(1, Ordering::Release) == 1 {
+            fence(Ordering::Acquire);
+            // SAFETY: no MyArc and no MyWeak left: free the allocation (the value was already dropped)
+            unsafe { drop(Box::from_raw(self.ptr.as_ptr())) };
+        }
+    }
+}
+
+impl<T> fmt::Debug for MyWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(MyWeak)")
+    }
+}
@@ -0,0 +1,247 @@
+// MyRc<T>: a hand-written Rc<T>
+//
+// 'smart_pointers_rc.rs' uses Rc::clone and Rc::strong_count. This module shows what happens behind them.
+// A MyRc is a pointer to a heap allocation (RcBox) that holds the value and two counters:
+//
+//      MyRc ──┐
+//      MyRc ──┼──> RcBox { strong: 3, weak: 2, value: T }
+//      MyRc ──┘      ^
+//      MyWeak ───────┘
+//
+// - strong: number of MyRc. When it reaches 0 the value is dropped.
+// - weak: number of MyWeak, PLUS ONE shared by all the MyRc together (as std does).
+//   When it reaches 0 the allocation itself is freed.
+//   Thanks to that extra one, the last MyRc does not need to check whether there are weak pointers:
+//   it drops the value, then gives its "+1" back, which frees the allocation only if no MyWeak is left.
+//
+// A MyWeak does not keep the value alive, only the allocation (so it can still read the counters).
+// upgrade() gives a new MyRc if the value is still alive.
+//
+// The counters are Cell<usize>: they are modified through shared references (&self), in a single thread.
+// MyRc contains a raw pointer (NonNull), so the compiler does not implement Send/Sync for it,
+// exactly like Rc. The thread-safe version with atomic counters is 'my_arc.rs'.
+
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+struct RcBox<T> {
+    strong: Cell<usize>,
+    weak: Cell<usize>,
+    // ManuallyDrop: the value is dropped when the strong count reaches 0, which may happen long before
+    // the allocation is freed (when the weak count reaches 0). Freeing the Box must not drop it again.
+    value: ManuallyDrop<T>,
+}
+
+/// A single-threaded reference-counted pointer, like `std::rc::Rc`.
+pub struct MyRc<T> {
+    ptr: NonNull<RcBox<T>>,
+    // tells the compiler (drop check) that a MyRc owns a T
+    _marker: PhantomData<RcBox<T>>,
+}
+
+/// A non-owning pointer to the value of a `MyRc`, like `std::rc::Weak`.
+pub struct MyWeak<T> {
+    ptr: NonNull<RcBox<T>>,
+}
+
+// Increments a counter, aborting on overflow like std does (the count could only overflow
+// if the pointers were leaked with mem::forget in a loop, and a wrapped count would be a use after free).
+fn increment(count: &Cell<usize>) {
+    let value = count.get().checked_add(1).unwrap_or_else(|| std::process::abort());
+    count.set(value);
+}
+
+impl<T> MyRc<T> {
+    pub fn new(value: T) -> Self {
+        let boxed = Box::new(RcBox {
+            strong: Cell::new(1),
+            // the "+1" of the strong pointers
+            weak: Cell::new(1),
+            value: ManuallyDrop::new(value),
+        });
+        MyRc {
+            // SAFETY: Box::into_raw never returns a null pointer
+            ptr: unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) },
+            _marker: PhantomData,
+        }
+    }
+
+    fn inner(&self) -> &RcBox<T> {
+        // SAFETY: the allocation lives as long as there is a MyRc (the weak count is at least 1)
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Number of `MyRc` pointing at the value.
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().strong.get()
+    }
+
+    /// Number of `MyWeak` pointing at the value (without the "+1" of the strong pointers).
+    pub fn weak_count(this: &Self) -> usize {
+        this.inner().weak.get() - 1
+    }
+
+    /// Creates a `MyWeak` pointing at the same value.
+    pub fn downgrade(this: &Self) -> MyWeak<T> {
+        increment(&this.inner().weak);
+        MyWeak { ptr: this.ptr }
+    }
+
+    /// Returns true if both pointers point at the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+
+    /// A mutable reference to the value, if this is the only pointer to it (no other `MyRc`, no `MyWeak`).
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if Self::strong_count(this) == 1 && Self::weak_count(this) == 0 {
+            // SAFETY: nobody else can reach the value, and `this` is borrowed mutably
+            Some(unsafe { &mut *(*this.ptr.as_ptr()).value })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value if this is the only `MyRc`, otherwise gives the pointer back.
+    /// The `MyWeak` pointers (if any) can no longer be upgraded.
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        if Self::strong_count(&this) != 1 {
+            return Err(this);
+        }
+        // ManuallyDrop: `this` must not run its Drop, the value is moved out instead
+        let this = ManuallyDrop::new(this);
+        let inner = this.inner();
+        // SAFETY: this is the last MyRc, the value is read exactly once and never dropped in place
+        let value = unsafe { std::ptr::read(&*inner.value) };
+        inner.strong.set(0);
+        // give the "+1" of the strong pointers back, this frees the allocation if there is no MyWeak
+        drop(MyWeak { ptr: this.ptr });
+        Ok(value)
+    }
+}
+
+impl<T: Clone> MyRc<T> {
+    /// A mutable reference to the value, cloning it first if it is shared ("clone on write").
+    ///
+    /// - other `MyRc` point at the value: it is cloned into a new allocation, the others keep the old one.
+    /// - only `MyWeak` point at it: the value is moved into a new allocation, the weak pointers
+    ///   can no longer be upgraded.
+    /// - nobody else points at it: the value is modified in place.
+    pub fn make_mut(this: &mut Self) -> &mut T {
+        if Self::strong_count(this) != 1 {
+            // the assignment drops the old pointer, decrementing the strong count of the shared value
+            *this = MyRc::new((**this).clone());
+        } else if Self::weak_count(this) != 0 {
+            let inner = this.inner();
+            // SAFETY: this is the last MyRc, the value is moved out once and the strong count set to 0,
+            // so the remaining MyWeak will neither upgrade nor drop it
+            let value = unsafe { std::ptr::read(&*inner.value) };
+            inner.strong.set(0);
+            inner.weak.set(inner.weak.get() - 1);
+            // ptr::write: the old MyRc must not run its Drop, its counts were already updated above
+            unsafe { std::ptr::write(this, MyRc::new(value)) };
+        }
+        // SAFETY: `this` is now the only pointer to its value
+        unsafe { &mut (*this.ptr.as_ptr()).value }
+    }
+}
+
+impl<T> Clone for MyRc<T> {
+    fn clone(&self) -> Self {
+        increment(&self.inner().strong);
+        MyRc { ptr: self.ptr, _marker: PhantomData }
+    }
+}
+
+impl<T> Deref for MyRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T> Drop for MyRc<T> {
+    fn drop(&mut self) {
+        let inner = self.inner();
+        inner.strong.set(inner.strong.get() - 1);
+        if inner.strong.get() == 0 {
+            // SAFETY: this was the last MyRc, nobody can reach the value anymore
+            unsafe { ManuallyDrop::drop(&mut (*self.ptr.as_ptr()).value) };
+            // give the "+1" of the strong pointers back
+            drop(MyWeak { ptr: self.ptr });
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MyRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MyRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T> MyWeak<T> {
+    fn inner(&self) -> &RcBox<T> {
+        // SAFETY: the allocation lives as long as there is a MyWeak
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// A new `MyRc` if the value is still alive.
+    pub fn upgrade(&self) -> Option<MyRc<T>> {
+        let inner = self.inner();
+        if inner.strong.get() == 0 {
+            return None;
+        }
+        increment(&inner.strong);
+        Some(MyRc { ptr: self.ptr, _marker: PhantomData })
+    }
+
+    /// Number of `MyRc` pointing at the value.
+    pub fn strong_count(&self) -> usize {
+        self.inner().strong.get()
+    }
+
+    /// Number of `MyWeak` pointing at the value, 0 if the value was dropped (same as std).
+    pub fn weak_count(&self) -> usize {
+        let inner = self.inner();
+        if inner.strong.get() > 0 {
+            inner.weak.get() - 1
+        } else {
+            0
+        }
+    }
+}
+
+impl<T> Clone for MyWeak<T> {
+    fn clone(&self) -> Self {
+        increment(&self.inner().weak);
+        MyWeak { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for MyWeak<T> {
+    fn drop(&mut self) {
+        let inner = self.inner();
+        inner.weak.set(inner.weak.get() - 1);
+        if inner.weak.get() == 0 {
+            // SAFETY: no MyRc and no MyWeak left: free the allocation (the value was already dropped)
+            unsafe { drop(Box::from_raw(self.ptr.as_ptr())) };
+        }
+    }
+}
+
+impl<T> fmt::Debug for MyWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(MyWeak)")
+    }
+}
@@ -4,28 +4,28 @@ pub fn run(){
 
     // In Rust we can access an Option value using pattern matching 
     // or the unwrap method.  see the examples: 
-    println!("Examples of accessing Option value:");
-    println!("-----------------------------------");
+    outln!("Examples of accessing Option value:");
+    outln!("-----------------------------------");
     
     // 1) using pattern matching
-    println!("1> using pattern matching:");
+    outln!("1> using pattern matching:");
     let y: Option<i32> = Some(15);
-    println!("The Option is {:?}", y);
+    outln!("The Option is {:?}", y);
     match y {
         Some(extracted_value) => {  
-            println!("The value is {:?}",extracted_value); 
+            outln!("The value is {:?}",extracted_value); 
         },
         None => { 
-            println!("The option is empty");
+            outln!("The option is empty");
         }
     }
 
-    println!("-----------------------------------");
+    outln!("-----------------------------------");
     // 2) using unwrap: 
-    println!("2> using unwrap:");
+    outln!("2> using unwrap:");
     let x: Option<i32> = Some(5);
-    println!("The Option is {:?}", x); 
-    println!("The value is {:?}", x.unwrap()); 
+    outln!("The Option is {:?}", x); 
+    outln!("The value is {:?}", x.unwrap()); 
     // NOTE: accessing the value directly with unwrap is only recommended 
     //       if you're certain the option contains a value
     // ------------------------------------------------------------------
@@ -38,74 +38,81 @@ pub fn run(){
     // - as_ref : returns an Option containing a reference to the value inside 
     // the Option if Option is Some, or None if the Option is None. Example:
    
-    println!("-----------------------------------");
+    outln!("-----------------------------------");
     // 3) using as_ref() 
-    println!("3> using as_ref():");
+    outln!("3> using as_ref():");
     let z: Option<i32> = Some(25);
-    println!("The Option is {:?}", z); 
+    outln!("The Option is {:?}", z); 
 
     let extracted_ref = z.as_ref(); 
     match extracted_ref {
         Some(ref_to_value) => {
-            println!("The value is {:?}", ref_to_value);  
+            outln!("The value is {:?}", ref_to_value);  
         },
         None => {
-            println!("The option is empty");
+            outln!("The option is empty");
         }
     }
 
-    println!("-----------------------------------");
+    outln!("-----------------------------------");
     // using as_mut
     // - as_mut : Similarly, we can use this method to get a mutable reference 
     // to the value inside the Option. 
-    println!("4> using as_mut():");
+    outln!("4> using as_mut():");
     let mut w: Option<i32> = Some(25);
-    println!("The Option is {:?}", w); 
+    outln!("The Option is {:?}", w); 
     let extracted_mut_ref = w.as_mut(); 
     match extracted_mut_ref {
         Some(mut_ref_to_value) => {
-            println!("The value is {:?}", mut_ref_to_value);  
+            outln!("The value is {:?}", mut_ref_to_value);  
             *mut_ref_to_value += 5; 
-            println!("Changed value is {:?}", mut_ref_to_value);
+            outln!("Changed value is {:?}", mut_ref_to_value);
         },
         None => {
-            println!("The option is empty");
+            outln!("The option is empty");
         }
     }
 
-    println!("-----------------------------------");
+    outln!("-----------------------------------");
     // example using an immutable boxed value: 
     // returns an Option containing a reference to the Box inside the Option 
     // if Option is Some, or None if the Option is None.
-    println!("5> using as_ref() with an immutable boxed value:");
+    outln!("5> using as_ref() with an immutable boxed value:");
     let boxed_option : Option<Box<i32>> = Some(Box::new(10));
-    println!("Boxed Option: {:?}", boxed_option);
+    outln!("Boxed Option: {:?}", boxed_option);
     match boxed_option.as_ref() {
         Some(ref_to_box) => {
-            println!("Box Value: {:?}", &ref_to_box);
+            outln!("Box Value: {:?}", &ref_to_box);
         }
         None => {
-            println!("The Option is empty");
+            outln!("The Option is empty");
         }
     }
-    println!("Boxed Option: {:?}", boxed_option);
+    outln!("Boxed Option: {:?}", boxed_option);
 
-    println!("-----------------------------------");
+    outln!("-----------------------------------");
     // example using an mutable boxed value: 
-    println!("6> using as_mut() with a utable boxed value:");
+    outln!("6> using as_mut() with a utable boxed value:");
     let mut boxed_option : Option<Box<i32>> = Some(Box::new(20));
-    println!("Mut Boxed Option: {:?}", boxed_option);
+    outln!("Mut Boxed Option: {:?}", boxed_option);
     match boxed_option.as_mut() {
         Some(mut_ref_to_box) => {
-            println!("Box Value: {:?}", mut_ref_to_box);
+            outln!("Box Value: {:?}", mut_ref_to_box);
             **mut_ref_to_box += 15;
-            println!("Changed Box Value: {:?}", mut_ref_to_box);
+            outln!("Changed Box Value: {:?}", mut_ref_to_box);
         }
         None => {
-            println!("The Option is empty");
+            outln!("The Option is empty");
         }
     }
-    println!("Mut Boxed Option: {:?}", boxed_option);
+    outln!("Mut Boxed Option: {:?}", boxed_option);
 
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "options",
+    title: "Accessing Option values",
+    tags: &["option"],
+    run,
+};
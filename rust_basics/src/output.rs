@@ -0,0 +1,103 @@
+// Output sink used by the lessons
+//
+// The lessons print through the `outln!` / `out!` / `errln!` macros instead of calling
+// `println!` / `print!` / `eprintln!` directly. By default the macros forward to the real
+// stdout/stderr, so `cargo run -- run <topic>` behaves exactly as before.
+// The difference is that the output can be redirected: `capture()` runs a closure with
+// an in-memory sink installed and returns everything it printed. This is what the
+// snapshot tests (tests/snapshots.rs) use to compare each lesson against its golden file.
+//
+// The sink is thread local, so tests running in parallel don't mix their outputs.
+// Note that output printed from a thread spawned by a lesson goes to the real stdout.
+
+use std::cell::RefCell;
+use std::fmt;
+
+/// Everything a closure printed while running under [`capture`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Captured {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Captured>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with an in-memory sink installed on the current thread and returns what it printed.
+///
+/// The previous sink (if any) is restored afterwards, even if `f` panics, so captures can be nested.
+pub fn capture<F: FnOnce()>(f: F) -> Captured {
+    // Puts the previous sink back when dropped, i.e. also while unwinding.
+    struct Restore(Option<Captured>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SINK.with(|sink| *sink.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SINK.with(|sink| sink.borrow_mut().replace(Captured::default()));
+    let _restore = Restore(previous);
+    f();
+    SINK.with(|sink| sink.borrow_mut().take().unwrap_or_default())
+}
+
+#[doc(hidden)]
+pub fn write_out(args: fmt::Arguments) {
+    let captured = SINK.with(|sink| match sink.borrow_mut().as_mut() {
+        Some(captured) => {
+            fmt::Write::write_fmt(&mut captured.stdout, args).expect("formatting into a String");
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        print!("{}", args);
+    }
+}
+
+#[doc(hidden)]
+pub fn write_err(args: fmt::Arguments) {
+    let captured = SINK.with(|sink| match sink.borrow_mut().as_mut() {
+        Some(captured) => {
+            fmt::Write::write_fmt(&mut captured.stderr, args).expect("formatting into a String");
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        eprint!("{}", args);
+    }
+}
+
+/// Same as `print!`, but goes through the lesson output sink.
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write_out(format_args!($($arg)*))
+    };
+}
+
+/// Same as `println!`, but goes through the lesson output sink.
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::output::write_out(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_out(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Same as `eprintln!`, but goes through the lesson output sink.
+#[macro_export]
+macro_rules! errln {
+    () => {
+        $crate::output::write_err(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_err(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
@@ -38,20 +38,20 @@ pub fn run(){
 // The variable is valid from the point at which it’s declared until the end of the current scope.
 
 // -- Ex1: string literals (str):  In this type, the string value is hardcoded into our program at compile time. 
-    println!("-- Example 1: ");
+    outln!("-- Example 1: ");
     {
         let a = "Hello World";
-        println!("Variable a is a string literal: {}" , a);
+        outln!("Variable a is a string literal: {}" , a);
     }
     // uncomment the following line to see that an error ('not found in this scope') will appear because the variable a becomes invalid out of its scope
     // println!("Variable a is a string literal: {}" , a); 
 
 // -- Ex2: String Type: This type manages data allocated on the heap and as such is able to store an amount of text that is unknown to us at compile time.
-    println!("-- Example 2: ");
+    outln!("-- Example 2: ");
     {
         let mut s = String::from("hello");
         s.push_str(", world!"); // push_str() appends a literal to a String
-        println!("String type : {}", s); // This will print `hello, world!`
+        outln!("String type : {}", s); // This will print `hello, world!`
     }
         
 // Why can String be mutated but literals cannot? The difference is how these two types deal with memory.
@@ -69,11 +69,11 @@ pub fn run(){
 
 // - Ex3:  When a heap-allocated variable leaves its scope, rust calls the special function 'Drop'. 
 //         Rust calls drop automatically at the closing curly bracket.
-    println!("-- Example 3: ");
+    outln!("-- Example 3: ");
     {  // start scope
         let mut g = String::from("hello");      // g is valid from this point forward
         g.push_str(", world!");                 // do stuffs with g
-        println!("String type in a scope : {}", g);        
+        outln!("String type in a scope : {}", g);        
     }
     // this scope is now over, and g is no longer valid, uncomment the line to check.
     //println!("String type : {}", g); 
@@ -86,20 +86,20 @@ pub fn run(){
 // - Ex4: Interactions of Variables and Data : MOVE
 //  a) fixed-size simple values such as integers:  5 is assinged to x and a copy of x is assigned to y.
 //     This happens because the values have fixed size and are therefore the value 5 is pushed twice on the stack. 
-    println!("-- Example 4.a: ");
+    outln!("-- Example 4.a: ");
     {
         let x = 5;
         let y = x;
         // in this case, as the value is fixed-size and allocated on the stack, when we assign y with the value of x, 
         // a new copy of the value is created and each copy has a different owner. 
 
-        println!(" x : {} , y: {} ", x, y);
+        outln!(" x : {} , y: {} ", x, y);
     }
 
 //  b)  growable strings: A String is made up of three parts: 
 //                         a pointer to the memory that holds the contents of the string, a length, and a capacity. 
 //      This group of data is stored on the stack and the content itself is stored on the heap.
-    println!("-- Example 4.b: ");
+    outln!("-- Example 4.b: ");
     {
         let s1 = String::from("hello");
         let s2 = s1;    // values on the stack are moved to s2. s1 will be invalidated
@@ -109,25 +109,25 @@ pub fn run(){
         // Therefore, Rust doesn’t need to free anything when s1 goes out of scope.
         // if you uncomment next line you will see an error because, s1 is invalidated and Rust compiler prevents you from using the invalidated reference. 
         //println!("{}, world!", s1);
-        println!("s2 (moved from s1): {}, world!", s2);
+        outln!("s2 (moved from s1): {}, world!", s2);
     }
 // - Ex5: Interacttions of Variables and Data : CLONE
 //        If we do want/need to copy the heap data of the String, not just the stack data, we can use a common method called CLONE.
-    println!("-- Example 5: ");
+    outln!("-- Example 5: ");
     {
         let s1 = String::from("hello");
         let s2 = s1.clone();
 
-        println!("S2 (cloned from S1) -> s1 = {}, s2 = {}", s1, s2);
+        outln!("S2 (cloned from S1) -> s1 = {}, s2 = {}", s1, s2);
     }
 
 // - Ex6: The case of Stack-Only Data. When data is stored only on the stack we will COPY (deep copy) the full data
-    println!("-- Example 6: ");
+    outln!("-- Example 6: ");
     {
         let x = 5;
         let y = x;
 
-        println!("x = {}, y = {}", x, y);
+        outln!("x = {}, y = {}", x, y);
         // as we fully copy the data on the stack , the origin keeps its validity after the assignment and each variable owns a copy.
     }
 
@@ -164,3 +164,10 @@ pub fn run(){
 // 
 
 // Ref: https://hashrust.com/blog/moves-copies-and-clones-in-rust/
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "ownership",
+    title: "Ownership, moves and the stack/heap",
+    tags: &["memory", "ownership"],
+    run,
+};
@@ -0,0 +1,200 @@
+// A persistent (immutable) list with structural sharing
+//
+// 'smart_pointers_box.rs' defines the classic cons list:
+//      enum List<T> { Cons(T, Box<List<T>>), Nil }
+// Every Cons owns the rest of the list through a Box, so two lists can never share a tail:
+// building 'b = Cons(3, a)' moves 'a' into 'b', and keeping 'a' around means copying it.
+//
+// Replacing the Box with an Rc makes the tail shareable. Nothing is ever modified in place:
+// prepend() creates a new node pointing at the current one, tail() returns the next node.
+// Every version of the list stays valid, and versions share their common nodes:
+//
+//      let a = PersistentList::new().prepend(3).prepend(2);      a -> [2] -> [3]
+//      let b = a.prepend(1);                                      b -> [1] ---^
+//      let c = a.prepend(0);                                      c -> [0] ---^
+//      (a, b and c share the nodes 2 and 3)
+//
+// A node is freed when the last list pointing at it is gone.
+// The same list is also available with Arc instead of Rc (ArcPersistentList), to share lists between threads.
+//
+// NOTE: Drop. As for the Box list, the default drop would be recursive (each node drops its 'next' Rc).
+// The Drop implementation walks the list in a loop instead, and stops at the first node that is still
+// shared with another list: that node (and everything after it) is still in use.
+
+// Both lists have the same code, only the pointer type changes, so they are generated by this macro.
+macro_rules! persistent_list {
+    ($(#[$doc:meta])* $list:ident, $iter:ident, $ptr:ident) => {
+        struct Node<T> {
+            elem: T,
+            // length of the list starting at this node
+            len: usize,
+            next: Option<$ptr<Node<T>>>,
+        }
+
+        $(#[$doc])*
+        pub struct $list<T> {
+            head: Option<$ptr<Node<T>>>,
+        }
+
+        impl<T> $list<T> {
+            /// Creates an empty list.
+            pub fn new() -> Self {
+                $list { head: None }
+            }
+
+            /// Returns a new list with `elem` in front of this one, in O(1).
+            /// `self` is not modified and both lists share all of its nodes.
+            pub fn prepend(&self, elem: T) -> Self {
+                let len = self.len() + 1;
+                $list {
+                    head: Some($ptr::new(Node { elem, len, next: self.head.clone() })),
+                }
+            }
+
+            /// Returns the list without its first element (shared with this one), in O(1).
+            /// The tail of an empty list is an empty list.
+            pub fn tail(&self) -> Self {
+                $list {
+                    head: self.head.as_ref().and_then(|node| node.next.clone()),
+                }
+            }
+
+            /// Returns the first element of the list.
+            pub fn head(&self) -> Option<&T> {
+                self.head.as_ref().map(|node| &node.elem)
+            }
+
+            /// Number of elements, in O(1) (every node knows the length of the list it starts).
+            pub fn len(&self) -> usize {
+                self.head.as_ref().map_or(0, |node| node.len)
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.head.is_none()
+            }
+
+            /// Returns true if both lists start at the same node, i.e. they are the same version.
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                match (&self.head, &other.head) {
+                    (Some(a), Some(b)) => $ptr::ptr_eq(a, b),
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
+
+            pub fn iter(&self) -> $iter<'_, T> {
+                $iter { next: self.head.as_deref() }
+            }
+        }
+
+        impl<T> Drop for $list<T> {
+            fn drop(&mut self) {
+                let mut head = self.head.take();
+                while let Some(node) = head {
+                    // into_inner only returns the node to the last owner. Unlike try_unwrap, when two
+                    // threads drop their Arc of the same node at the same time, one of them always gets it:
+                    // with try_unwrap both could fail, and the last Arc dropped would free the rest of the
+                    // list recursively.
+                    match $ptr::into_inner(node) {
+                        Some(mut node) => head = node.next.take(),
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        /// Cloning a list is O(1): the clone shares every node.
+        impl<T> Clone for $list<T> {
+            fn clone(&self) -> Self {
+                $list { head: self.head.clone() }
+            }
+        }
+
+        impl<T> Default for $list<T> {
+            fn default() -> Self {
+                $list::new()
+            }
+        }
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for $list<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for $list<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.ptr_eq(other) || (self.len() == other.len() && self.iter().eq(other.iter()))
+            }
+        }
+
+        impl<T: Eq> Eq for $list<T> {}
+
+        /// The first element of the iterator is the head of the list.
+        impl<T> FromIterator<T> for $list<T> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                // the list is built from its end, so collect the elements first
+                let elems: Vec<T> = iter.into_iter().collect();
+                elems.into_iter().rev().fold($list::new(), |list, elem| list.prepend(elem))
+            }
+        }
+
+        /// Iterator over the elements of a list, from the head.
+        pub struct $iter<'a, T> {
+            next: Option<&'a Node<T>>,
+        }
+
+        impl<'a, T> Iterator for $iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.next.map(|node| {
+                    self.next = node.next.as_deref();
+                    &node.elem
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.next.map_or(0, |node| node.len);
+                (len, Some(len))
+            }
+        }
+
+        impl<T> ExactSizeIterator for $iter<'_, T> {}
+        impl<T> std::iter::FusedIterator for $iter<'_, T> {}
+
+        impl<'a, T> IntoIterator for &'a $list<T> {
+            type Item = &'a T;
+            type IntoIter = $iter<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    };
+}
+
+pub use self::rc::{Iter, PersistentList};
+pub use self::sync::{ArcIter, ArcPersistentList};
+
+mod rc {
+    use std::rc::Rc;
+
+    persistent_list!(
+        /// A persistent list whose nodes are shared with `Rc`, for a single thread.
+        PersistentList,
+        Iter,
+        Rc
+    );
+}
+
+mod sync {
+    use std::sync::Arc;
+
+    persistent_list!(
+        /// A persistent list whose nodes are shared with `Arc`, it can be sent to and shared between threads.
+        ArcPersistentList,
+        ArcIter,
+        Arc
+    );
+}
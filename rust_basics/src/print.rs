@@ -1,23 +1,24 @@
 // 'pub' makes the function accessible publicly 
 // this example shows a glimpse of printing/formatting in Rust
+// NOTE: the examples below call 'outln!' and 'errln!' (see output.rs), which take exactly the same
+// arguments as 'println!' and 'eprintln!' and forward to them, unless the output is being captured by the tests.
 #[allow(dead_code)]
-
 pub fn run() {
 
     // println! is a macro that prints text to the console.
     // Sends the output to the standard output (stdout)
     // Typically used for general output that the user expects to see as normal program operation. 
-    println!("Hello printing from file : print.rs");
+    outln!("Hello printing from file : print.rs");
 
     // Basic formatting
-    println!("Printing using placeholders :  {} is {} !", "Rust", "Fun");
+    outln!("Printing using placeholders :  {} is {} !", "Rust", "Fun");
 
     // Positional arguments: 
-    println!("Printing using positional arguments : {0} is an {1} language and I {2} it !", "Rust", "interesting", "like");
-    println!("Printing using positional arguments : {2} is an {1} language and I {0} it !", "Rust", "interesting", "like");
+    outln!("Printing using positional arguments : {0} is an {1} language and I {2} it !", "Rust", "interesting", "like");
+    outln!("Printing using positional arguments : {2} is an {1} language and I {0} it !", "Rust", "interesting", "like");
 
     // Named arguments:
-    println!{
+    outln!{
         "Printing using named arguments : {language} is an {opinion} programming language and I {feel} it!",
         language="Rust",
         opinion="interesting",
@@ -25,13 +26,13 @@ pub fn run() {
     }
 
     // Placeholder traits:
-    println!("Printing using traits. 20 in Binary: {:b} , Hex: {:x} , Octal: {:o} ", 20 , 20 , 20);
+    outln!("Printing using traits. 20 in Binary: {:b} , Hex: {:x} , Octal: {:o} ", 20 , 20 , 20);
 
     // Placeholder for debug traits:
-    println!("Printing using debug traits: {:?}" , (12, true, "hello"));
+    outln!("Printing using debug traits: {:?}" , (12, true, "hello"));
 
     // Basic Math
-    println!("Printing using basic math:  10 + 10 = {}", 10 + 10);
+    outln!("Printing using basic math:  10 + 10 = {}", 10 + 10);
 
     // eprintln! 
     // eprintln! is another macro used to print text to the console.
@@ -46,6 +47,13 @@ pub fn run() {
     //  The "This is a normal output." will be stored in output.log, 
     //  and the error message "This is an error message." will be stored in error.log
 
-    eprintln!("This message is sent to the standard error (stderr).");
+    errln!("This message is sent to the standard error (stderr).");
+
+}
 
-}
\ No newline at end of file
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "print",
+    title: "Printing and formatting",
+    tags: &["basics", "io"],
+    run,
+};
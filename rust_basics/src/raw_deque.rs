@@ -0,0 +1,592 @@
+// A double-ended queue built on raw pointers
+//
+// 'raw_pointers.rs' only creates a *const i32 from a reference and dereferences it once.
+// This module shows how raw pointers are actually used: to build a data structure the borrow checker
+// cannot express (every node is pointed at by both of its neighbours), while exposing a SAFE API.
+//
+//      head -> [A] <-> [B] <-> [C] <- tail
+//
+// How the nodes are managed:
+// - a node is allocated with Box::new() and turned into a raw pointer with Box::into_raw():
+//   from that moment the Box does not free it anymore, the deque is responsible for it.
+// - the pointers are stored as NonNull<Node<T>>: a *mut that is never null, so Option<NonNull<..>>
+//   is the same size as a plain pointer and "no node" is spelled None instead of null.
+// - when a node is removed, Box::from_raw() turns the pointer back into a Box, which frees it when dropped.
+//
+// The rules that keep the unsafe code sound:
+// - every pointer stored in the deque points to a live node allocated by Box and owned by this deque.
+// - a node is freed exactly once, right after being unlinked, so no pointer to it remains.
+// - references handed out (&T / &mut T) borrow the deque, so the node cannot be freed while they are alive.
+//
+// The tests in 'tests/raw_deque.rs' exercise every unsafe path and are meant to be run under Miri,
+// the interpreter that detects undefined behaviour (use-after-free, double free, leaks, aliasing violations):
+//
+//      rustup +nightly component add miri
+//      cargo +nightly miri test --test raw_deque
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+/// A doubly linked deque with O(1) push/pop at both ends and a cursor for edits in the middle.
+pub struct RawDeque<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // Tells the compiler that the deque owns values of type T (for the drop check and variance),
+    // although it only stores raw pointers to them.
+    _owns: PhantomData<Box<Node<T>>>,
+}
+
+// Raw pointers are neither Send nor Sync, so the compiler does not implement them for us.
+// The deque owns its nodes exactly like a Vec<T> owns its elements, so it can follow the same rules.
+unsafe impl<T: Send> Send for RawDeque<T> {}
+unsafe impl<T: Sync> Sync for RawDeque<T> {}
+
+impl<T> RawDeque<T> {
+    /// Creates an empty deque.
+    pub fn new() -> Self {
+        RawDeque { head: None, tail: None, len: 0, _owns: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds an element in the beginning of the deque.
+    pub fn push_front(&mut self, elem: T) {
+        let node = Self::allocate(elem, None, self.head);
+        match self.head {
+            // SAFETY: 'old' is a live node of this deque
+            Some(old) => unsafe { (*old.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    /// Adds an element at the end of the deque.
+    pub fn push_back(&mut self, elem: T) {
+        let node = Self::allocate(elem, self.tail, None);
+        match self.tail {
+            // SAFETY: 'old' is a live node of this deque
+            Some(old) => unsafe { (*old.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: the head is a live node of this deque
+        self.head.map(|node| unsafe { self.unlink(node) })
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: the tail is a live node of this deque
+        self.tail.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: the node is live and the returned reference borrows the deque
+        self.head.map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: the node is live and the returned reference borrows the deque
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node is live and the returned reference borrows the deque mutably
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node is live and the returned reference borrows the deque mutably
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    /// Removes every element of the deque.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Moves all the elements of `other` at the end of this deque, in O(1).
+    pub fn append(&mut self, other: &mut RawDeque<T>) {
+        let (Some(other_head), Some(other_tail)) = (other.head.take(), other.tail.take()) else {
+            return;
+        };
+        match self.tail {
+            // SAFETY: both nodes are live, 'other' gave up its nodes above
+            Some(tail) => unsafe {
+                (*tail.as_ptr()).next = Some(other_head);
+                (*other_head.as_ptr()).prev = Some(tail);
+            },
+            None => self.head = Some(other_head),
+        }
+        self.tail = Some(other_tail);
+        self.len += std::mem::take(&mut other.len);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { front: self.head, back: self.tail, len: self.len, _borrow: PhantomData }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { front: self.head, back: self.tail, len: self.len, _borrow: PhantomData }
+    }
+
+    /// Cursor on the first element (or on the "ghost" position if the deque is empty).
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { current: self.head, index: 0, deque: self }
+    }
+
+    /// Cursor on the last element (or on the "ghost" position if the deque is empty).
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let index = self.len.saturating_sub(1);
+        CursorMut { current: self.tail, index, deque: self }
+    }
+
+    // Moves the element to the heap and gives up the Box: the deque now owns the allocation.
+    fn allocate(elem: T, prev: Link<T>, next: Link<T>) -> NonNull<Node<T>> {
+        let node = Box::new(Node { elem, prev, next });
+        // SAFETY: Box::into_raw never returns a null pointer
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+
+    // Unlinks the node from its neighbours, frees it and returns its element.
+    //
+    // SAFETY: 'node' must be a live node of this deque. It is freed here, the caller must not use it afterwards.
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        // Box::from_raw takes the ownership of the allocation back, it is freed at the end of this function
+        let node = Box::from_raw(node.as_ptr());
+        match node.prev {
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+        node.elem
+    }
+}
+
+impl<T> Drop for RawDeque<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for RawDeque<T> {
+    fn default() -> Self {
+        RawDeque::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RawDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for RawDeque<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for RawDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RawDeque<T> {}
+
+impl<T> FromIterator<T> for RawDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = RawDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for RawDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+// ------------------------------------------------
+// Iterators
+// ------------------------------------------------
+// 'front' and 'back' walk towards each other, 'len' tells when they have met.
+
+/// Iterator returned by [`RawDeque::iter`].
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _borrow: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.front.map(|node| {
+            self.len -= 1;
+            // SAFETY: the node is live for 'a, the iterator borrows the deque
+            unsafe {
+                self.front = (*node.as_ptr()).next;
+                &(*node.as_ptr()).elem
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| {
+            self.len -= 1;
+            // SAFETY: the node is live for 'a, the iterator borrows the deque
+            unsafe {
+                self.back = (*node.as_ptr()).prev;
+                &(*node.as_ptr()).elem
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Iterator returned by [`RawDeque::iter_mut`].
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _borrow: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.front.map(|node| {
+            self.len -= 1;
+            // SAFETY: the node is live for 'a and every node is yielded at most once,
+            // so the mutable references never alias
+            unsafe {
+                self.front = (*node.as_ptr()).next;
+                &mut (*node.as_ptr()).elem
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| {
+            self.len -= 1;
+            // SAFETY: see next()
+            unsafe {
+                self.back = (*node.as_ptr()).prev;
+                &mut (*node.as_ptr()).elem
+            }
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// Consuming iterator returned by `into_iter()`.
+pub struct IntoIter<T>(RawDeque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for RawDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RawDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RawDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// ------------------------------------------------
+// Cursor
+// ------------------------------------------------
+
+/// A cursor to walk the deque and edit it in the middle in O(1).
+///
+/// The cursor points at an element, or at the "ghost" position that sits between the back and the front:
+/// moving next from the last element (or prev from the first one) lands on the ghost,
+/// moving again wraps around to the other end.
+pub struct CursorMut<'a, T> {
+    // None when the cursor is on the ghost position
+    current: Link<T>,
+    // index of 'current', equal to the length of the deque on the ghost position
+    index: usize,
+    deque: &'a mut RawDeque<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Index of the current element, `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Mutable reference to the current element, `None` on the ghost position.
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the node is live and the reference borrows the cursor
+        self.current.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    /// Moves to the next element (from the ghost position: to the front).
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                // SAFETY: the node is live
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.deque.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Moves to the previous element (from the ghost position: to the back).
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => {
+                // SAFETY: the node is live
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = self.index.checked_sub(1).unwrap_or(self.deque.len);
+            }
+            None => {
+                self.current = self.deque.tail;
+                self.index = self.deque.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Mutable reference to the element after the current one (from the ghost position: the front).
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            // SAFETY: the node is live
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.deque.head,
+        };
+        // SAFETY: the node is live and the reference borrows the cursor
+        next.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    /// Mutable reference to the element before the current one (from the ghost position: the back).
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            // SAFETY: the node is live
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.deque.tail,
+        };
+        // SAFETY: the node is live and the reference borrows the cursor
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    /// Inserts an element after the current one (from the ghost position: at the front).
+    /// The cursor stays on the same element.
+    pub fn insert_after(&mut self, elem: T) {
+        let Some(current) = self.current else {
+            self.deque.push_front(elem);
+            // the ghost position index is the length of the deque
+            self.index = self.deque.len;
+            return;
+        };
+        // SAFETY: 'current' and its next node are live nodes of the deque
+        unsafe {
+            let next = (*current.as_ptr()).next;
+            let node = RawDeque::allocate(elem, Some(current), next);
+            (*current.as_ptr()).next = Some(node);
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(node),
+                None => self.deque.tail = Some(node),
+            }
+        }
+        self.deque.len += 1;
+    }
+
+    /// Inserts an element before the current one (from the ghost position: at the back).
+    /// The cursor stays on the same element.
+    pub fn insert_before(&mut self, elem: T) {
+        let Some(current) = self.current else {
+            self.deque.push_back(elem);
+            self.index = self.deque.len;
+            return;
+        };
+        // SAFETY: 'current' and its previous node are live nodes of the deque
+        unsafe {
+            let prev = (*current.as_ptr()).prev;
+            let node = RawDeque::allocate(elem, prev, Some(current));
+            (*current.as_ptr()).prev = Some(node);
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(node),
+                None => self.deque.head = Some(node),
+            }
+        }
+        self.deque.len += 1;
+        self.index += 1;
+    }
+
+    /// Removes the current element and returns it, the cursor moves to the next element.
+    /// Does nothing on the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        // SAFETY: 'current' is a live node of the deque, the cursor moves away from it before it is freed
+        unsafe {
+            self.current = (*current.as_ptr()).next;
+            Some(self.deque.unlink(current))
+        }
+    }
+
+    /// Splits the deque after the current element: everything after it is moved to the returned deque.
+    /// On the ghost position the whole content is moved.
+    pub fn split_after(&mut self) -> RawDeque<T> {
+        let Some(current) = self.current else {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        };
+        let kept = self.index + 1;
+        // SAFETY: 'current' is a live node of the deque
+        let Some(first_moved) = (unsafe { (*current.as_ptr()).next }) else {
+            return RawDeque::new();
+        };
+        // SAFETY: both nodes are live, the link between them is cut on both sides
+        unsafe {
+            (*current.as_ptr()).next = None;
+            (*first_moved.as_ptr()).prev = None;
+        }
+        let moved = RawDeque {
+            head: Some(first_moved),
+            tail: self.deque.tail,
+            len: self.deque.len - kept,
+            _owns: PhantomData,
+        };
+        self.deque.tail = Some(current);
+        self.deque.len = kept;
+        moved
+    }
+
+    /// Splits the deque before the current element: everything before it is moved to the returned deque.
+    /// On the ghost position the whole content is moved.
+    pub fn split_before(&mut self) -> RawDeque<T> {
+        let Some(current) = self.current else {
+            self.index = 0;
+            return std::mem::take(self.deque);
+        };
+        let moved_len = self.index;
+        // SAFETY: 'current' is a live node of the deque
+        let Some(last_moved) = (unsafe { (*current.as_ptr()).prev }) else {
+            return RawDeque::new();
+        };
+        // SAFETY: both nodes are live, the link between them is cut on both sides
+        unsafe {
+            (*current.as_ptr()).prev = None;
+            (*last_moved.as_ptr()).next = None;
+        }
+        let moved = RawDeque {
+            head: self.deque.head,
+            tail: Some(last_moved),
+            len: moved_len,
+            _owns: PhantomData,
+        };
+        self.deque.head = Some(current);
+        self.deque.len -= moved_len;
+        self.index = 0;
+        moved
+    }
+
+    /// Gives the mutable reference to the deque back, ending the cursor.
+    pub fn into_deque(self) -> &'a mut RawDeque<T> {
+        self.deque
+    }
+}
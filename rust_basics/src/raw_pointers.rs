@@ -12,15 +12,25 @@
 pub fn run(){
 
 let x = 42;
-println!("The original value stored in x is {:?}", &x);
+outln!("The original value stored in x is {:?}", &x);
 //Raw pointers can be created using the & operator or the as keyword.
 let raw_ptr: *const i32 = &x as *const i32;   // The as keyword is used to convert a reference to a raw pointer. 
 
-println!("The value stored in raw_ptr is {:?}", &raw_ptr);
+outln!("The value stored in raw_ptr is {:?}", &raw_ptr);
 let y = unsafe { *raw_ptr };   // dereference of raw pointer is unsafe and requires unsafe function or block
 // dereferencing a raw pointer can cause undefined behavior if the pointer is null or points to invalid memory. 
 // The unsafe keyword signals to the compiler that we're taking responsibility for ensuring that the pointer is valid.
 
-println!("The dereferenced value stored in y is {:?}", y);
+outln!("The dereferenced value stored in y is {:?}", y);
 
-}
\ No newline at end of file
+// NOTE: for a real use of raw pointers, see 'raw_deque.rs': a double-ended queue whose nodes
+// are linked with NonNull<Node<T>> pointers, wrapped in a safe API.
+
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "raw_pointers",
+    title: "Raw pointers and unsafe dereferencing",
+    tags: &["memory", "pointers", "unsafe"],
+    run,
+};
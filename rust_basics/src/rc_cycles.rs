@@ -0,0 +1,115 @@
+// Reference cycles: how Rc can leak memory, and how Weak avoids it
+//
+// A tree where the parent owns its children (Rc) and every child points back to its parent.
+// If the back pointer is also an Rc, parent and children own each other: a reference cycle.
+// Their strong counts never reach 0, so they are never dropped, even after all our variables are gone.
+//
+//      parent ──Rc──> child            parent ──Rc──> child
+//        ^              │                ^              │
+//        └─────Rc───────┘                └────Weak──────┘
+//          cycle: leaked                    freed when `parent` goes away
+//
+// The nodes are TrackedRc ('tracked_rc.rs'), which records every live value: report_leaks()
+// shows which nodes are still alive, with their strong and weak counts.
+
+use std::cell::RefCell;
+
+use crate::tracked_rc::{self, TrackedRc, TrackedWeak};
+
+// The pointer from a child to its parent
+enum Parent {
+    None,
+    Strong(TrackedRc<Node>),
+    Weak(TrackedWeak<Node>),
+}
+
+struct Node {
+    name: &'static str,
+    parent: RefCell<Parent>,
+    children: RefCell<Vec<TrackedRc<Node>>>,
+}
+
+// Creates a root node with two children, pointing back to it with a strong or a weak pointer.
+fn build_tree(weak_parent: bool) -> TrackedRc<Node> {
+    let root = TrackedRc::new("root", Node { name: "root", parent: RefCell::new(Parent::None), children: RefCell::new(vec![]) });
+    for name in ["left", "right"] {
+        let parent = match weak_parent {
+            true => Parent::Weak(TrackedRc::downgrade(&root)),
+            false => Parent::Strong(TrackedRc::clone(&root)),
+        };
+        let child = TrackedRc::new(name, Node { name, parent: RefCell::new(parent), children: RefCell::new(vec![]) });
+        root.children.borrow_mut().push(child);
+    }
+    root
+}
+
+// Name of the parent of a node, if it has one (and, for a Weak pointer, if it is still alive).
+fn parent_name(node: &Node) -> Option<&'static str> {
+    match &*node.parent.borrow() {
+        Parent::None => None,
+        Parent::Strong(parent) => Some(parent.name),
+        Parent::Weak(parent) => parent.upgrade().map(|parent| parent.name),
+    }
+}
+
+fn print_report(checkpoint: &tracked_rc::Checkpoint) {
+    let live = checkpoint.report_leaks();
+    outln!("  {} node(s) alive", live.len());
+    for value in live {
+        outln!("    {}", value);
+    }
+}
+
+#[allow(dead_code)]
+pub fn run() {
+    // 1. children -> parent with Rc
+    outln!("Children pointing to their parent with Rc:");
+    let checkpoint = tracked_rc::checkpoint();
+    let root = build_tree(false);
+    // root: 1 (our variable) + 2 (the children)
+    print_report(&checkpoint);
+    outln!("  parent of 'left': {:?}", parent_name(&root.children.borrow()[0]));
+
+    // keep a Weak pointer, only to show the nodes are still there afterwards
+    let spy = TrackedRc::downgrade(&root);
+    drop(root);
+    outln!("after dropping `root`:");
+    print_report(&checkpoint);
+    // nothing owns the tree anymore, yet it is alive: it is leaked
+    outln!("  the root can still be upgraded: {}", spy.upgrade().is_some());
+
+    // The only way out is to break the cycle by hand, here by taking the children away from the root
+    if let Some(root) = spy.upgrade() {
+        let children = root.children.take();
+        outln!("  breaking the cycle: removed {} children from '{}'", children.len(), root.name);
+    }
+    outln!("after breaking the cycle:");
+    print_report(&checkpoint);
+
+    // 2. children -> parent with Weak
+    outln!("Children pointing to their parent with Weak:");
+    let checkpoint = tracked_rc::checkpoint();
+    let root = build_tree(true);
+    // root: 1 (our variable), and 2 weak pointers (the children)
+    print_report(&checkpoint);
+
+    // a child reaches its parent by upgrading the Weak pointer, while the parent is alive
+    let left = TrackedRc::clone(&root.children.borrow()[0]);
+    outln!("  parent of '{}': {:?}", left.name, parent_name(&left));
+
+    drop(root);
+    outln!("after dropping `root`:");
+    // the root and the right child are gone, `left` is still alive because of our variable
+    print_report(&checkpoint);
+    outln!("  parent of '{}': {:?}", left.name, parent_name(&left));
+    drop(left);
+    outln!("after dropping `left`:");
+    print_report(&checkpoint);
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "rc_cycles",
+    title: "Reference cycles with Rc, and breaking them with Weak",
+    tags: &["smart-pointers"],
+    run,
+};
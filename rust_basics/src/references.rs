@@ -41,19 +41,19 @@ pub fn run(){
     // --------------------------
     // ex1: basics of references, 
     //      suppose we have a variable x that owns a value 42
-    println!("Example 1: basic references (borrowing)");
+    outln!("Example 1: basic references (borrowing)");
     {
         let x: i32 = 42;
-        println!("immutable value x: {:?} ", &x);
+        outln!("immutable value x: {:?} ", &x);
         //  in order to create a reference to x we use the 'ampersand' symbol '&'
         //  The '&x' syntax lets us create a reference that refers to the value of 'x' but does not own it. 
         //  Because it does not own it, the value (it points to) will not be dropped when the reference stops being used.     
         { 
             let r = &x; 
             //  to get the value corresponding to the address pointed by r, we use the operator '*'
-            println!("immutable reference 'r' to the value x: {:?} ", *r); 
+            outln!("immutable reference 'r' to the value x: {:?} ", *r); 
         }  
-        println!("immutable value x: {:?} ", x);
+        outln!("immutable value x: {:?} ", x);
         
     }
     // if we try to change the value it wont be possible because all variable and references above are immutable by default.
@@ -62,20 +62,20 @@ pub fn run(){
     //      by using a mutable reference.
     //      In order to change the value using the reference, both the 
     //      variable owning the value and the reference must be mutable.
-    println!("Example 2: change the value of a variable by using a mutable reference.");
+    outln!("Example 2: change the value of a variable by using a mutable reference.");
     {
         
         let mut z: i32 = 52;
-        println!("create mutable value z : {:?} ", &z); 
+        outln!("create mutable value z : {:?} ", &z); 
         let m = &mut z;
-        println!("create mutable reference 'm' to value z : {:?} ", &m); 
+        outln!("create mutable reference 'm' to value z : {:?} ", &m); 
 
         // to access the value corresponding to the address pointed 
         // by the mutable reference 'm' we use the operator '*'
         // set the value z, by using the mutable reference 'm', to 100
         *m = 100;
-        println!("changed, new value referenced by m: {:?} ", &m);
-        println!("new value of 'z' that is referenced by m: {:?} ", &z);
+        outln!("changed, new value referenced by m: {:?} ", &m);
+        outln!("new value of 'z' that is referenced by m: {:?} ", &z);
     }
     // This makes possible 'm' to mutate the value it borrows from 'z'.
     // --------------------------
@@ -85,7 +85,7 @@ pub fn run(){
     //            you can have no other references to that value.
     //
     //      NOTE: a reference’s scope starts from where it is introduced and continues through the last time that reference is used. 
-    println!("Example 3: Shared and mutable references");
+    outln!("Example 3: Shared and mutable references");
     {
         let mut original_owner_x: i32 = 12;
         
@@ -96,7 +96,7 @@ pub fn run(){
         // (we cannot have other references if we have a mutable reference of a value)
        // let mut_ref_2: &mut i32 = &mut original_owner_x; 
         
-        println!("mut_ref_1 is {}", mut_ref_1);            
+        outln!("mut_ref_1 is {}", mut_ref_1);            
         // the scope of the reference 'mut_ref_1' ends here, because it is the last time it is used.
         
         // println!("mut_ref_2 is {}", mut_ref_2);
@@ -161,4 +161,11 @@ pub fn run(){
 // --------------------------
 
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "references",
+    title: "References and borrowing",
+    tags: &["memory", "ownership", "pointers"],
+    run,
+};
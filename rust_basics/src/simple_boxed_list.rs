@@ -1,3 +1,5 @@
+use crate::linked_list::LinkedList;
+
 
 #[allow(dead_code)]
 pub fn run() {
@@ -18,7 +20,7 @@ pub fn run() {
         let mut new_node = Box::new(Node{value: node_value, next: None});
         // check if head is none, add new node as first node if so, 
         // add node in the beginning of the list, otherwise
-        println!("Creating node: {:?} and adding it into the beginning of the list.", new_node);
+        outln!("Creating node: {:?} and adding it into the beginning of the list.", new_node);
         if head.is_none() {
             head = Some(new_node);
         }else {
@@ -27,14 +29,35 @@ pub fn run() {
         }
     }
 
-    println!("\nFull List : {:?} \n", &head);
+    outln!("\nFull List : {:?} \n", &head);
 
     // Traverse the linked list and print the node values
     let mut aux_node = &head;
     
     while let Some(current_node) = aux_node {
-        println!("Current node: {:?}", &current_node.value);
+        outln!("Current node: {:?}", &current_node.value);
         aux_node = &current_node.next;
     }
 
+    // The same list, now using the generic LinkedList<T> from 'linked_list.rs'.
+    // It wraps the Option<Box<Node>> chain above, so we don't have to repeat the push logic every time.
+    outln!("\nUsing crate::linked_list::LinkedList<i32>:");
+    let mut list = LinkedList::new();
+    for node_value in 1..11 {
+        list.push_front(node_value);
+    }
+    outln!("Full List : {:?} (len: {})", list, list.len());
+    outln!("peek: {:?}", list.peek());
+    outln!("pop_front: {:?}", list.pop_front());
+    list.reverse();
+    outln!("Reversed List : {:?}", list);
+    let doubled: LinkedList<i32> = list.iter().map(|value| value * 2).collect();
+    outln!("Doubled List : {:?}", doubled);
 }
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "simple_boxed_list",
+    title: "A singly linked list of Box<Node>",
+    tags: &["pointers", "smart-pointers", "collections"],
+    run,
+};
@@ -30,17 +30,17 @@
 pub fn run(){
     // ------------------------------------------------------------------
     // This is a basic example that shows how to use a box to store a value on the heap:
-    println!("\nCreating Box<i32> smart pointer: Box::new(1);");
+    outln!("\nCreating Box<i32> smart pointer: Box::new(1);");
     let boxed_int: Box<i32> = Box::new(1);
     // in this case, we can access the data in the box similar to how we would if this data were on the stack.
-    println!("The value of box is: {:?} \n", boxed_int);
+    outln!("The value of box is: {:?} \n", boxed_int);
     // ------------------------------------------------------------------
-    println!("\nCreating Box<Vec<i32>> smart pointer: Box::new(vec![1, 2, 3]);");
+    outln!("\nCreating Box<Vec<i32>> smart pointer: Box::new(vec![1, 2, 3]);");
     let boxed_vec: Box<Vec<i32>> = Box::new(vec![1, 2, 3]); 
-    println!("boxed_vec[0] : {:?}", boxed_vec.get(0)); 
-    println!("boxed_vec[1] : {:?}", boxed_vec.get(1));
-    println!("boxed_vec[2] : {:?}", boxed_vec.get(2));
-    println!("boxed_vec[10] (out of bounds): {:?}", boxed_vec.get(10));
+    outln!("boxed_vec[0] : {:?}", boxed_vec.get(0)); 
+    outln!("boxed_vec[1] : {:?}", boxed_vec.get(1));
+    outln!("boxed_vec[2] : {:?}", boxed_vec.get(2));
+    outln!("boxed_vec[10] (out of bounds): {:?}", boxed_vec.get(10));
     // get => returns a reference to an element or subslice depending on the type of index.
     // If given a position, returns a reference to the element at that position or None if out of bounds.
     // If given a range, returns the subslice corresponding to that range, or None if out of bounds.
@@ -58,4 +58,11 @@ pub fn run(){
     // a box in the recursive type definition.
 
     
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "smart_pointers",
+    title: "Smart pointers overview",
+    tags: &["pointers", "smart-pointers"],
+    run,
+};
@@ -15,6 +15,7 @@ use std::sync::Arc;
 // on the heap as the source Arc, while increasing a reference count. When the last Arc pointer
 //  to a given allocation is destroyed, the value stored in that allocation (often referred to as
 //  “inner value”) is also dropped.
+// ('my_arc.rs' implements a small Arc from scratch, with atomic counters.)
 
 // We have the Truck struct 
 #[derive(Debug)]
@@ -51,23 +52,32 @@ pub fn run(){
 
     let (facility_one, facility_two) = thread.join().unwrap();
 
-    println!("[Arc smart pointer] Facility one: {:?}" , facility_one);
-    println!("[Arc smart pointer] Facility two: {:?}" , facility_two);
+    outln!("[Arc smart pointer] Facility one: {:?}" , facility_one);
+    outln!("[Arc smart pointer] Facility two: {:?}" , facility_two);
 
     // count the number of references with strong_count method
     // note that we need to clone the truck_b again from either facility one or facility two because the ownership of truck_b 
     // was transfered to the thread.  So in order to retrieve truck_b again we clone that from facility one or two.
     let truck_b = Arc::clone(&facility_one[1]);
-    println!("[Arc smart pointer] # of references `truck b` (strong count): {:?}" , Arc::strong_count(&truck_b));
+    outln!("[Arc smart pointer] # of references `truck b` (strong count): {:?}" , Arc::strong_count(&truck_b));
     
     // drop one of the references of 'truck_b', by dropping (closing the scope) of facility_two
     std::mem::drop(facility_two);
 
-    println!("[Arc smart pointer] Facility one after : {:?}" , facility_one);
+    outln!("[Arc smart pointer] Facility one after : {:?}" , facility_one);
     // if we try to print facility_two, as shown in the next line, we will get a compile error because facility_two is now out of scope.
     // println!("Facility two: {:?}" , facility_two);
 
     // count the number of references with strong_count method, after dropping
-    println!("[Arc smart pointer] # of references `truck b` (strong count): {:?}" , Arc::strong_count(&truck_b));
+    outln!("[Arc smart pointer] # of references `truck b` (strong count): {:?}" , Arc::strong_count(&truck_b));
 
-}
\ No newline at end of file
+    // NOTE: 'fleet_arc.rs' shares a whole fleet between threads: dispatcher threads move trucks
+    // between facilities (each one behind a Mutex) without losing or duplicating any truck.
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "smart_pointers_arc",
+    title: "Arc<T>: shared ownership across threads",
+    tags: &["smart-pointers", "concurrency"],
+    run,
+};
@@ -48,20 +48,20 @@
 pub fn run(){
     // ------------------------------------------------------------------
     // Storing a string on the heap
-    println!("Example 1:  Using Box to store a string on the heap:");
+    outln!("Example 1:  Using Box to store a string on the heap:");
     let s = Box::new("hello");
-    println!("s: {:?}", s);
-    println!("s: {:?}", *s);    
+    outln!("s: {:?}", s);
+    outln!("s: {:?}", *s);    
     // we can also use the method deref :
-    println!("s: {:?}", std::ops::Deref::deref(&s));
-    println!{"--------------------------\n"};
+    outln!("s: {:?}", std::ops::Deref::deref(&s));
+    outln!{"--------------------------\n"};
     // ------------------------------------------------------------------
     // In this example, the List enum has two variants: 
     // Cons, which represents a node with a value and a pointer to the next node, 
     // and a Nil, which represents the end of the list.
     // The Cons variant contains a value of type T and a Box<List<T>> 
     // pointing to the next node in the list.
-    println!("Example 2: Using Box for Recursive data structures:");
+    outln!("Example 2: Using Box for Recursive data structures:");
     #[derive(Debug)]
     enum List<T> {
         Cons(T, Box<List<T>>),
@@ -72,18 +72,20 @@ pub fn run(){
     let third = List::Cons(1, second);
 
     let my_list: List<i32> =  third; 
-    println!("my_list => {:?}", my_list);
+    outln!("my_list => {:?}", my_list);
 
     let another_list: List<i32> = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Nil))));
-    println!("another_list => {:?}", another_list);
+    outln!("another_list => {:?}", another_list);
 
     let root: List<i32> = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Cons(3, Box::new(List::Cons(4, Box::new(List::Nil)))))))); 
-    println!("root => {:?}", root);
+    outln!("root => {:?}", root);
 
     // Because the size of the List data structure can be variable depending on the 
     // number of nodes it contains, it is necessary to use Box to store the data on the 
     // heap rather than including it directly in the structure. 
     // This allows the data structure to be allocated and deallocated dynamically at runtime.
+    // NOTE: each Cons owns the rest of the list through its Box, so two lists cannot share the same tail.
+    // 'persistent_list.rs' replaces the Box with an Rc to do exactly that (PersistentList<T>).
     // ------------------------------------------------------------------
     // this example panics due to the fact that we will try to use a value 
     // after moving it. 
@@ -95,7 +97,7 @@ pub fn run(){
     //}
     // ------------------------------------------------------------------
     // another example: now using a node
-    println!{"--------------------------\n"};
+    outln!{"--------------------------\n"};
 
     #[derive(Debug)]
     struct Node {
@@ -105,17 +107,17 @@ pub fn run(){
 
     // set root to new (initalizing list)
     let mut root: Option<Box<Node>>  = None;    
-    println!{"Root=> {:?}", &root};
+    outln!{"Root=> {:?}", &root};
 
     // creating a first node
     let mut new_node = Node { elem : 10, next : None };
-    println!{"ADDING Node1"};
-    println!{"Node1: {:?}", &new_node};
+    outln!{"ADDING Node1"};
+    outln!{"Node1: {:?}", &new_node};
 
     // Use matching pattern to check if root is None or not.
     match root {
         None => {
-            println!("root None, add first");
+            outln!("root None, add first");
             root = Some(Box::new(new_node));
         },
         Some(root_value) => {
@@ -123,32 +125,32 @@ pub fn run(){
             root = Some(Box::new(new_node));
         }
     }
-    println!{"Root=> {:?}\n", &root};
+    outln!{"Root=> {:?}\n", &root};
 
     // Creating a second node
     let mut new_node = Node { elem : 20, next : None };
-    println!{"ADDING Node2"};
-    println!{"Node2: {:?}", &new_node};
+    outln!{"ADDING Node2"};
+    outln!{"Node2: {:?}", &new_node};
 
     match root{
         None => {
-            println!("root None, add first");
+            outln!("root None, add first");
             root = Some(Box::new(new_node));
 
         } ,
         Some(root_value) => {
             new_node.next = Some(root_value);
-            println!{"node: {:?}", &new_node };
+            outln!{"node: {:?}", &new_node };
             root = Some(Box::new(new_node));
         }
     }
-    println!{"Root=> {:?}\n", &root};
+    outln!{"Root=> {:?}\n", &root};
     
    
     // Creating a third node
     let mut new_node = Node { elem : 30, next : None };
-    println!{"ADDING Node3"};
-    println!{"Node3: {:?}", &new_node};
+    outln!{"ADDING Node3"};
+    outln!{"Node3: {:?}", &new_node};
 
     // NOTE: we can optionally use the take() method 
     // on 'root' value which is an Option
@@ -159,15 +161,25 @@ pub fn run(){
     // Note2: here below we dont use the matching pattern as above,
     // in order to access the Option enum directly
     if root.is_none() {
-        println!("root None, add first");
+        outln!("root None, add first");
         root = Some(Box::new(new_node));
     }else{
         new_node.next = root.take();
         // take takes the value of root and leave a none in place
-        println!{"Root: {:?}", &root };
-        println!{"new_node: {:?}\n", &new_node };
+        outln!{"Root: {:?}", &root };
+        outln!{"new_node: {:?}\n", &new_node };
         root = Some(Box::new(new_node));
     }
-    println!{"Root=> {:?}\n", root};
-   
-}
\ No newline at end of file
+    outln!{"Root=> {:?}\n", root};
+
+    // NOTE: the same push logic was repeated three times above.
+    // 'linked_list.rs' wraps it in a generic LinkedList<T> with push_front(), pop_front(), iter(), ...
+    // (see the end of 'simple_boxed_list.rs' for an example).
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "smart_pointers_box",
+    title: "Box<T> and recursive data structures",
+    tags: &["smart-pointers", "collections"],
+    run,
+};
@@ -10,6 +10,8 @@ use std::rc::Rc;
 // If there are zero references to a value, the value can be cleaned up without any references becoming invalid.
 // We use the Rc<T> type when we want to allocate some data on the heap for multiple parts of our program to read 
 // and we can’t determine at compile time which part will finish using the data last. 
+// ('my_rc.rs' implements a small Rc from scratch, to show how the strong and weak counts work.)
+// (Values pointing at each other with Rc are never freed: see the 'rc_cycles' lesson.)
 // 
 // Example: 
 // Supose that we have a struct that represents a truck with his corresponding capacity. 
@@ -37,22 +39,31 @@ pub fn run(){
     let facility_one: Vec<Rc<Truck>> = vec![ Rc::clone(&truck_a), Rc::clone(&truck_b)];
     let facility_two: Vec<Rc<Truck>> = vec![ Rc::clone(&truck_b),  Rc::clone(&truck_c)];
 
-    println!("Facility one: {:?}" , facility_one);
-    println!("Facility two: {:?}" , facility_two);
+    outln!("Facility one: {:?}" , facility_one);
+    outln!("Facility two: {:?}" , facility_two);
 
     // count the number of references with strong_count method
-    println!("# of references `truck b` (strong count): {:?}" , Rc::strong_count(&truck_b));
+    outln!("# of references `truck b` (strong count): {:?}" , Rc::strong_count(&truck_b));
     
     // drop one of the references of 'truck_b', by dropping (closing the scope) of facility_two
     std::mem::drop(facility_two);
 
-    println!("Facility one after : {:?}" , facility_one);
+    outln!("Facility one after : {:?}" , facility_one);
     // if we try to print facility_two, as shown in the next line, 
     // we will get a compile error because facility_two is now out of scope.
     // println!("Facility two: {:?}" , facility_two);
 
     // count the number of references with strong_count method, after dropping
-    println!("# of references `truck b` (strong count): {:?}" , Rc::strong_count(&truck_b));
+    outln!("# of references `truck b` (strong count): {:?}" , Rc::strong_count(&truck_b));
 
+    // NOTE: here the facilities are plain vectors and nothing stops a truck from being in both.
+    // 'fleet_rc.rs' builds a real model out of this example: trucks and facilities with ids,
+    // facilities pointing back to their region with a Weak, assign/unassign and capacity per facility.
+}
 
-}
\ No newline at end of file
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "smart_pointers_rc",
+    title: "Rc<T>: shared ownership in one thread",
+    tags: &["smart-pointers"],
+    run,
+};
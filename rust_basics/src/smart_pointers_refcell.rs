@@ -19,9 +19,9 @@ pub fn run(){
     // we need to declare p1 as mutable 
     {
         let mut p1 = Person { name: String::from("Alice") };
-        println!("name is {:?}",  p1.name );
+        outln!("name is {:?}",  p1.name );
         p1.name = String::from("Bob");
-        println!("name is {:?}",  p1.name );
+        outln!("name is {:?}",  p1.name );
     }
 
     // If we use a RefCell, we can use its Interior Mutability to change the value
@@ -30,10 +30,17 @@ pub fn run(){
         let p1 = AnotherPerson{
             name: RefCell::new(String::from("Charlie"))
         };
-        println!("name [using RefCell] is {:?}",  p1.name.take());
+        outln!("name [using RefCell] is {:?}",  p1.name.take());
         p1.name.replace(String::from("David"));
-        println!("name [using RefCell] is {:?}",  p1.name.take() );
+        outln!("name [using RefCell] is {:?}",  p1.name.take() );
     }
 
     
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "smart_pointers_refcell",
+    title: "RefCell<T> and interior mutability",
+    tags: &["smart-pointers"],
+    run,
+};
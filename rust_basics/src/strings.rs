@@ -15,15 +15,14 @@
 
 
 #[allow(dead_code)]
-
 pub fn run(){
 
     // str type : fixed size
     let hello: &str = "hello ";
     // -> String literals are string slices, which means we can explicitly specify hello’s lifetime as well, such this: 
     // let hello: &'static str = "Hello, world";
-    println!("Length: {} ", hello.len());
-    println!("{}", hello); 
+    outln!("Length: {} ", hello.len());
+    outln!("{}", hello); 
 
      // this wouldn't work, because the size for values of type `str` cannot be known at compilation time 
     // let a: str = "hev";
@@ -49,40 +48,47 @@ pub fn run(){
     //           "any string literal".to_string();
     //           "any string literal".into();
     
-    println!("Length: {}", world.len());
-    println!("{}", world);
+    outln!("Length: {}", world.len());
+    outln!("{}", world);
 
 
     // You can append a char to a String usin 'push'
     world.push('!');
-    println!("{}", world);
+    outln!("{}", world);
     // we can append a slice string (&str) to a String using 'push_str'
     world.push_str(" Welcome to Rust!");
-    println!("{}", world);
+    outln!("{}", world);
 
 
     // CAPACITY in bytes
-    println!("{}", world.capacity());
+    outln!("{}", world.capacity());
     // is EMPTY
-    println!("{}", world.is_empty());
+    outln!("{}", world.is_empty());
     // CONTAINS
-    println!("Contains world hello: {}", world.contains("hello"));
-    println!("Contains world Rust: {}", world.contains("Rust"));
+    outln!("Contains world hello: {}", world.contains("hello"));
+    outln!("Contains world Rust: {}", world.contains("Rust"));
 
     // REPLACE
-    println!("{}", world.replace("Rust", "Java"));
+    outln!("{}", world.replace("Rust", "Java"));
     //LOOP  through string by white space 
     for word in world.split_whitespace(){
-        println!("split : {}", word);
+        outln!("split : {}", word);
     }
     // create string with capacity
     let mut s = String::with_capacity(20);
     s.push('a');
     s.push('b');
-    println!("s: {}", s);
+    outln!("s: {}", s);
     
     assert_eq!(2, s.len());  // this prints nothing because the assertion is true
     //assert_eq!(3, s.len());  // this prints 'assertion failed' because the assertion is false
     assert_eq!(20, s.capacity());  // this prints nothing because the assertion of capacity is correct
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "strings",
+    title: "str and String",
+    tags: &["basics", "strings"],
+    run,
+};
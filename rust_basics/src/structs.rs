@@ -66,26 +66,26 @@ pub fn run(){
         blue: 0,
     };
 
-    println!("Color: {} {} {} " , c.red, c.green, c.blue );   
+    outln!("Color: {} {} {} " , c.red, c.green, c.blue );   
     c.red =200;
-    println!("Color: {} {} {} " , c.red, c.green, c.blue );  
+    outln!("Color: {} {} {} " , c.red, c.green, c.blue );  
     // -------------------------------------------------------
 
     // Ex2: Using tuple struct 
     let mut tuple_color = TupleColor(255,0,0);
-    println!("Tuple Color: {} {} {} " , tuple_color.0, tuple_color.1, tuple_color.2 );  
+    outln!("Tuple Color: {} {} {} " , tuple_color.0, tuple_color.1, tuple_color.2 );  
     tuple_color.0 = 150;
-    println!("Tuple Color: {} {} {} " , tuple_color.0, tuple_color.1, tuple_color.2 );  
+    outln!("Tuple Color: {} {} {} " , tuple_color.0, tuple_color.1, tuple_color.2 );  
 // -------------------------------------------------------
 
     // Ex3: Using structs with associated functions
     let mut p = Person::new("John", "Doe");
-    println!("Person is: {} {} " , p.first_name, p.last_name);
-    println!("Fullname: {}", p.fullname() );
+    outln!("Person is: {} {} " , p.first_name, p.last_name);
+    outln!("Fullname: {}", p.fullname() );
     // changing properties (check method set_last_name)
     p.set_last_name("Willians");
-    println!("Fullname: {}", p.fullname() );
-    println!("Fullname: {:?}", p.to_tuple() );
+    outln!("Fullname: {}", p.fullname() );
+    outln!("Fullname: {:?}", p.to_tuple() );
     // -------------------------------------------------------
     
 // #[derive(Debug)] The println! macro can do many kinds of formatting, and by default, the curly brackets tell println! to use formatting known as Display
@@ -94,4 +94,11 @@ pub fn run(){
 //                  to avoid this error we have either to implement the Display and Debug traits to our struct or (easier solution) 
 //                  we add the outer attribute #[derive(Debug)] just before the struct definition
 //                  REF: https://doc.rust-lang.org/book/ch05-02-example-structs.html#adding-useful-functionality-with-derived-traits
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "structs",
+    title: "Structs and their methods",
+    tags: &["basics", "structs"],
+    run,
+};
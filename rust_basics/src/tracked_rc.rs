@@ -0,0 +1,208 @@
+// TrackedRc<T>: an Rc that knows which of its values are still alive
+//
+// With Rc, memory is freed when the strong count reaches 0. Two values pointing at each other with Rc
+// (a parent owning its children, each child owning its parent) keep each other's count above 0:
+// once our variables are gone nothing can reach them anymore, but they are never freed. A leak.
+//
+// TrackedRc<T> wraps an Rc and records every value it creates in a registry, with a label:
+//
+//      registry (thread local):  id -> (label, type name, a way to read the counts)
+//
+// The entry is removed when the value is dropped. So at any point (a "checkpoint"), the registry lists
+// exactly the values still alive, and report_leaks() prints them with their strong and weak counts.
+// If a value is in the report after every variable that should own it is gone, it is leaked.
+//
+// NOTE: to read the counts, the registry keeps a Weak pointer to every value. That Weak does not keep
+// the value alive, and it is not included in the weak counts shown by TrackedRc / the report.
+// The registry is thread local, like the Rc it tracks (an Rc never leaves its thread).
+
+use std::any::type_name;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::{Rc, Weak};
+
+// Reads the (strong, weak) counts of a registered value, without the Weak of the registry.
+type Counts = Box<dyn Fn() -> (usize, usize)>;
+
+struct Entry {
+    label: String,
+    type_name: &'static str,
+    counts: Counts,
+}
+
+thread_local! {
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+    static REGISTRY: RefCell<BTreeMap<u64, Entry>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+// The value and its id in the registry. Its Drop removes it from the registry.
+struct Tracked<T> {
+    id: u64,
+    value: T,
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        // try_with: the registry may already be gone if the value is dropped while the thread exits
+        let _ = REGISTRY.try_with(|registry| {
+            let entry = registry.borrow_mut().remove(&self.id);
+            drop(entry);
+        });
+    }
+}
+
+/// An `Rc<T>` registered with a label in the registry of live values (see [`report_leaks`]).
+pub struct TrackedRc<T>(Rc<Tracked<T>>);
+
+/// A weak pointer to the value of a [`TrackedRc`].
+pub struct TrackedWeak<T>(Weak<Tracked<T>>);
+
+impl<T: 'static> TrackedRc<T> {
+    pub fn new(label: impl Into<String>, value: T) -> Self {
+        let id = NEXT_ID.with(|next| {
+            next.set(next.get() + 1);
+            next.get()
+        });
+        let rc = Rc::new(Tracked { id, value });
+        let weak = Rc::downgrade(&rc);
+        let entry = Entry {
+            label: label.into(),
+            type_name: type_name::<T>(),
+            counts: Box::new(move || (weak.strong_count(), weak.weak_count().saturating_sub(1))),
+        };
+        REGISTRY.with(|registry| registry.borrow_mut().insert(id, entry));
+        TrackedRc(rc)
+    }
+}
+
+impl<T> TrackedRc<T> {
+    /// Number of `TrackedRc` pointing at the value.
+    pub fn strong_count(this: &Self) -> usize {
+        Rc::strong_count(&this.0)
+    }
+
+    /// Number of `TrackedWeak` pointing at the value (the registry's own Weak is not counted).
+    pub fn weak_count(this: &Self) -> usize {
+        Rc::weak_count(&this.0) - 1
+    }
+
+    pub fn downgrade(this: &Self) -> TrackedWeak<T> {
+        TrackedWeak(Rc::downgrade(&this.0))
+    }
+
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Rc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// The label the value was registered with.
+    pub fn label(this: &Self) -> String {
+        REGISTRY.with(|registry| registry.borrow()[&this.0.id].label.clone())
+    }
+}
+
+impl<T> Clone for TrackedRc<T> {
+    fn clone(&self) -> Self {
+        TrackedRc(Rc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for TrackedRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TrackedRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0.value, f)
+    }
+}
+
+impl<T> TrackedWeak<T> {
+    /// A weak pointer that points at nothing (`upgrade()` returns `None`).
+    pub fn new() -> Self {
+        TrackedWeak(Weak::new())
+    }
+
+    pub fn upgrade(&self) -> Option<TrackedRc<T>> {
+        self.0.upgrade().map(TrackedRc)
+    }
+
+    pub fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+}
+
+impl<T> Default for TrackedWeak<T> {
+    fn default() -> Self {
+        TrackedWeak::new()
+    }
+}
+
+impl<T> Clone for TrackedWeak<T> {
+    fn clone(&self) -> Self {
+        TrackedWeak(Weak::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for TrackedWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(TrackedWeak)")
+    }
+}
+
+/// A value still alive when the report was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveValue {
+    pub id: u64,
+    pub label: String,
+    pub type_name: &'static str,
+    pub strong: usize,
+    pub weak: usize,
+}
+
+impl fmt::Display for LiveValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): strong = {}, weak = {}", self.label, self.type_name, self.strong, self.weak)
+    }
+}
+
+/// A point in time: values created after it can be listed with [`Checkpoint::report_leaks`].
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint {
+    first_id: u64,
+}
+
+/// Marks the current point in time.
+pub fn checkpoint() -> Checkpoint {
+    Checkpoint { first_id: NEXT_ID.with(|next| next.get()) + 1 }
+}
+
+impl Checkpoint {
+    /// Values created since the checkpoint that are still alive, in creation order.
+    pub fn report_leaks(&self) -> Vec<LiveValue> {
+        live_values(self.first_id)
+    }
+}
+
+/// Every value of this thread that is still alive, in creation order.
+pub fn report_leaks() -> Vec<LiveValue> {
+    live_values(0)
+}
+
+fn live_values(first_id: u64) -> Vec<LiveValue> {
+    REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .range(first_id..)
+            .map(|(id, entry)| {
+                let (strong, weak) = (entry.counts)();
+                LiveValue { id: *id, label: entry.label.clone(), type_name: entry.type_name, strong, weak }
+            })
+            .collect()
+    })
+}
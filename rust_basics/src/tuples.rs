@@ -10,12 +10,18 @@
 // Each position in the tuple has a type, and the types of the different values in the tuple don’t have to be the same.
 
 #[allow(dead_code)]
-
 pub fn run(){
 
     let person: (&str, &str, i8)  = ("John" , "Doe", 20);
 
 
-    println!("My first name is {} and my family name is {} and Im {} years old!", person.0, person.1, person.2);
+    outln!("My first name is {} and my family name is {} and Im {} years old!", person.0, person.1, person.2);
+
+}
 
-}
\ No newline at end of file
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "tuples",
+    title: "Tuples",
+    tags: &["basics"],
+    run,
+};
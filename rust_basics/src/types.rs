@@ -83,8 +83,8 @@ pub fn run(){
     // Or a default will be used.
     let _default_integer = 7;   // default `i32`
     let _default_float   = 3.0; // default `f64`
-    println!("Max i32 is {} ", std::i32::MAX);
-    println!("Max i64 is {} ", std::i64::MAX);
+    outln!("Max i32 is {} ", std::i32::MAX);
+    outln!("Max i64 is {} ", std::i64::MAX);
     
     
     // 2) A type can also be inferred from context 
@@ -95,7 +95,7 @@ pub fn run(){
     // 3) A mutable variable's value can be changed.
     let mut _mutable = 12; // Mutable `i32`
     _mutable = 21;
-    println!("{}", _mutable);
+    outln!("{}", _mutable);
     
     // 5) Error! The type of a variable can't be changed.
     //    ex, attributing this:  'mutable = true;' when  the variable was previously declared   'let mut _mutable = 12;' 
@@ -104,9 +104,9 @@ pub fn run(){
     // 6) Variables can be overwritten with shadowing. 
     {
         let _mutable = true;
-        println!("{}", _mutable);
+        outln!("{}", _mutable);
     }
-    println!("{}", _mutable);
+    outln!("{}", _mutable);
     // NOTE:    Variable shadowing occurs when a variable declared within a certain scope (decision block, method, or inner class) has the 
     //          same name as a variable declared in an outer scope. At the level of identifiers (names, rather than variables), this is known 
     //          as name masking. This outer variable is said to be shadowed by the inner variable, while the inner identifier is said to mask 
@@ -118,7 +118,7 @@ pub fn run(){
     let a1 = 'a';
     // let b1 = 'ab'  will give an error!!!! 
     let c1 = '\u{1F600}';     // can be an unicode  \u{<unicode_code>}
-    println!("This is char a1: {} and this is char b1: {} using unicode!" , a1, c1);
+    outln!("This is char a1: {} and this is char b1: {} using unicode!" , a1, c1);
 
 
     // 8) A tuple is a collection of values of different types. Tuples are constructed using parentheses (), and each tuple itself is a value with type 
@@ -127,10 +127,10 @@ pub fn run(){
     // A tuple with a bunch of different types
     let long_tuple = (1u8, 2u16, 3u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true);
     // Tuples are printable
-    println!("long_tuple with different types: {:?}", long_tuple);
+    outln!("long_tuple with different types: {:?}", long_tuple);
     // Values can be extracted from the tuple using tuple indexing
-    println!("long tuple first value: {}", long_tuple.0);
-    println!("long tuple first value: {}", long_tuple.3);
+    outln!("long tuple first value: {}", long_tuple.0);
+    outln!("long tuple first value: {}", long_tuple.3);
     // Tuples can be tuple members
 
     // But long Tuples cannot be printed (max 12 elements?):  Uncomment the 2 lines to see the compiler error
@@ -140,20 +140,27 @@ pub fn run(){
 
     // 9) 
     // Short-circuiting boolean logic
-    println!("true AND false is {}", true && false);
-    println!("true OR false is {}", true || false);
-    println!("NOT true is {}", !true);
+    outln!("true AND false is {}", true && false);
+    outln!("true OR false is {}", true || false);
+    outln!("NOT true is {}", !true);
 
     // 10) Bitwise operations
-    println!("0011 AND 0101 is {:04b}", 0b0011u32 & 0b0101);
-    println!("0011 OR 0101 is {:04b}", 0b0011u32 | 0b0101);
-    println!("0011 XOR 0101 is {:04b}", 0b0011u32 ^ 0b0101);
-    println!("1 << 5 is {}", 1u32 << 5);
-    println!("0x80 >> 2 is 0x{:x}", 0x80u32 >> 2);
+    outln!("0011 AND 0101 is {:04b}", 0b0011u32 & 0b0101);
+    outln!("0011 OR 0101 is {:04b}", 0b0011u32 | 0b0101);
+    outln!("0011 XOR 0101 is {:04b}", 0b0011u32 ^ 0b0101);
+    outln!("1 << 5 is {}", 1u32 << 5);
+    outln!("0x80 >> 2 is 0x{:x}", 0x80u32 >> 2);
 
     // Use underscores to improve readability!
-    println!("One million is written as {}", 1_000_000u32);
+    outln!("One million is written as {}", 1_000_000u32);
 
     
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "types",
+    title: "Primitive and compound data types",
+    tags: &["basics"],
+    run,
+};
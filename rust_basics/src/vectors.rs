@@ -16,62 +16,62 @@ pub fn run(){
     
     let mut numbers: Vec<i32>  = vec![1,2,3,4,5];
 
-    println!("{:?}", numbers);
+    outln!("{:?}", numbers);
     // Reading elements in a vector: 
     // There are two ways to reference a value stored in a vector: 
     //       via indexing or using the get method.
     // get a single value using the index
     // using index gives us a reference to the element at the index value. 
     let element = &numbers[1];
-    println!("Get element at index 1 (using index): {}", element);
-    println!("Get element at index 0 (using index): {}", numbers[0]);
+    outln!("Get element at index 1 (using index): {}", element);
+    outln!("Get element at index 0 (using index): {}", numbers[0]);
 
     // get a single value using get method:
     // When we use the get method with the index passed as an argument, 
     // we get an Option<&T> that we can use with match
-    println!("Get value of a vector using get method: {:?}", numbers.get(3));
+    outln!("Get value of a vector using get method: {:?}", numbers.get(3));
 
     // The reason Rust provides these two ways to reference an element is so you can choose 
     // how the program behaves when you try to use an index value outside the range of existing elements
 
     // get array length (number of elements in the vector)
-    println!("Get Vector length: {}", numbers.len());
+    outln!("Get Vector length: {}", numbers.len());
 
     // the capacity is the number of possible elements in the vector, without re-allocating it 
-    println!("Get Vector capacity: {}", numbers.capacity());
+    outln!("Get Vector capacity: {}", numbers.capacity());
 
     // reassigning values to the vector
     numbers[2] = 20;
-    println!("{:?}", numbers);
+    outln!("{:?}", numbers);
 
     // Add elements in the Vector
     numbers.push(6);
     numbers.push(7);
-    println!("{:?}", numbers);
-    println!("Get Vector length: {}", numbers.len());
+    outln!("{:?}", numbers);
+    outln!("Get Vector length: {}", numbers.len());
 
     // Remove elements from the vector
     numbers.pop();
-    println!("Vector pop {:?}", numbers);
+    outln!("Vector pop {:?}", numbers);
 
     // arrays are stack allocated 
-    println!("Vector_1 occupies {} bytes", std::mem::size_of_val(&numbers));
+    outln!("Vector_1 occupies {} bytes", std::mem::size_of_val(&numbers));
     let other_numbers: Vec<i32>  = vec![1];
-    println!("Vector_2 occupies {} bytes", std::mem::size_of_val(&other_numbers));
+    outln!("Vector_2 occupies {} bytes", std::mem::size_of_val(&other_numbers));
 
     // A slice is a dynamically sized type representing a 'view' into a sequence of elements of type T. 
     // The slice type is written as [T].
     // Slice types are generally used through pointer types.
     // get slice of an array
     let slice1: &[i32] = &numbers[0..2];
-    println!("Vector Slice 1: {:?} ", slice1);
+    outln!("Vector Slice 1: {:?} ", slice1);
     // another slice
     let slice2: &[i32] = &numbers[0..3];
-    println!("Vector Slice 2: {:?} ", slice2);
+    outln!("Vector Slice 2: {:?} ", slice2);
 
     // Loop through vector items
     for x in numbers.iter(){
-        println!("Number {} ", x);
+        outln!("Number {} ", x);
     }
 
     // Loop and mutate values
@@ -79,6 +79,13 @@ pub fn run(){
         *elem = *elem * 2;  // simplified to:   *elem *= 2
         // note: the dereference operator * is used to access the value of a reference
     }
-    println!("Vector x2:  {:?}", numbers);
+    outln!("Vector x2:  {:?}", numbers);
 
-}
\ No newline at end of file
+}
+
+pub const LESSON: crate::lessons::Lesson = crate::lessons::Lesson {
+    name: "vectors",
+    title: "Vec<T>: growable arrays",
+    tags: &["collections"],
+    run,
+};
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::ops::Bound;
+
+use proptest::prelude::*;
+use rust_basics::avl_map::{AvlMap, Entry};
+
+fn assert_valid<K: Ord + Debug, V>(map: &AvlMap<K, V>) {
+    if let Err(error) = map.validate() {
+        panic!("invalid tree: {}", error);
+    }
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut map = AvlMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(2, "two"), None);
+    assert_eq!(map.insert(1, "one"), None);
+    assert_eq!(map.insert(3, "three"), None);
+    assert_eq!(map.insert(2, "TWO"), Some("two"));
+    assert_eq!(map.len(), 3);
+    assert_valid(&map);
+
+    assert_eq!(map.get(&2), Some(&"TWO"));
+    assert_eq!(map.get(&4), None);
+    assert!(map.contains_key(&1));
+    *map.get_mut(&1).unwrap() = "ONE";
+    assert_eq!(map.get_key_value(&1), Some((&1, &"ONE")));
+
+    assert_eq!(map.remove(&2), Some("TWO"));
+    assert_eq!(map.remove(&2), None);
+    assert_eq!(map.remove_entry(&3), Some((3, "three")));
+    assert_eq!(map.len(), 1);
+    assert_valid(&map);
+    assert_eq!(format!("{:?}", map), r#"{1: "ONE"}"#);
+}
+
+#[test]
+fn sorted_insertions_keep_the_tree_balanced() {
+    let map: AvlMap<i32, ()> = (0..1023).map(|key| (key, ())).collect();
+    assert_valid(&map);
+    // a perfectly balanced tree of 1023 nodes has 10 levels, an AVL tree may be a bit higher
+    assert!(map.height() <= 14, "height {}", map.height());
+
+    let map: AvlMap<i32, ()> = (0..1023).rev().map(|key| (key, ())).collect();
+    assert_valid(&map);
+    assert!(map.height() <= 14, "height {}", map.height());
+}
+
+#[test]
+fn string_keys_can_be_looked_up_with_str() {
+    let mut map = AvlMap::new();
+    map.insert(String::from("b"), 2);
+    map.insert(String::from("a"), 1);
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map.remove("b"), Some(2));
+    assert_eq!(map.range::<str, _>((Bound::Included("a"), Bound::Unbounded)).count(), 1);
+}
+
+#[test]
+fn in_order_and_pre_order_iterators() {
+    let map: AvlMap<i32, char> = vec![(2, 'b'), (1, 'a'), (3, 'c')].into_iter().collect();
+    assert_eq!(map.iter().len(), 3);
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(map.values().copied().collect::<String>(), "abc");
+    assert_eq!(map.pre_order().map(|(key, _)| *key).collect::<Vec<_>>(), vec![2, 1, 3]);
+    assert_eq!(map.first_key_value(), Some((&1, &'a')));
+    assert_eq!(map.last_key_value(), Some((&3, &'c')));
+
+    // inserting in pre-order builds the same tree
+    let map: AvlMap<i32, i32> = (0..100).map(|key| (key, key * 2)).collect();
+    let copy: AvlMap<i32, i32> = map.pre_order().map(|(key, value)| (*key, *value)).collect();
+    assert!(map.pre_order().eq(copy.pre_order()));
+    assert_eq!(map, copy);
+}
+
+#[test]
+fn range_queries() {
+    let map: AvlMap<i32, i32> = (0..20).map(|key| (key * 2, key)).collect();
+    let keys = |range: (Bound<i32>, Bound<i32>)| map.range(range).map(|(key, _)| *key).collect::<Vec<_>>();
+
+    assert_eq!(map.range(4..10).map(|(key, _)| *key).collect::<Vec<_>>(), vec![4, 6, 8]);
+    assert_eq!(map.range(3..=10).map(|(key, _)| *key).collect::<Vec<_>>(), vec![4, 6, 8, 10]);
+    assert_eq!(map.range(..3).count(), 2);
+    assert_eq!(map.range(35..).count(), 2);
+    assert_eq!(map.range(..).count(), 20);
+    assert_eq!(keys((Bound::Excluded(4), Bound::Excluded(8))), vec![6]);
+    assert_eq!(keys((Bound::Excluded(4), Bound::Included(4))), Vec::<i32>::new());
+    assert_eq!(map.range(100..200).count(), 0);
+    assert_eq!(map.range(5..5).count(), 0);
+}
+
+#[test]
+fn entry_api() {
+    let mut counts: AvlMap<char, usize> = AvlMap::new();
+    for letter in "mississippi".chars() {
+        *counts.entry(letter).or_insert(0) += 1;
+        assert_valid(&counts);
+    }
+    assert_eq!(format!("{:?}", counts), "{'i': 4, 'm': 1, 'p': 2, 's': 4}");
+
+    counts.entry('m').and_modify(|count| *count += 10).or_default();
+    counts.entry('z').and_modify(|count| *count += 10).or_default();
+    assert_eq!(counts.get(&'m'), Some(&11));
+    assert_eq!(counts.get(&'z'), Some(&0));
+
+    match counts.entry('p') {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.key(), &'p');
+            assert_eq!(entry.insert(20), 2);
+            assert_eq!(entry.get(), &20);
+            assert_eq!(entry.remove(), 20);
+        }
+        Entry::Vacant(_) => panic!("'p' is in the map"),
+    }
+    match counts.entry('q') {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), &'q');
+            assert_eq!(entry.into_key(), 'q');
+        }
+        Entry::Occupied(_) => panic!("'q' is not in the map"),
+    }
+    assert_eq!(counts.keys().collect::<String>(), "imsz");
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(i32, i32),
+    Remove(i32),
+    EntryAdd(i32, i32),
+    EntryRemove(i32),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    // a small key space, so removals and updates hit existing keys
+    let key = -50..50i32;
+    prop_oneof![
+        (key.clone(), any::<i32>()).prop_map(|(key, value)| Op::Insert(key, value)),
+        key.clone().prop_map(Op::Remove),
+        (key.clone(), -100..100i32).prop_map(|(key, value)| Op::EntryAdd(key, value)),
+        key.prop_map(Op::EntryRemove),
+    ]
+}
+
+proptest! {
+    // Runs random sequences of operations on both maps: every result and the final content must be the same.
+    #[test]
+    fn behaves_like_btreemap(ops in prop::collection::vec(op(), 0..200)) {
+        let mut avl = AvlMap::new();
+        let mut reference = BTreeMap::new();
+
+        for op in ops {
+            match op {
+                Op::Insert(key, value) => prop_assert_eq!(avl.insert(key, value), reference.insert(key, value)),
+                Op::Remove(key) => prop_assert_eq!(avl.remove(&key), reference.remove(&key)),
+                Op::EntryAdd(key, value) => {
+                    let a = *avl.entry(key).and_modify(|v| *v = v.wrapping_add(value)).or_insert(value);
+                    let b = *reference.entry(key).and_modify(|v: &mut i32| *v = v.wrapping_add(value)).or_insert(value);
+                    prop_assert_eq!(a, b);
+                }
+                Op::EntryRemove(key) => {
+                    let a = match avl.entry(key) {
+                        Entry::Occupied(entry) => Some(entry.remove_entry()),
+                        Entry::Vacant(_) => None,
+                    };
+                    let b = match reference.entry(key) {
+                        std::collections::btree_map::Entry::Occupied(entry) => Some(entry.remove_entry()),
+                        std::collections::btree_map::Entry::Vacant(_) => None,
+                    };
+                    prop_assert_eq!(a, b);
+                }
+            }
+            prop_assert_eq!(avl.validate(), Ok(()));
+            prop_assert_eq!(avl.len(), reference.len());
+        }
+
+        prop_assert!(avl.iter().eq(reference.iter()));
+        for key in -55..55 {
+            prop_assert_eq!(avl.get(&key), reference.get(&key));
+        }
+    }
+
+    #[test]
+    fn ranges_match_btreemap(
+        keys in prop::collection::vec(-100..100i32, 0..100),
+        start in -110..110i32,
+        end in -110..110i32,
+    ) {
+        let avl: AvlMap<i32, i32> = keys.iter().map(|&key| (key, key)).collect();
+        let reference: BTreeMap<i32, i32> = keys.iter().map(|&key| (key, key)).collect();
+
+        prop_assert!(avl.range(start..).eq(reference.range(start..)));
+        prop_assert!(avl.range(..=end).eq(reference.range(..=end)));
+        if start <= end {
+            prop_assert!(avl.range(start..=end).eq(reference.range(start..=end)));
+            prop_assert!(avl.range(start..end).eq(reference.range(start..end)));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::rc::Rc;
+
+use rust_basics::doubly_linked_list::DoublyLinkedList;
+
+#[test]
+fn push_and_pop_at_both_ends() {
+    let mut list = DoublyLinkedList::new();
+    assert!(list.is_empty());
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.pop_back(), None);
+
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+
+    // the list is still usable once emptied
+    list.push_front(4);
+    assert_eq!(list.pop_back(), Some(4));
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn peek() {
+    let mut list = DoublyLinkedList::new();
+    assert!(list.peek_front().is_none());
+    assert!(list.peek_back().is_none());
+
+    list.push_back(1);
+    assert_eq!(*list.peek_front().unwrap(), 1);
+    assert_eq!(*list.peek_back().unwrap(), 1);
+
+    list.push_back(2);
+    list.push_back(3);
+    *list.peek_front_mut().unwrap() *= 10;
+    *list.peek_back_mut().unwrap() *= 100;
+    assert_eq!(*list.peek_front().unwrap(), 10);
+    assert_eq!(*list.peek_back().unwrap(), 300);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![10, 2, 300]);
+}
+
+#[test]
+fn double_ended_iteration() {
+    let list: DoublyLinkedList<i32> = (1..=5).collect();
+    let mut iter = list.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next_back(), Some(4));
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+
+    let list: DoublyLinkedList<i32> = (1..=3).collect();
+    assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+}
+
+#[test]
+fn dropping_the_list_drops_every_element() {
+    // Each element is an Rc: the list's nodes hold the only other strong reference to it,
+    // so once every node is freed the element counts are back to what the test holds.
+    // (the counts of the nodes themselves are checked in src/doubly_linked_list.rs)
+    let elements: Vec<Rc<i32>> = (0..10).map(Rc::new).collect();
+    let mut list: DoublyLinkedList<Rc<i32>> = elements.iter().cloned().collect();
+    list.push_front(Rc::new(-1));
+    for element in &elements {
+        assert_eq!(Rc::strong_count(element), 2);
+    }
+
+    drop(list);
+    for element in &elements {
+        assert_eq!(Rc::strong_count(element), 1);
+        assert_eq!(Rc::weak_count(element), 0);
+    }
+}
+
+#[test]
+fn popping_frees_the_removed_nodes() {
+    let first = Rc::new("first");
+    let last = Rc::new("last");
+    let mut list = DoublyLinkedList::new();
+    list.push_back(Rc::clone(&first));
+    list.push_back(Rc::new("middle"));
+    list.push_back(Rc::clone(&last));
+
+    let popped = list.pop_front().unwrap();
+    assert_eq!(Rc::strong_count(&first), 2);
+    drop(popped);
+    assert_eq!(Rc::strong_count(&first), 1);
+
+    drop(list.pop_back());
+    assert_eq!(Rc::strong_count(&last), 1);
+    assert_eq!(list.len(), 1);
+}
+
+#[test]
+fn dropping_a_long_list_does_not_overflow_the_stack() {
+    let list: DoublyLinkedList<u32> = (0..1_000_000).collect();
+    assert_eq!(list.len(), 1_000_000);
+    drop(list);
+}
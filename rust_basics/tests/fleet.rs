@@ -0,0 +1,151 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+use rust_basics::fleet_arc;
+use rust_basics::fleet_rc::{Fleet, FleetError};
+
+#[test]
+fn assign_unassign_and_capacity() {
+    let mut fleet = Fleet::new();
+    let region = fleet.add_region("North");
+    let depot = fleet.add_facility(region, "Depot").unwrap();
+    let harbour = fleet.add_facility(region, "Harbour").unwrap();
+    let small = fleet.add_truck(2);
+    let big = fleet.add_truck(10);
+
+    assert_eq!(fleet.add_facility(999, "Nowhere"), Err(FleetError::UnknownRegion(999)));
+    assert_eq!(fleet.assign(999, depot), Err(FleetError::UnknownTruck(999)));
+    assert_eq!(fleet.assign(small, 999), Err(FleetError::UnknownFacility(999)));
+
+    fleet.assign(small, depot).unwrap();
+    fleet.assign(big, depot).unwrap();
+    assert_eq!(fleet.total_capacity(depot), Ok(12));
+    assert_eq!(fleet.total_capacity(harbour), Ok(0));
+    assert_eq!(fleet.facility_of(big), Ok(Some(depot)));
+
+    // a truck is in one facility at most
+    assert_eq!(fleet.assign(big, harbour), Err(FleetError::AlreadyAssigned { truck: big, facility: depot }));
+    fleet.move_truck(big, harbour).unwrap();
+    assert_eq!(fleet.total_capacity(depot), Ok(2));
+    assert_eq!(fleet.total_capacity(harbour), Ok(10));
+
+    assert_eq!(fleet.unassign(small), Ok(depot));
+    assert_eq!(fleet.unassign(small), Err(FleetError::NotAssigned(small)));
+    assert_eq!(fleet.unassigned_trucks(), vec![small]);
+    assert_eq!(fleet.capacity_report().into_iter().collect::<Vec<_>>(), vec![(depot, 0), (harbour, 10)]);
+
+    // moving an unassigned truck assigns it
+    fleet.move_truck(small, harbour).unwrap();
+    assert_eq!(fleet.total_capacity(harbour), Ok(12));
+    assert!(fleet.unassigned_trucks().is_empty());
+}
+
+#[test]
+fn facilities_share_trucks_and_point_back_to_their_region() {
+    let mut fleet = Fleet::new();
+    let region = fleet.add_region("North");
+    let depot = fleet.add_facility(region, "Depot").unwrap();
+    let truck = fleet.add_truck(3);
+
+    let shared = fleet.truck(truck).unwrap();
+    assert_eq!(Rc::strong_count(&shared), 2); // the fleet and `shared`
+    fleet.assign(truck, depot).unwrap();
+    assert_eq!(Rc::strong_count(&shared), 3);
+    assert!(Rc::ptr_eq(&shared, &fleet.facility(depot).unwrap().borrow().trucks()[0]));
+    fleet.unassign(truck).unwrap();
+    assert_eq!(Rc::strong_count(&shared), 2);
+
+    let facility = fleet.facility(depot).unwrap();
+    assert_eq!(facility.borrow().region().unwrap().name, "North");
+    assert_eq!(fleet.region(region).unwrap().facility_ids(), vec![depot]);
+
+    // the back-reference is Weak: it does not keep the region alive
+    drop(fleet);
+    assert!(facility.borrow().region().is_none());
+}
+
+fn arc_fleet(facilities: usize, trucks: u32) -> (fleet_arc::Fleet, Vec<u32>) {
+    let mut fleet = fleet_arc::Fleet::new();
+    let region = fleet.add_region("South");
+    let ids: Vec<_> = (0..facilities).map(|i| fleet.add_facility(region, &format!("F{}", i)).unwrap()).collect();
+    for capacity in 1..=trucks {
+        let truck = fleet.add_truck(capacity);
+        fleet.assign(truck, ids[capacity as usize % facilities]).unwrap();
+    }
+    (fleet, ids)
+}
+
+#[test]
+fn arc_fleet_moves_are_checked() {
+    let mut fleet = fleet_arc::Fleet::new();
+    let region = fleet.add_region("South");
+    let a = fleet.add_facility(region, "A").unwrap();
+    let b = fleet.add_facility(region, "B").unwrap();
+    let truck = fleet.add_truck(5);
+
+    assert_eq!(fleet.snapshot(), vec![(truck, None)]);
+    assert_eq!(fleet.move_truck(truck, a, b), Err(FleetError::NotAtFacility { truck, facility: a }));
+    fleet.assign(truck, a).unwrap();
+    assert_eq!(fleet.assign(truck, b), Err(FleetError::AlreadyAssigned { truck, facility: a }));
+    assert_eq!(fleet.assign(truck, a), Err(FleetError::AlreadyAssigned { truck, facility: a }));
+    assert_eq!(fleet.move_truck(truck, a, a), Ok(()));
+    fleet.move_truck(truck, a, b).unwrap();
+    assert_eq!(fleet.total_capacity(b), Ok(5));
+    assert_eq!(fleet.facility(b).unwrap().truck_ids(), vec![truck]);
+    assert_eq!(fleet.unassign(truck, a), Err(FleetError::NotAtFacility { truck, facility: a }));
+    fleet.unassign(truck, b).unwrap();
+    assert_eq!(fleet.move_any(a, b, |_| 0), Ok(None));
+    assert_eq!(fleet.audit(), Ok(()));
+    assert_eq!(fleet.facility(a).unwrap().region().unwrap().name, "South");
+}
+
+#[test]
+fn concurrent_dispatchers_neither_lose_nor_duplicate_trucks() {
+    let (fleet, facilities) = arc_fleet(5, 50);
+    let fleet = Arc::new(fleet);
+    let total = |fleet: &fleet_arc::Fleet| facilities.iter().map(|id| fleet.total_capacity(*id).unwrap()).sum::<u32>();
+    let before = total(&fleet);
+
+    // an auditor checks consistent snapshots while the dispatchers are running
+    let auditor = {
+        let fleet = Arc::clone(&fleet);
+        thread::spawn(move || {
+            for _ in 0..200 {
+                fleet.audit().unwrap();
+                assert_eq!(fleet.snapshot().len(), 50);
+            }
+        })
+    };
+    let moved = fleet_arc::dispatch(&fleet, 8, 2_000, 7);
+    auditor.join().unwrap();
+
+    assert!(moved > 0);
+    assert_eq!(fleet.audit(), Ok(()));
+    assert_eq!(total(&fleet), before);
+    assert!(fleet.snapshot().iter().all(|(_, place)| place.is_some()));
+}
+
+#[test]
+fn opposite_moves_do_not_deadlock() {
+    // two threads moving trucks in opposite directions between the same facilities
+    let (fleet, facilities) = arc_fleet(2, 20);
+    let fleet = Arc::new(fleet);
+    let (a, b) = (facilities[0], facilities[1]);
+    let handles: Vec<_> = [(a, b), (b, a)]
+        .into_iter()
+        .map(|(from, to)| {
+            let fleet = Arc::clone(&fleet);
+            thread::spawn(move || {
+                for i in 0..5_000 {
+                    fleet.move_any(from, to, |_| i).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(fleet.audit(), Ok(()));
+    assert_eq!(fleet.total_capacity(a).unwrap() + fleet.total_capacity(b).unwrap(), (1..=20).sum());
+}
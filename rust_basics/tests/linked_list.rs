@@ -0,0 +1,121 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use rust_basics::linked_list::LinkedList;
+
+#[test]
+fn push_pop_peek() {
+    let mut list = LinkedList::new();
+    assert!(list.is_empty());
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.peek(), None);
+
+    list.push_front(1);
+    list.push_front(2);
+    list.push_front(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.peek(), Some(&3));
+
+    if let Some(first) = list.peek_mut() {
+        *first = 30;
+    }
+    assert_eq!(list.pop_front(), Some(30));
+    assert_eq!(list.pop_front(), Some(2));
+    list.push_front(4);
+    assert_eq!(list.pop_front(), Some(4));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn from_iter_and_extend_keep_the_order() {
+    let mut list: LinkedList<i32> = (1..=3).collect();
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    list.extend(vec![4, 5]);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+    let mut empty = LinkedList::new();
+    empty.extend(Vec::<i32>::new());
+    assert!(empty.is_empty());
+    empty.extend(Some(7));
+    assert_eq!(empty.peek(), Some(&7));
+}
+
+#[test]
+fn extend_counts_the_elements_added_before_a_panic() {
+    let mut list: LinkedList<i32> = (1..=3).collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.extend((4..).map(|value| if value == 6 { panic!("no more values") } else { value }));
+    }));
+    assert!(result.is_err());
+    // 4 and 5 were linked before the panic: they are counted
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.iter().len(), 5);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn reverse() {
+    let mut list: LinkedList<i32> = (1..=5).collect();
+    list.reverse();
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+    assert_eq!(list.len(), 5);
+
+    let mut empty: LinkedList<i32> = LinkedList::new();
+    empty.reverse();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn iterators() {
+    let mut list: LinkedList<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next().map(String::as_str), Some("a"));
+    assert_eq!(iter.len(), 2);
+
+    for value in list.iter_mut() {
+        value.push('!');
+    }
+    for value in &mut list {
+        value.push('?');
+    }
+    let borrowed: Vec<&String> = (&list).into_iter().collect();
+    assert_eq!(borrowed, ["a!?", "b!?", "c!?"]);
+
+    let mut owned = list.into_iter();
+    assert_eq!(owned.len(), 3);
+    assert_eq!(owned.next().as_deref(), Some("a!?"));
+    assert_eq!(owned.collect::<Vec<_>>(), ["b!?", "c!?"]);
+}
+
+#[test]
+fn debug_clone_and_eq() {
+    let list: LinkedList<i32> = (1..=3).collect();
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+    assert_eq!(format!("{:?}", LinkedList::<i32>::new()), "[]");
+
+    let mut copy = list.clone();
+    assert_eq!(copy, list);
+    copy.push_front(0);
+    assert_ne!(copy, list);
+    assert_eq!(copy.len(), 4);
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn dropping_a_long_list_does_not_overflow_the_stack() {
+    let mut list = LinkedList::new();
+    for value in 0..1_000_000 {
+        list.push_front(value);
+    }
+    assert_eq!(list.len(), 1_000_000);
+    drop(list);
+
+    let mut list: LinkedList<u32> = (0..1_000_000).collect();
+    list.clear();
+    assert!(list.is_empty());
+}
@@ -0,0 +1,149 @@
+// Model checking of MyArc with loom.
+//
+// loom runs every test closure many times, once for each possible interleaving of the threads (and of the
+// values the atomic loads may return under the C++ memory model). The value inside the MyArc is a
+// loom UnsafeCell: loom reports an error if two accesses to it are not ordered by the atomics of MyArc.
+//
+// These tests only build with the loom cfg, and are best run in release mode:
+//
+//      RUSTFLAGS="--cfg loom" cargo test --release --test loom_my_arc
+
+#![cfg(loom)]
+
+use loom::cell::UnsafeCell;
+use loom::sync::atomic::{AtomicUsize, Ordering};
+use loom::sync::Arc;
+use loom::thread;
+
+use rust_basics::my_arc::MyArc;
+
+// Writes to its cell when dropped: a drop that is not ordered after the reads of other threads is a race.
+struct Value {
+    cell: UnsafeCell<usize>,
+    drops: Arc<AtomicUsize>,
+}
+
+impl Value {
+    fn new(drops: &Arc<AtomicUsize>) -> Self {
+        Value { cell: UnsafeCell::new(1), drops: Arc::clone(drops) }
+    }
+
+    fn read(&self) -> usize {
+        self.cell.with(|value| unsafe { *value })
+    }
+}
+
+impl Drop for Value {
+    fn drop(&mut self) {
+        self.cell.with_mut(|value| unsafe { *value = 0 });
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn last_drop_drops_the_value_once() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let a = MyArc::new(Value::new(&drops));
+        let b = a.clone();
+
+        let thread = thread::spawn(move || {
+            assert_eq!(b.read(), 1);
+            drop(b);
+        });
+        assert_eq!(a.read(), 1);
+        drop(a);
+        thread.join().unwrap();
+
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn upgrade_races_with_the_last_drop() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let a = MyArc::new(Value::new(&drops));
+        let weak = MyArc::downgrade(&a);
+
+        let thread = thread::spawn(move || match weak.upgrade() {
+            // an upgraded pointer always sees a live value
+            Some(upgraded) => assert_eq!(upgraded.read(), 1),
+            None => assert_eq!(weak.strong_count(), 0),
+        });
+        drop(a);
+        thread.join().unwrap();
+
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn get_mut_races_with_downgrade_and_upgrade() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut a = MyArc::new(Value::new(&drops));
+        let b = a.clone();
+
+        // the other thread turns its strong pointer into a weak one, then tries to get it back
+        let thread = thread::spawn(move || {
+            let weak = MyArc::downgrade(&b);
+            drop(b);
+            if let Some(upgraded) = weak.upgrade() {
+                upgraded.read();
+            }
+        });
+        // get_mut only succeeds when the other thread has no pointer that could read the value
+        if let Some(value) = MyArc::get_mut(&mut a) {
+            value.cell.with_mut(|value| unsafe { *value += 1 });
+        }
+        thread.join().unwrap();
+        drop(a);
+
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn make_mut_races_with_upgrade() {
+    loom::model(|| {
+        let mut a = MyArc::new(5usize);
+        let weak = MyArc::downgrade(&a);
+
+        let thread = thread::spawn(move || weak.upgrade().map(|upgraded| *upgraded));
+        *MyArc::make_mut(&mut a) += 1;
+        let seen = thread.join().unwrap();
+
+        // the other thread either upgraded before make_mut (and saw the old value, which make_mut
+        // cloned), or after (and could not upgrade: make_mut moved the value away from the weak pointers)
+        assert!(seen.is_none() || seen == Some(5));
+        assert_eq!(*a, 6);
+        assert_eq!(MyArc::strong_count(&a), 1);
+        assert_eq!(MyArc::weak_count(&a), 0);
+    });
+}
+
+#[test]
+fn try_unwrap_races_with_the_other_drop() {
+    loom::model(|| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let a = MyArc::new(Value::new(&drops));
+        let b = a.clone();
+
+        let thread = thread::spawn(move || {
+            b.read();
+            drop(b);
+        });
+        // fails while the other thread still has its pointer, succeeds once it dropped it
+        let first_try = MyArc::try_unwrap(a);
+        thread.join().unwrap();
+        let value = match first_try {
+            Ok(value) => value,
+            Err(a) => MyArc::try_unwrap(a).ok().unwrap(),
+        };
+        assert_eq!(value.read(), 1);
+        drop(value);
+
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
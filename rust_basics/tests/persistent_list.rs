@@ -0,0 +1,129 @@
+use std::rc::Rc;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use rust_basics::persistent_list::{ArcPersistentList, PersistentList};
+
+#[test]
+fn prepend_head_and_tail() {
+    let empty: PersistentList<i32> = PersistentList::new();
+    assert!(empty.is_empty());
+    assert_eq!(empty.head(), None);
+    assert!(empty.tail().is_empty());
+
+    let list = empty.prepend(3).prepend(2).prepend(1);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.head(), Some(&1));
+    assert_eq!(list.tail().head(), Some(&2));
+    assert_eq!(list.tail().tail().tail(), empty);
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+
+    // older versions are not modified
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn iteration_and_collect_keep_the_order() {
+    let list: PersistentList<i32> = (1..=4).collect();
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(list.iter().len(), 4);
+    let doubled: Vec<i32> = (&list).into_iter().map(|x| x * 2).collect();
+    assert_eq!(doubled, vec![2, 4, 6, 8]);
+}
+
+#[test]
+fn versions_share_their_tail() {
+    let shared = PersistentList::new().prepend(3).prepend(2);
+    let b = shared.prepend(1);
+    let c = shared.prepend(0);
+
+    assert!(b.tail().ptr_eq(&shared));
+    assert!(c.tail().ptr_eq(&shared));
+    assert!(b.tail().ptr_eq(&c.tail()));
+    assert_ne!(b, c);
+    assert_eq!(b.tail(), c.tail());
+
+    // a clone is the same version
+    assert!(b.clone().ptr_eq(&b));
+}
+
+#[test]
+fn a_shared_tail_stays_alive_until_both_lists_are_gone() {
+    let elem = Rc::new("shared");
+    let a = PersistentList::new().prepend(Rc::clone(&elem));
+    let b = a.prepend(Rc::new("b"));
+    let c = a.prepend(Rc::new("c"));
+    drop(a);
+
+    // the node holding 'elem' is still reachable from b and c
+    assert_eq!(Rc::strong_count(&elem), 2);
+    assert_eq!(c.tail().head().map(|e| **e), Some("shared"));
+
+    drop(b);
+    assert_eq!(Rc::strong_count(&elem), 2);
+    assert_eq!(c.tail().head().map(|e| **e), Some("shared"));
+
+    drop(c);
+    assert_eq!(Rc::strong_count(&elem), 1);
+}
+
+#[test]
+fn dropping_a_long_list_does_not_overflow_the_stack() {
+    let mut list = PersistentList::new();
+    for value in 0..1_000_000 {
+        list = list.prepend(value);
+    }
+    let tail = list.tail();
+    drop(list);
+    assert_eq!(tail.len(), 999_999);
+    drop(tail);
+
+    let list: ArcPersistentList<u32> = (0..1_000_000).collect();
+    drop(list);
+}
+
+// Two threads drop the last two lists of the same long tail at the same time: exactly one of them must
+// go on freeing the nodes in its loop, otherwise the last Arc would free the list recursively.
+#[test]
+fn dropping_two_clones_at_the_same_time_does_not_overflow_the_stack() {
+    for _ in 0..5 {
+        let list: ArcPersistentList<u32> = (0..1_000_000).collect();
+        let start = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = [list.clone(), list]
+            .into_iter()
+            .map(|list| {
+                let start = Arc::clone(&start);
+                thread::spawn(move || {
+                    start.wait();
+                    drop(list);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
+
+#[test]
+fn arc_lists_can_be_shared_between_threads() {
+    let elem = Arc::new(0);
+    let base = ArcPersistentList::new().prepend(Arc::clone(&elem));
+
+    let handles: Vec<_> = (1..=4)
+        .map(|i| {
+            let base = base.clone();
+            thread::spawn(move || {
+                let mine = base.prepend(Arc::new(i));
+                assert!(mine.tail().ptr_eq(&base));
+                mine.iter().map(|value| **value).sum::<i32>()
+            })
+        })
+        .collect();
+    let sums: Vec<i32> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(sums, vec![1, 2, 3, 4]);
+
+    assert_eq!(Arc::strong_count(&elem), 2);
+    drop(base);
+    assert_eq!(Arc::strong_count(&elem), 1);
+}
@@ -0,0 +1,243 @@
+// Tests for the raw pointer deque.
+//
+// They go through every path of the unsafe code (allocation, unlinking, cursor edits, splits, drop)
+// and are kept small on purpose so they also run under Miri, which checks them for undefined behaviour:
+//
+//      cargo +nightly miri test --test raw_deque
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rust_basics::raw_deque::RawDeque;
+
+fn contents<T: Clone>(deque: &RawDeque<T>) -> Vec<T> {
+    deque.iter().cloned().collect()
+}
+
+#[test]
+fn push_and_pop_at_both_ends() {
+    let mut deque = RawDeque::new();
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+
+    deque.push_back(2);
+    deque.push_front(1);
+    deque.push_back(3);
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.front(), Some(&1));
+    assert_eq!(deque.back(), Some(&3));
+
+    *deque.front_mut().unwrap() += 10;
+    *deque.back_mut().unwrap() += 30;
+    assert_eq!(contents(&deque), vec![11, 2, 33]);
+
+    assert_eq!(deque.pop_front(), Some(11));
+    assert_eq!(deque.pop_back(), Some(33));
+    assert_eq!(deque.pop_back(), Some(2));
+    assert_eq!(deque.pop_front(), None);
+    assert!(deque.is_empty());
+
+    deque.push_front(4);
+    assert_eq!(deque.front(), deque.back());
+    assert_eq!(deque.pop_back(), Some(4));
+}
+
+#[test]
+fn iterators_in_both_directions() {
+    let mut deque: RawDeque<i32> = (1..=5).collect();
+
+    let mut iter = deque.iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    for value in deque.iter_mut().rev() {
+        *value *= 10;
+    }
+    assert_eq!(format!("{:?}", deque), "[10, 20, 30, 40, 50]");
+
+    let mut owned = deque.into_iter();
+    assert_eq!(owned.next_back(), Some(50));
+    assert_eq!(owned.collect::<Vec<_>>(), vec![10, 20, 30, 40]);
+}
+
+#[test]
+fn clone_eq_and_append() {
+    let mut first: RawDeque<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    let mut second = first.clone();
+    assert_eq!(first, second);
+
+    second.push_back("c".to_string());
+    assert_ne!(first, second);
+
+    first.append(&mut second);
+    assert_eq!(contents(&first), ["a", "b", "a", "b", "c"]);
+    assert!(second.is_empty());
+    assert_eq!(second.iter().count(), 0);
+
+    let mut empty = RawDeque::new();
+    empty.append(&mut first);
+    assert_eq!(empty.len(), 5);
+    assert_eq!(empty.back().map(String::as_str), Some("c"));
+    empty.append(&mut RawDeque::new());
+    assert_eq!(empty.len(), 5);
+}
+
+#[test]
+fn cursor_moves_and_wraps_around_the_ghost_position() {
+    let mut deque: RawDeque<i32> = (1..=3).collect();
+    let mut cursor = deque.cursor_front_mut();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 2));
+
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 3));
+    assert_eq!(cursor.index(), Some(2));
+
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), Some(&mut 3));
+
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&mut 3));
+    assert_eq!(cursor.index(), Some(2));
+
+    let mut empty: RawDeque<i32> = RawDeque::new();
+    let mut cursor = empty.cursor_back_mut();
+    assert_eq!(cursor.current(), None);
+    cursor.move_next();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+}
+
+#[test]
+fn cursor_inserts_and_removes() {
+    let mut deque: RawDeque<i32> = vec![1, 3].into_iter().collect();
+    let mut cursor = deque.cursor_front_mut();
+    cursor.insert_after(2);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.insert_before(0);
+    assert_eq!(cursor.index(), Some(1));
+
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 3));
+    cursor.insert_after(4);
+    assert_eq!(contents(cursor.into_deque()), vec![0, 1, 2, 3, 4]);
+    assert_eq!(deque.back(), Some(&4));
+
+    let mut cursor = deque.cursor_front_mut();
+    assert_eq!(cursor.remove_current(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.current(), Some(&mut 3));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(4));
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.remove_current(), None);
+
+    // on the ghost position insertions go to the ends
+    cursor.insert_after(0);
+    cursor.insert_before(5);
+    assert_eq!(cursor.index(), None);
+    assert_eq!(contents(&deque), vec![0, 1, 3, 5]);
+    assert_eq!(deque.len(), 4);
+}
+
+#[test]
+fn cursor_splits() {
+    let mut deque: RawDeque<i32> = (0..6).collect();
+    let mut cursor = deque.cursor_front_mut();
+    cursor.move_next();
+    cursor.move_next();
+    let after = cursor.split_after();
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(contents(&after), vec![3, 4, 5]);
+    assert_eq!(after.len(), 3);
+    assert_eq!(after.front(), Some(&3));
+    assert_eq!(after.back(), Some(&5));
+
+    let before = cursor.split_before();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(contents(&before), vec![0, 1]);
+    assert_eq!(before.len(), 2);
+    assert_eq!(contents(&deque), vec![2]);
+    assert_eq!(deque.len(), 1);
+    assert_eq!(deque.front(), deque.back());
+
+    // splitting at the ends gives an empty deque
+    let mut cursor = deque.cursor_front_mut();
+    assert!(cursor.split_after().is_empty());
+    assert!(cursor.split_before().is_empty());
+
+    // on the ghost position everything moves
+    cursor.move_next();
+    let all = cursor.split_after();
+    assert_eq!(contents(&all), vec![2]);
+    assert!(deque.is_empty());
+
+    // the split halves are independent deques
+    let mut after = after;
+    after.push_front(-1);
+    after.pop_back();
+    assert_eq!(contents(&after), vec![-1, 3, 4]);
+}
+
+// Counts the drops of its values, to check that every element is dropped exactly once.
+struct DropCounter(Rc<Cell<usize>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn every_element_is_dropped_exactly_once() {
+    let drops = Rc::new(Cell::new(0));
+    let mut deque: RawDeque<DropCounter> = (0..10).map(|_| DropCounter(Rc::clone(&drops))).collect();
+
+    drop(deque.pop_front());
+    drop(deque.pop_back());
+    assert_eq!(drops.get(), 2);
+
+    let mut cursor = deque.cursor_front_mut();
+    cursor.move_next();
+    drop(cursor.remove_current());
+    let split = cursor.split_after();
+    assert_eq!(drops.get(), 3);
+    assert_eq!(deque.len(), 2);
+    assert_eq!(split.len(), 5);
+
+    drop(split);
+    assert_eq!(drops.get(), 8);
+
+    // a partially consumed IntoIter drops the rest
+    let mut iter = deque.into_iter();
+    drop(iter.next());
+    drop(iter);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn can_be_sent_to_another_thread() {
+    let deque: RawDeque<i32> = (1..=3).collect();
+    let sum = std::thread::spawn(move || deque.iter().sum::<i32>()).join().unwrap();
+    assert_eq!(sum, 6);
+}
@@ -0,0 +1,252 @@
+// Tests for the hand-written MyRc and MyArc.
+//
+// The proptests run the same random sequence of operations on MyRc and std::rc::Rc (and on MyArc and
+// std::sync::Arc): after every operation the values and the strong/weak counts of every pointer must match.
+// The atomics of MyArc are also model checked with loom, see 'loom_my_arc.rs'.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use proptest::prelude::*;
+use rust_basics::my_arc::{self, MyArc};
+use rust_basics::my_rc::{self, MyRc};
+
+#[derive(Debug, Clone)]
+enum Op {
+    New(i32),
+    Clone(usize),
+    Drop(usize),
+    Downgrade(usize),
+    CloneWeak(usize),
+    DropWeak(usize),
+    Upgrade(usize),
+    GetMut(usize),
+    MakeMut(usize),
+    TryUnwrap(usize),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let index = 0..16usize;
+    prop_oneof![
+        any::<i32>().prop_map(Op::New),
+        index.clone().prop_map(Op::Clone),
+        index.clone().prop_map(Op::Drop),
+        index.clone().prop_map(Op::Downgrade),
+        index.clone().prop_map(Op::CloneWeak),
+        index.clone().prop_map(Op::DropWeak),
+        index.clone().prop_map(Op::Upgrade),
+        index.clone().prop_map(Op::GetMut),
+        index.clone().prop_map(Op::MakeMut),
+        index.prop_map(Op::TryUnwrap),
+    ]
+}
+
+// Generates a function running the operations on our pointer and on the std one side by side.
+macro_rules! compare_with_std {
+    ($name:ident, $mine:ident, $my_weak:ty, $std:ident, $std_weak:ty) => {
+        fn $name(ops: Vec<Op>) -> Result<(), TestCaseError> {
+            let mut mine: Vec<$mine<i32>> = Vec::new();
+            let mut theirs: Vec<$std<i32>> = Vec::new();
+            let mut my_weaks: Vec<$my_weak> = Vec::new();
+            let mut std_weaks: Vec<$std_weak> = Vec::new();
+
+            for op in ops {
+                let strong = mine.len().max(1);
+                let weak = my_weaks.len().max(1);
+                match op {
+                    Op::New(value) => {
+                        mine.push($mine::new(value));
+                        theirs.push($std::new(value));
+                    }
+                    _ if mine.is_empty() && my_weaks.is_empty() => {}
+                    Op::Clone(i) if !mine.is_empty() => {
+                        mine.push(mine[i % strong].clone());
+                        theirs.push(theirs[i % strong].clone());
+                    }
+                    Op::Drop(i) if !mine.is_empty() => {
+                        drop(mine.swap_remove(i % strong));
+                        drop(theirs.swap_remove(i % strong));
+                    }
+                    Op::Downgrade(i) if !mine.is_empty() => {
+                        my_weaks.push($mine::downgrade(&mine[i % strong]));
+                        std_weaks.push($std::downgrade(&theirs[i % strong]));
+                    }
+                    Op::CloneWeak(i) if !my_weaks.is_empty() => {
+                        my_weaks.push(my_weaks[i % weak].clone());
+                        std_weaks.push(std_weaks[i % weak].clone());
+                    }
+                    Op::DropWeak(i) if !my_weaks.is_empty() => {
+                        drop(my_weaks.swap_remove(i % weak));
+                        drop(std_weaks.swap_remove(i % weak));
+                    }
+                    Op::Upgrade(i) if !my_weaks.is_empty() => {
+                        match (my_weaks[i % weak].upgrade(), std_weaks[i % weak].upgrade()) {
+                            (Some(a), Some(b)) => {
+                                mine.push(a);
+                                theirs.push(b);
+                            }
+                            (None, None) => {}
+                            (a, b) => prop_assert!(false, "upgrade: {:?} / {:?}", a, b),
+                        }
+                    }
+                    Op::GetMut(i) if !mine.is_empty() => {
+                        let a = $mine::get_mut(&mut mine[i % strong]).map(|value| {
+                            *value = value.wrapping_add(1);
+                            *value
+                        });
+                        let b = $std::get_mut(&mut theirs[i % strong]).map(|value| {
+                            *value = value.wrapping_add(1);
+                            *value
+                        });
+                        prop_assert_eq!(a, b);
+                    }
+                    Op::MakeMut(i) if !mine.is_empty() => {
+                        let a = $mine::make_mut(&mut mine[i % strong]);
+                        *a = a.wrapping_mul(3);
+                        let b = $std::make_mut(&mut theirs[i % strong]);
+                        *b = b.wrapping_mul(3);
+                    }
+                    Op::TryUnwrap(i) if !mine.is_empty() => {
+                        let a = $mine::try_unwrap(mine.swap_remove(i % strong));
+                        let b = $std::try_unwrap(theirs.swap_remove(i % strong));
+                        match (a, b) {
+                            (Ok(a), Ok(b)) => prop_assert_eq!(a, b),
+                            (Err(a), Err(b)) => {
+                                mine.push(a);
+                                theirs.push(b);
+                            }
+                            (a, b) => prop_assert!(false, "try_unwrap: {:?} / {:?}", a, b),
+                        }
+                    }
+                    _ => {}
+                }
+
+                for (a, b) in mine.iter().zip(&theirs) {
+                    prop_assert_eq!(**a, **b);
+                    prop_assert_eq!($mine::strong_count(a), $std::strong_count(b));
+                    prop_assert_eq!($mine::weak_count(a), $std::weak_count(b));
+                }
+                for (a, b) in my_weaks.iter().zip(&std_weaks) {
+                    prop_assert_eq!(a.strong_count(), b.strong_count());
+                    prop_assert_eq!(a.weak_count(), b.weak_count());
+                    prop_assert_eq!(a.upgrade().map(|value| *value), b.upgrade().map(|value| *value));
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+compare_with_std!(rc_sequence, MyRc, my_rc::MyWeak<i32>, Rc, std::rc::Weak<i32>);
+compare_with_std!(arc_sequence, MyArc, my_arc::MyWeak<i32>, Arc, std::sync::Weak<i32>);
+
+proptest! {
+    #[test]
+    fn my_rc_counts_match_std_rc(ops in prop::collection::vec(op(), 0..100)) {
+        rc_sequence(ops)?;
+    }
+
+    #[test]
+    fn my_arc_counts_match_std_arc(ops in prop::collection::vec(op(), 0..100)) {
+        arc_sequence(ops)?;
+    }
+}
+
+// Counts the drops of its values, to check that a value is dropped exactly once, at the right time.
+struct DropCounter<'a>(&'a Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn rc_value_is_dropped_with_the_last_strong_pointer() {
+    let drops = Cell::new(0);
+    let a = MyRc::new(DropCounter(&drops));
+    let b = a.clone();
+    let weak = MyRc::downgrade(&a);
+    assert_eq!(MyRc::strong_count(&a), 2);
+    assert_eq!(MyRc::weak_count(&a), 1);
+
+    drop(a);
+    assert_eq!(drops.get(), 0);
+    assert!(weak.upgrade().is_some());
+    drop(b);
+    assert_eq!(drops.get(), 1);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+    drop(weak);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn rc_try_unwrap_and_make_mut() {
+    let drops = Cell::new(0);
+    let a = MyRc::new(DropCounter(&drops));
+    let weak = MyRc::downgrade(&a);
+    let value = MyRc::try_unwrap(a).ok().unwrap();
+    assert!(weak.upgrade().is_none());
+    assert_eq!(drops.get(), 0);
+    drop(value);
+    assert_eq!(drops.get(), 1);
+
+    let mut a = MyRc::new(String::from("shared"));
+    let b = a.clone();
+    MyRc::make_mut(&mut a).push_str(" then copied");
+    assert_eq!(*b, "shared");
+    assert_eq!(*a, "shared then copied");
+    assert!(!MyRc::ptr_eq(&a, &b));
+
+    // with only a weak pointer, the value is moved out and the weak pointer is left behind
+    let weak = MyRc::downgrade(&a);
+    MyRc::make_mut(&mut a).push('!');
+    assert!(weak.upgrade().is_none());
+    assert_eq!(MyRc::weak_count(&a), 0);
+    assert_eq!(MyRc::get_mut(&mut a).map(|s| s.len()), Some(19));
+    assert_eq!(format!("{} {:?}", a, a), "shared then copied! \"shared then copied!\"");
+}
+
+#[test]
+fn arc_is_shared_between_threads() {
+    struct AtomicDropCounter(Arc<AtomicUsize>);
+
+    impl Drop for AtomicDropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let shared = MyArc::new(AtomicDropCounter(Arc::clone(&drops)));
+    let weak = MyArc::downgrade(&shared);
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let shared = shared.clone();
+            let weak = weak.clone();
+            thread::spawn(move || {
+                for _ in 0..1_000 {
+                    let copy = shared.clone();
+                    let upgraded = weak.upgrade().unwrap();
+                    let downgraded = MyArc::downgrade(&copy);
+                    drop((copy, upgraded, downgraded));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(MyArc::strong_count(&shared), 1);
+    assert_eq!(MyArc::weak_count(&shared), 1);
+    drop(shared);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    assert!(weak.upgrade().is_none());
+}
@@ -0,0 +1,208 @@
+// Golden-output tests for the lessons.
+//
+// Every lesson registered in `lessons::ALL` is run with its output captured (see src/output.rs),
+// and what it printed is compared with the snapshot file `tests/snapshots/<lesson>.snap`.
+//
+// When a change in a lesson output is intended, regenerate the snapshots with:
+//
+//     UPDATE_SNAPSHOTS=1 cargo test --test snapshots
+//
+// and review the changes in the .snap files before committing them.
+
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+
+use rust_basics::lessons::{self, Lesson};
+use rust_basics::output;
+
+// Lessons whose output depends on the way the process is launched, they are not snapshotted.
+const SKIPPED: &[(&str, &str)] = &[("cli", "reads the command line arguments of the process")];
+
+const CONTEXT_LINES: usize = 3;
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("snapshots")
+}
+
+// Some lessons create files in the current directory (see error_handling.rs), so they run
+// in an empty scratch directory: their output must not depend on what a previous run left behind.
+fn scratch_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("lessons")
+}
+
+fn update_mode() -> bool {
+    env::var_os("UPDATE_SNAPSHOTS").is_some_and(|value| value != "0")
+}
+
+// Runs the lesson and renders what it printed as the content of its snapshot file.
+fn render(lesson: &Lesson) -> Result<String, String> {
+    let captured = panic::catch_unwind(|| output::capture(lesson.run))
+        .map_err(|_| format!("lesson '{}' panicked", lesson.name))?;
+    Ok(format!(
+        "--- stdout\n{}--- stderr\n{}",
+        normalize(&captured.stdout),
+        normalize(&captured.stderr)
+    ))
+}
+
+// Replaces the parts of the output that change from one run to another
+// (memory addresses, file descriptors, location of the checkout) with fixed placeholders.
+fn normalize(text: &str) -> String {
+    let text = text.replace(&*scratch_dir().to_string_lossy(), "[scratch]");
+    let text = text.replace(env!("CARGO_MANIFEST_DIR"), "[crate]");
+    let text = replace_after(&text, "0x", |c| c.is_ascii_hexdigit(), "[addr]");
+    replace_after(&text, "fd: ", |c| c.is_ascii_digit(), "[fd]")
+}
+
+// Replaces the run of characters matching `keep` that follows every occurrence of `prefix`.
+fn replace_after(text: &str, prefix: &str, keep: fn(char) -> bool, placeholder: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find(prefix) {
+        let (before, after) = rest.split_at(position + prefix.len());
+        result.push_str(before);
+        let end = after.find(|c: char| !keep(c)).unwrap_or(after.len());
+        if end > 0 {
+            result.push_str(placeholder);
+        }
+        rest = &after[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// Line based diff (longest common subsequence), printed with CONTEXT_LINES lines around each change.
+fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] = length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<(char, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = (0..lines.len()).filter(|&k| lines[k].0 != ' ').collect();
+    let mut output = String::new();
+    let mut last_printed: Option<usize> = None;
+    for (k, (marker, line)) in lines.iter().enumerate() {
+        let near_change = changed
+            .iter()
+            .any(|&c| k + CONTEXT_LINES >= c && k <= c + CONTEXT_LINES);
+        if !near_change {
+            continue;
+        }
+        if last_printed.is_some_and(|last| last + 1 != k) {
+            output.push_str("   ...\n");
+        }
+        output.push_str(&format!("{:>4} {} {}\n", k + 1, marker, line));
+        last_printed = Some(k);
+    }
+    output
+}
+
+#[test]
+fn lessons_match_their_snapshots() {
+    let dir = snapshot_dir();
+    let update = update_mode();
+    if update {
+        fs::create_dir_all(&dir).expect("cannot create the snapshot directory");
+    }
+
+    let scratch = scratch_dir();
+    if scratch.exists() {
+        fs::remove_dir_all(&scratch).expect("cannot clean the scratch directory");
+    }
+    fs::create_dir_all(&scratch).expect("cannot create the scratch directory");
+    // NOTE: this changes the directory of the whole test process, the other tests of this file don't touch files.
+    env::set_current_dir(&scratch).expect("cannot enter the scratch directory");
+
+    let mut failures = Vec::new();
+    for lesson in lessons::ALL {
+        if SKIPPED.iter().any(|(name, _)| *name == lesson.name) {
+            continue;
+        }
+        let actual = match render(lesson) {
+            Ok(actual) => actual,
+            Err(error) => {
+                failures.push(error);
+                continue;
+            }
+        };
+        let path = dir.join(format!("{}.snap", lesson.name));
+
+        if update {
+            fs::write(&path, &actual).expect("cannot write the snapshot file");
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "lesson '{}' does not match {}\n(- expected, + actual)\n{}",
+                lesson.name,
+                path.display(),
+                diff(&expected, &actual)
+            )),
+            Err(_) => failures.push(format!(
+                "lesson '{}' has no snapshot, expected {}",
+                lesson.name,
+                path.display()
+            )),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nIf the changes are intended, run `UPDATE_SNAPSHOTS=1 cargo test --test snapshots`",
+        failures.join("\n\n")
+    );
+}
+
+#[test]
+fn skipped_lessons_exist() {
+    for (name, reason) in SKIPPED {
+        assert!(lessons::find(name).is_some(), "skipped lesson '{}' ({}) is not registered", name, reason);
+    }
+}
+
+#[test]
+fn normalize_replaces_unstable_values() {
+    assert_eq!(
+        normalize("ptr 0x7ffd5e8c, File { fd: 3, path: \"x\" }, 0x"),
+        "ptr 0x[addr], File { fd: [fd], path: \"x\" }, 0x"
+    );
+}
+
+#[test]
+fn diff_shows_changed_lines_with_context() {
+    let expected = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+    let actual = "a\nb\nc\nd\ne\nF\ng\nh\ni\n";
+    assert_eq!(
+        diff(expected, actual),
+        "   3   c\n   4   d\n   5   e\n   6 - f\n   7 + F\n   8   g\n   9   h\n  10   i\n"
+    );
+}
@@ -0,0 +1,97 @@
+use std::cell::RefCell;
+
+use rust_basics::tracked_rc::{self, TrackedRc, TrackedWeak};
+
+#[test]
+fn live_values_are_reported_until_dropped() {
+    let checkpoint = tracked_rc::checkpoint();
+    let a = TrackedRc::new("a", 1);
+    let b = TrackedRc::new("b", String::from("two"));
+    let a2 = a.clone();
+    let weak = TrackedRc::downgrade(&a);
+
+    assert_eq!(TrackedRc::strong_count(&a), 2);
+    assert_eq!(TrackedRc::weak_count(&a), 1);
+    assert_eq!(TrackedRc::label(&b), "b");
+    assert_eq!(*b, "two");
+
+    let report = checkpoint.report_leaks();
+    assert_eq!(report.len(), 2);
+    assert_eq!((report[0].label.as_str(), report[0].strong, report[0].weak), ("a", 2, 1));
+    assert_eq!(report[1].type_name, "alloc::string::String");
+    assert_eq!(report[1].to_string(), "b (alloc::string::String): strong = 1, weak = 0");
+
+    drop(a);
+    drop(b);
+    assert_eq!(checkpoint.report_leaks().len(), 1);
+    drop(a2);
+    assert!(checkpoint.report_leaks().is_empty());
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.strong_count(), 0);
+}
+
+#[test]
+fn checkpoints_only_see_newer_values() {
+    let old = TrackedRc::new("old", ());
+    let checkpoint = tracked_rc::checkpoint();
+    let new = TrackedRc::new("new", ());
+
+    let labels = |report: Vec<tracked_rc::LiveValue>| report.into_iter().map(|value| value.label).collect::<Vec<_>>();
+    assert_eq!(labels(checkpoint.report_leaks()), vec!["new"]);
+    assert_eq!(labels(tracked_rc::report_leaks()), vec!["old", "new"]);
+    drop((old, new));
+    assert!(tracked_rc::report_leaks().is_empty());
+}
+
+struct Node {
+    next: RefCell<Option<TrackedRc<Node>>>,
+    previous: RefCell<TrackedWeak<Node>>,
+}
+
+fn node(label: &str) -> TrackedRc<Node> {
+    TrackedRc::new(label, Node { next: RefCell::new(None), previous: RefCell::new(TrackedWeak::new()) })
+}
+
+#[test]
+fn strong_cycles_are_reported_as_leaks() {
+    let checkpoint = tracked_rc::checkpoint();
+    let a = node("a");
+    let b = node("b");
+    *a.next.borrow_mut() = Some(b.clone());
+    *b.next.borrow_mut() = Some(a.clone());
+    let spy = TrackedRc::downgrade(&a);
+    drop((a, b));
+
+    // both nodes are unreachable from our variables, but alive
+    let leaks = checkpoint.report_leaks();
+    assert_eq!(leaks.iter().map(|value| (value.label.as_str(), value.strong)).collect::<Vec<_>>(), vec![("a", 1), ("b", 1)]);
+
+    // breaking the cycle frees them
+    let a = spy.upgrade().unwrap();
+    a.next.borrow_mut().take();
+    drop(a);
+    assert!(checkpoint.report_leaks().is_empty());
+}
+
+#[test]
+fn weak_back_pointers_do_not_leak() {
+    let checkpoint = tracked_rc::checkpoint();
+    let a = node("a");
+    let b = node("b");
+    *a.next.borrow_mut() = Some(b.clone());
+    *b.previous.borrow_mut() = TrackedRc::downgrade(&a);
+    assert_eq!(TrackedRc::weak_count(&a), 1);
+    assert!(TrackedRc::ptr_eq(&b.previous.borrow().upgrade().unwrap(), &a));
+    drop((a, b));
+    assert!(checkpoint.report_leaks().is_empty());
+}
+
+#[test]
+fn registry_is_per_thread() {
+    let _here = TrackedRc::new("main", 0);
+    let there = std::thread::spawn(|| {
+        let _value = TrackedRc::new("other thread", 0);
+        tracked_rc::report_leaks().len()
+    });
+    assert_eq!(there.join().unwrap(), 1);
+}